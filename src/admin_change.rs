@@ -0,0 +1,188 @@
+use crate::*;
+
+pub type AdminProposalIndex = u32;
+
+/// The default delay between proposing an admin change and accepting it.
+pub const DEFAULT_ADMIN_CHANGE_DELAY_SEC: TimestampSec = 24 * 60 * 60;
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(tag = "kind", rename_all = "snake_case")]
+#[cfg_attr(not(target_arch = "wasm32"), derive(PartialEq))]
+pub enum AdminChange {
+    GrantRoles {
+        roles: Vec<Role>,
+        account_ids: Vec<ValidAccountId>,
+    },
+    RevokeRoles {
+        roles: Vec<Role>,
+        account_ids: Vec<ValidAccountId>,
+    },
+    SetDelay {
+        admin_change_delay_sec: TimestampSec,
+    },
+    SetTerminationApprovalConfig {
+        config: Option<TerminationApprovalConfig>,
+    },
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug, PartialEq, Deserialize))]
+pub struct AdminProposal {
+    pub proposer_id: AccountId,
+    pub change: AdminChange,
+    /// The change can't be accepted before this timestamp.
+    pub accept_timestamp: TimestampSec,
+}
+
+impl Contract {
+    /// Stores the change as a pending proposal. Without a delay it's accepted right away,
+    /// since there is no window to cancel it in. Revoking admins always has to be accepted,
+    /// so one admin can't remove the others in a single call.
+    pub(crate) fn internal_propose_admin_change(
+        &mut self,
+        change: AdminChange,
+    ) -> AdminProposalIndex {
+        if let AdminChange::SetTerminationApprovalConfig {
+            config: Some(config),
+        } = &change
+        {
+            config.assert_valid();
+        }
+        let proposer_id = env::predecessor_account_id();
+        let revokes_admins = matches!(
+            &change,
+            AdminChange::RevokeRoles { roles, .. } if roles.contains(&Role::Admin)
+        );
+        let proposal = AdminProposal {
+            proposer_id: proposer_id.clone(),
+            change,
+            accept_timestamp: current_timestamp_sec() + self.admin_change_delay_sec,
+        };
+        let index = self.next_admin_proposal_id;
+        self.next_admin_proposal_id += 1;
+        self.admin_proposals.insert(&index, &proposal);
+
+        emit(EventKind::FtLockupProposeAdminChange(
+            FtLockupProposeAdminChange {
+                id: index,
+                proposer_id,
+                change: proposal.change,
+                accept_timestamp: proposal.accept_timestamp,
+            },
+        ));
+
+        if self.admin_change_delay_sec == 0 && !revokes_admins {
+            self.internal_accept_admin_change(index);
+        }
+        index
+    }
+
+    fn internal_accept_admin_change(&mut self, proposal_id: AdminProposalIndex) {
+        let proposal = self
+            .admin_proposals
+            .remove(&proposal_id)
+            .expect("Proposal not found");
+        assert!(
+            current_timestamp_sec() >= proposal.accept_timestamp,
+            "The proposal can't be accepted before {}",
+            proposal.accept_timestamp
+        );
+
+        emit(EventKind::FtLockupAcceptAdminChange(
+            FtLockupAcceptAdminChange {
+                id: proposal_id,
+                account_id: env::predecessor_account_id(),
+            },
+        ));
+
+        match proposal.change {
+            AdminChange::GrantRoles { roles, account_ids } => {
+                let event_account_ids: Vec<AccountId> =
+                    account_ids.iter().map(|x| x.to_string()).collect();
+                for role in roles {
+                    self.internal_grant_role(role, &account_ids);
+                    // the deposit whitelist event is kept for the existing indexers
+                    if role == Role::Depositor {
+                        emit(EventKind::FtLockupAddToDepositWhitelist(
+                            FtLockupAddToDepositWhitelist {
+                                account_ids: event_account_ids.clone(),
+                            },
+                        ));
+                    }
+                    emit(EventKind::FtLockupGrantRole(FtLockupGrantRole {
+                        role,
+                        account_ids: event_account_ids.clone(),
+                    }));
+                }
+            }
+            AdminChange::RevokeRoles { roles, account_ids } => {
+                let event_account_ids: Vec<AccountId> =
+                    account_ids.iter().map(|x| x.to_string()).collect();
+                for role in roles {
+                    self.internal_revoke_role(role, &account_ids);
+                    if role == Role::Depositor {
+                        emit(EventKind::FtLockupRemoveFromDepositWhitelist(
+                            FtLockupRemoveFromDepositWhitelist {
+                                account_ids: event_account_ids.clone(),
+                            },
+                        ));
+                    }
+                    emit(EventKind::FtLockupRevokeRole(FtLockupRevokeRole {
+                        role,
+                        account_ids: event_account_ids.clone(),
+                    }));
+                }
+            }
+            AdminChange::SetDelay {
+                admin_change_delay_sec,
+            } => {
+                self.admin_change_delay_sec = admin_change_delay_sec;
+            }
+            AdminChange::SetTerminationApprovalConfig { config } => {
+                self.termination_approval_config = config.clone();
+                emit(EventKind::FtLockupSetTerminationApprovalConfig(
+                    FtLockupSetTerminationApprovalConfig { config },
+                ));
+            }
+        }
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Proposes a change of the roles, the termination approvals or the delay itself.
+    /// It can be accepted by an admin once the delay passes.
+    #[payable]
+    pub fn propose_admin_change(&mut self, change: AdminChange) -> AdminProposalIndex {
+        assert_one_yocto();
+        self.assert_admin(&env::predecessor_account_id());
+        self.internal_propose_admin_change(change)
+    }
+
+    #[payable]
+    pub fn accept_admin_change(&mut self, proposal_id: AdminProposalIndex) {
+        assert_one_yocto();
+        self.assert_admin(&env::predecessor_account_id());
+        self.internal_accept_admin_change(proposal_id);
+    }
+
+    /// Any admin can cancel a pending proposal.
+    #[payable]
+    pub fn cancel_admin_change(&mut self, proposal_id: AdminProposalIndex) {
+        assert_one_yocto();
+        let account_id = env::predecessor_account_id();
+        self.assert_admin(&account_id);
+        self.admin_proposals
+            .remove(&proposal_id)
+            .expect("Proposal not found");
+
+        emit(EventKind::FtLockupCancelAdminChange(
+            FtLockupCancelAdminChange {
+                id: proposal_id,
+                account_id,
+            },
+        ));
+    }
+}
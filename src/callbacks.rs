@@ -5,12 +5,18 @@ trait SelfCallbacks {
         &mut self,
         account_id: AccountId,
         lockup_claims: Vec<LockupClaim>,
+        token_account_id: TokenAccountId,
+        operator_id: Option<AccountId>,
     ) -> WrappedBalance;
 
+    fn after_ft_transfers(&mut self) -> WrappedBalance;
+
     fn after_lockup_termination(
         &mut self,
         account_id: AccountId,
         amount: WrappedBalance,
+        token_account_id: TokenAccountId,
+        payer_id: Option<AccountId>,
     ) -> WrappedBalance;
 }
 
@@ -21,59 +27,145 @@ impl SelfCallbacks for Contract {
         &mut self,
         account_id: AccountId,
         lockup_claims: Vec<LockupClaim>,
+        token_account_id: TokenAccountId,
+        operator_id: Option<AccountId>,
     ) -> WrappedBalance {
-        let promise_success = is_promise_success();
-        let mut total_balance = 0;
-        if promise_success {
-            let mut remove_indices = vec![];
-            let mut events: Vec<FtLockupClaimLockup> = vec![];
-            for LockupClaim {
-                index,
-                is_final,
-                claim_amount,
-            } in lockup_claims
-            {
-                if is_final {
-                    remove_indices.push(index);
-                }
-                total_balance += claim_amount.0;
-                let event = FtLockupClaimLockup {
-                    id: index,
-                    amount: claim_amount,
-                };
-                events.push(event);
+        let total_claim_amount: Balance = lockup_claims
+            .iter()
+            .map(|lockup_claim| lockup_claim.claim_amount.0)
+            .sum();
+        let transferred_amount = match env::promise_result(0) {
+            // `ft_transfer_call` returns the used amount, `ft_transfer` returns nothing
+            PromiseResult::Successful(value) if !value.is_empty() => {
+                serde_json::from_slice::<WrappedBalance>(&value)
+                    .map(|used_amount| std::cmp::min(used_amount.0, total_claim_amount))
+                    .unwrap_or(total_claim_amount)
             }
-            if !remove_indices.is_empty() {
-                let mut indices = self.account_lockups.get(&account_id).unwrap_or_default();
-                for index in remove_indices {
-                    indices.remove(&index);
+            PromiseResult::Successful(_) => total_claim_amount,
+            _ => {
+                log!("Token transfer has failed. Refunding.");
+                0
+            }
+        };
+
+        let mut refund_amount = total_claim_amount - transferred_amount;
+        // gives back the operator allowance spent on the refunded part,
+        // unless the operator was revoked in the meantime
+        if let Some(operator_id) = operator_id.as_ref() {
+            let mut operators = self.claim_operators.get(&account_id).unwrap_or_default();
+            if let Some(Some(allowances)) = operators.get_mut(operator_id) {
+                if let Some(allowance) = allowances.get_mut(&token_account_id) {
+                    if refund_amount > 0 {
+                        *allowance += refund_amount;
+                        self.internal_save_claim_operators(&account_id, operators);
+                    }
                 }
-                self.internal_save_account_lockups(&account_id, indices);
             }
-            emit(EventKind::FtLockupClaimLockup(events));
-        } else {
-            log!("Token transfer has failed. Refunding.");
-            let mut modified = false;
-            let mut indices = self.account_lockups.get(&account_id).unwrap_or_default();
-            for LockupClaim {
-                index,
-                claim_amount,
-                ..
-            } in lockup_claims
-            {
-                if indices.insert(index) {
-                    modified = true;
+        }
+        if refund_amount > 0 && transferred_amount > 0 {
+            log!("Refunding {} unused by the receiver", refund_amount);
+        }
+        let mut modified = false;
+        let mut indices = self.account_lockups.get(&account_id).unwrap_or_default();
+        let mut events: Vec<FtLockupClaimLockup> = vec![];
+        // the part of the refund that can't be restored to the claimed lockups,
+        // the storage payer of the first such lockup pays for the refund lockup
+        let mut unrestored_amount = 0;
+        let mut unrestored_payer_id: Option<AccountId> = None;
+        // the refund restores the claimed balance starting from the last claimed lockup
+        for LockupClaim {
+            index,
+            is_final,
+            claim_amount,
+        } in lockup_claims.into_iter().rev()
+        {
+            let lockup_refund_amount = std::cmp::min(refund_amount, claim_amount.0);
+            let mut lockup = self.lockups.get(index as _).unwrap();
+            // the lockup may have been transferred or split while the claim was in flight
+            let is_owned = lockup.account_id.as_ref() == &account_id;
+            if lockup_refund_amount > 0 {
+                refund_amount -= lockup_refund_amount;
+                let restored_amount = if is_owned {
+                    std::cmp::min(lockup_refund_amount, lockup.claimed_balance)
+                } else {
+                    0
+                };
+                if restored_amount > 0 {
+                    lockup.claimed_balance -= restored_amount;
+                    self.lockups.replace(index as _, &lockup);
+                    if indices.insert(index) {
+                        modified = true;
+                    }
                 }
-                let mut lockup = self.lockups.get(index as _).unwrap();
-                lockup.claimed_balance -= claim_amount.0;
-                self.lockups.replace(index as _, &lockup);
+                if lockup_refund_amount > restored_amount {
+                    unrestored_amount += lockup_refund_amount - restored_amount;
+                    unrestored_payer_id = unrestored_payer_id
+                        .or_else(|| self.internal_storage_payer(&StorageStakeId::Lockup(index)));
+                }
+            } else if is_final && is_owned && indices.remove(&index) {
+                modified = true;
+                self.internal_release_storage(StorageStakeId::Lockup(index));
             }
-
-            if modified {
-                self.internal_save_account_lockups(&account_id, indices);
+            let amount = claim_amount.0 - lockup_refund_amount;
+            if amount > 0 {
+                events.push(FtLockupClaimLockup {
+                    id: index,
+                    amount: amount.into(),
+                    token_account_id: token_account_id.clone(),
+                    operator_id: operator_id.clone(),
+                });
             }
         }
-        total_balance.into()
+        if modified {
+            self.internal_save_account_lockups(&account_id, indices);
+        }
+        if unrestored_amount > 0 {
+            // There is no internal balance, so instead we create a new lockup.
+            let lockup = Lockup::new_unlocked_since(
+                account_id,
+                unrestored_amount,
+                current_timestamp_sec(),
+                token_account_id.clone(),
+            );
+            let initial_storage_usage = env::storage_usage();
+            let lockup_index = self.internal_add_lockup(&lockup);
+            // the callback can't fail, so the storage stays unpaid for if the payer can't cover it
+            if let Some(payer_id) = unrestored_payer_id {
+                if let Err(err) = self.internal_try_stake_storage(
+                    StorageStakeId::Lockup(lockup_index),
+                    &payer_id,
+                    initial_storage_usage,
+                ) {
+                    log!(
+                        "The storage of lockup #{} is not paid for: {}",
+                        lockup_index,
+                        err
+                    );
+                }
+            }
+            let event: FtLockupCreateLockup = (lockup_index, lockup, None).into();
+            emit(EventKind::FtLockupCreateLockup(vec![event]));
+        }
+        if !events.is_empty() {
+            events.reverse();
+            emit(EventKind::FtLockupClaimLockup(events));
+        }
+        transferred_amount.into()
+    }
+
+    #[private]
+    fn after_ft_transfers(&mut self) -> WrappedBalance {
+        (0..env::promise_results_count())
+            .map(|index| match env::promise_result(index) {
+                PromiseResult::Successful(value) => {
+                    serde_json::from_slice::<WrappedBalance>(&value)
+                        .map(|amount| amount.0)
+                        .unwrap_or(0)
+                }
+                _ => 0,
+            })
+            .sum::<Balance>()
+            .into()
     }
 
     #[private]
@@ -81,18 +173,50 @@ impl SelfCallbacks for Contract {
         &mut self,
         account_id: AccountId,
         amount: WrappedBalance,
+        token_account_id: TokenAccountId,
+        payer_id: Option<AccountId>,
     ) -> WrappedBalance {
-        let promise_success = is_promise_success();
-        if !promise_success {
-            log!("Lockup termination transfer has failed.");
+        let transferred_amount = match env::promise_result(0) {
+            // `ft_transfer_call` returns the used amount, `ft_transfer` returns nothing
+            PromiseResult::Successful(value) if !value.is_empty() => {
+                serde_json::from_slice::<WrappedBalance>(&value)
+                    .map(|used_amount| std::cmp::min(used_amount.0, amount.0))
+                    .unwrap_or(amount.0)
+            }
+            PromiseResult::Successful(_) => amount.0,
+            _ => {
+                log!("Lockup termination transfer has failed.");
+                0
+            }
+        };
+        let refund_amount = amount.0 - transferred_amount;
+        if refund_amount > 0 {
             // There is no internal balance, so instead we create a new lockup.
-            let lockup = Lockup::new_unlocked_since(account_id, amount.0, current_timestamp_sec());
+            let lockup = Lockup::new_unlocked_since(
+                account_id,
+                refund_amount,
+                current_timestamp_sec(),
+                token_account_id,
+            );
+            let initial_storage_usage = env::storage_usage();
             let lockup_index = self.internal_add_lockup(&lockup);
+            // the callback can't fail, so the storage stays unpaid for if the payer can't cover it
+            if let Some(payer_id) = payer_id {
+                if let Err(err) = self.internal_try_stake_storage(
+                    StorageStakeId::Lockup(lockup_index),
+                    &payer_id,
+                    initial_storage_usage,
+                ) {
+                    log!(
+                        "The storage of lockup #{} is not paid for: {}",
+                        lockup_index,
+                        err
+                    );
+                }
+            }
             let event: FtLockupCreateLockup = (lockup_index, lockup, None).into();
             emit(EventKind::FtLockupCreateLockup(vec![event]));
-            0.into()
-        } else {
-            amount
         }
+        transferred_amount.into()
     }
 }
@@ -17,10 +17,15 @@ impl Draft {
     }
 
     pub fn assert_new_valid(&self) {
+        assert!(
+            self.lockup_create.clawback_receiver.is_none(),
+            "The clawback receiver of the drafts is set by the draft group funding"
+        );
         let amount = self.lockup_create.schedule.total_balance();
         // any valid near account id will work fine here as a parameter
+        let account_id = env::predecessor_account_id();
         self.lockup_create
-            .into_lockup(&env::predecessor_account_id().try_into().unwrap())
+            .into_lockup(&account_id.clone().try_into().unwrap(), &account_id)
             .assert_new_valid(amount);
     }
 }
@@ -31,6 +36,10 @@ pub struct DraftGroup {
     pub payer_id: Option<ValidAccountId>,
     pub draft_indices: HashSet<DraftIndex>,
     pub discarded: bool,
+    /// The token the group was funded with.
+    pub token_account_id: Option<TokenAccountId>,
+    /// Receives the unvested balance of the group lockups instead of the payer.
+    pub clawback_receiver: Option<ClawbackReceiver>,
 }
 
 impl DraftGroup {
@@ -40,6 +49,8 @@ impl DraftGroup {
             payer_id: None,
             draft_indices: HashSet::new(),
             discarded: false,
+            token_account_id: None,
+            clawback_receiver: None,
         }
     }
 
@@ -54,6 +65,17 @@ impl DraftGroup {
         );
     }
 
+    pub fn assert_can_edit_draft(&self) {
+        assert!(
+            !self.discarded,
+            "cannot edit draft, draft group is discarded"
+        );
+        assert!(
+            self.payer_id.is_none(),
+            "cannot edit draft, group already funded"
+        );
+    }
+
     pub fn assert_can_convert_draft(&self) {
         assert!(
             !self.discarded,
@@ -73,9 +95,16 @@ impl DraftGroup {
         assert!(self.payer_id.is_none(), "draft group already funded");
     }
 
-    pub fn fund(&mut self, payer_id: &ValidAccountId) {
+    pub fn fund(
+        &mut self,
+        payer_id: &ValidAccountId,
+        token_account_id: &TokenAccountId,
+        clawback_receiver: Option<ClawbackReceiver>,
+    ) {
         self.assert_can_fund();
         self.payer_id = Some(payer_id.clone());
+        self.token_account_id = Some(token_account_id.clone());
+        self.clawback_receiver = clawback_receiver;
     }
 
     pub fn assert_can_discard(&mut self) {
@@ -20,6 +20,18 @@ pub struct FtLockupRemoveFromDepositWhitelist {
     pub account_ids: Vec<AccountId>,
 }
 
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FtLockupAddToTokenWhitelist {
+    pub account_ids: Vec<AccountId>,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FtLockupRemoveFromTokenWhitelist {
+    pub account_ids: Vec<AccountId>,
+}
+
 #[derive(Serialize, Debug)]
 #[serde(crate = "near_sdk::serde")]
 pub struct FtLockupAddToDraftOperatorsWhitelist {
@@ -32,6 +44,113 @@ pub struct FtLockupRemoveFromDraftOperatorsWhitelist {
     pub account_ids: Vec<AccountId>,
 }
 
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FtLockupAddToGuardiansWhitelist {
+    pub account_ids: Vec<AccountId>,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FtLockupRemoveFromGuardiansWhitelist {
+    pub account_ids: Vec<AccountId>,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FtLockupGrantRole {
+    pub role: Role,
+    pub account_ids: Vec<AccountId>,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FtLockupRevokeRole {
+    pub role: Role,
+    pub account_ids: Vec<AccountId>,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FtLockupProposeAdminChange {
+    pub id: AdminProposalIndex,
+    pub proposer_id: AccountId,
+    pub change: AdminChange,
+    pub accept_timestamp: TimestampSec,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FtLockupAcceptAdminChange {
+    pub id: AdminProposalIndex,
+    pub account_id: AccountId,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FtLockupCancelAdminChange {
+    pub id: AdminProposalIndex,
+    pub account_id: AccountId,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FtLockupPause {
+    pub account_id: AccountId,
+    pub flags: Vec<PauseFlag>,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FtLockupUnpause {
+    pub account_id: AccountId,
+    pub flags: Vec<PauseFlag>,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FtLockupAddTerminationDelegates {
+    pub beneficiary_id: AccountId,
+    pub account_ids: Vec<AccountId>,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FtLockupRemoveTerminationDelegates {
+    pub beneficiary_id: AccountId,
+    pub account_ids: Vec<AccountId>,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FtLockupSetTerminationApprovalConfig {
+    pub config: Option<TerminationApprovalConfig>,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FtLockupRequestTermination {
+    pub id: TerminationRequestIndex,
+    pub lockup_id: LockupIndex,
+    pub requester_id: AccountId,
+    pub expiration_timestamp: TimestampSec,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FtLockupApproveTermination {
+    pub id: TerminationRequestIndex,
+    pub account_id: AccountId,
+    pub approvals: u32,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FtLockupCancelTerminationRequest {
+    pub id: TerminationRequestIndex,
+    pub account_id: AccountId,
+}
+
 #[derive(Serialize, Debug)]
 #[serde(crate = "near_sdk::serde")]
 pub struct FtLockupCreateLockup {
@@ -42,6 +161,7 @@ pub struct FtLockupCreateLockup {
     pub finish: TimestampSec,
     pub terminatable: bool,
     pub draft_id: Option<DraftIndex>,
+    pub token_account_id: TokenAccountId,
 }
 
 impl From<(LockupIndex, Lockup, Option<DraftIndex>)> for FtLockupCreateLockup {
@@ -55,6 +175,7 @@ impl From<(LockupIndex, Lockup, Option<DraftIndex>)> for FtLockupCreateLockup {
             finish: lockup.schedule.0.last().unwrap().timestamp,
             terminatable: lockup.termination_config.is_some(),
             draft_id,
+            token_account_id: lockup.token_account_id,
         }
     }
 }
@@ -64,6 +185,24 @@ impl From<(LockupIndex, Lockup, Option<DraftIndex>)> for FtLockupCreateLockup {
 pub struct FtLockupClaimLockup {
     pub id: LockupIndex,
     pub amount: WrappedBalance,
+    pub token_account_id: TokenAccountId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub operator_id: Option<AccountId>,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FtLockupApproveClaimOperator {
+    pub account_id: AccountId,
+    pub operator_id: AccountId,
+    pub allowances: Option<HashMap<TokenAccountId, WrappedBalance>>,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FtLockupRevokeClaimOperator {
+    pub account_id: AccountId,
+    pub operator_id: AccountId,
 }
 
 #[derive(Serialize, Debug)]
@@ -72,6 +211,67 @@ pub struct FtLockupTerminateLockup {
     pub id: LockupIndex,
     pub termination_timestamp: TimestampSec,
     pub unvested_balance: WrappedBalance,
+    pub token_account_id: TokenAccountId,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FtLockupScheduleTermination {
+    pub id: LockupIndex,
+    pub termination_timestamp: TimestampSec,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FtLockupCancelScheduledTermination {
+    pub id: LockupIndex,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FtLockupReduceLockup {
+    pub id: LockupIndex,
+    pub amount: WrappedBalance,
+    pub token_account_id: TokenAccountId,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FtLockupPauseVesting {
+    pub id: LockupIndex,
+    pub paused_since: TimestampSec,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FtLockupResumeVesting {
+    pub id: LockupIndex,
+    pub pause_duration: TimestampSec,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FtLockupTransferLockup {
+    pub id: LockupIndex,
+    pub account_id: AccountId,
+    pub new_account_id: AccountId,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FtLockupSplitLockup {
+    pub id: LockupIndex,
+    pub new_id: LockupIndex,
+    pub new_account_id: AccountId,
+    pub amount: WrappedBalance,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FtLockupMergeLockups {
+    pub id: LockupIndex,
+    pub account_id: AccountId,
+    pub source_ids: Vec<LockupIndex>,
 }
 
 #[derive(Serialize, Debug)]
@@ -107,11 +307,53 @@ impl From<(DraftIndex, Draft)> for FtLockupCreateDraft {
     }
 }
 
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FtLockupUpdateDraft {
+    pub id: DraftIndex,
+    pub draft_group_id: DraftGroupIndex,
+    pub account_id: AccountId,
+    pub balance: WrappedBalance,
+    pub start: TimestampSec,
+    pub finish: TimestampSec,
+    pub terminatable: bool,
+}
+
+impl From<(DraftIndex, Draft)> for FtLockupUpdateDraft {
+    fn from(tuple: (DraftIndex, Draft)) -> Self {
+        let FtLockupCreateDraft {
+            id,
+            draft_group_id,
+            account_id,
+            balance,
+            start,
+            finish,
+            terminatable,
+        } = tuple.into();
+        Self {
+            id,
+            draft_group_id,
+            account_id,
+            balance,
+            start,
+            finish,
+            terminatable,
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FtLockupRemoveDraft {
+    pub id: DraftIndex,
+}
+
 #[derive(Serialize, Debug)]
 #[serde(crate = "near_sdk::serde")]
 pub struct FtLockupFundDraftGroup {
     pub id: DraftGroupIndex,
     pub amount: WrappedBalance,
+    pub token_account_id: TokenAccountId,
 }
 
 #[derive(Serialize, Debug)]
@@ -126,6 +368,32 @@ pub struct FtLockupDeleteDraft {
     pub id: DraftIndex,
 }
 
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FtLockupStageUpgrade {
+    pub code_hash: Base58CryptoHash,
+    pub deploy_timestamp: TimestampSec,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FtLockupCancelUpgrade {
+    pub code_hash: Base58CryptoHash,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FtLockupDeployUpgrade {
+    pub code_hash: Base58CryptoHash,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FtLockupSetUpgradeDelay {
+    pub upgrade_delay_sec: TimestampSec,
+    pub effective_timestamp: TimestampSec,
+}
+
 #[derive(Serialize, Debug)]
 #[serde(crate = "near_sdk::serde")]
 #[serde(tag = "event", content = "data")]
@@ -134,16 +402,49 @@ pub(crate) enum EventKind {
     FtLockupNew(FtLockupNew),
     FtLockupAddToDepositWhitelist(FtLockupAddToDepositWhitelist),
     FtLockupRemoveFromDepositWhitelist(FtLockupRemoveFromDepositWhitelist),
+    FtLockupAddToTokenWhitelist(FtLockupAddToTokenWhitelist),
+    FtLockupRemoveFromTokenWhitelist(FtLockupRemoveFromTokenWhitelist),
     FtLockupAddToDraftOperatorsWhitelist(FtLockupAddToDraftOperatorsWhitelist),
     FtLockupRemoveFromDraftOperatorsWhitelist(FtLockupRemoveFromDraftOperatorsWhitelist),
+    FtLockupAddToGuardiansWhitelist(FtLockupAddToGuardiansWhitelist),
+    FtLockupRemoveFromGuardiansWhitelist(FtLockupRemoveFromGuardiansWhitelist),
+    FtLockupGrantRole(FtLockupGrantRole),
+    FtLockupRevokeRole(FtLockupRevokeRole),
+    FtLockupProposeAdminChange(FtLockupProposeAdminChange),
+    FtLockupAcceptAdminChange(FtLockupAcceptAdminChange),
+    FtLockupCancelAdminChange(FtLockupCancelAdminChange),
+    FtLockupPause(FtLockupPause),
+    FtLockupUnpause(FtLockupUnpause),
+    FtLockupAddTerminationDelegates(FtLockupAddTerminationDelegates),
+    FtLockupRemoveTerminationDelegates(FtLockupRemoveTerminationDelegates),
+    FtLockupSetTerminationApprovalConfig(FtLockupSetTerminationApprovalConfig),
+    FtLockupRequestTermination(FtLockupRequestTermination),
+    FtLockupApproveTermination(FtLockupApproveTermination),
+    FtLockupCancelTerminationRequest(FtLockupCancelTerminationRequest),
     FtLockupCreateLockup(Vec<FtLockupCreateLockup>),
     FtLockupClaimLockup(Vec<FtLockupClaimLockup>),
     FtLockupTerminateLockup(Vec<FtLockupTerminateLockup>),
+    FtLockupScheduleTermination(Vec<FtLockupScheduleTermination>),
+    FtLockupCancelScheduledTermination(Vec<FtLockupCancelScheduledTermination>),
+    FtLockupReduceLockup(Vec<FtLockupReduceLockup>),
+    FtLockupPauseVesting(Vec<FtLockupPauseVesting>),
+    FtLockupResumeVesting(Vec<FtLockupResumeVesting>),
+    FtLockupTransferLockup(Vec<FtLockupTransferLockup>),
+    FtLockupSplitLockup(Vec<FtLockupSplitLockup>),
+    FtLockupMergeLockups(Vec<FtLockupMergeLockups>),
+    FtLockupApproveClaimOperator(Vec<FtLockupApproveClaimOperator>),
+    FtLockupRevokeClaimOperator(Vec<FtLockupRevokeClaimOperator>),
     FtLockupCreateDraftGroup(Vec<FtLockupCreateDraftGroup>),
     FtLockupCreateDraft(Vec<FtLockupCreateDraft>),
+    FtLockupUpdateDraft(Vec<FtLockupUpdateDraft>),
+    FtLockupRemoveDraft(Vec<FtLockupRemoveDraft>),
     FtLockupFundDraftGroup(Vec<FtLockupFundDraftGroup>),
     FtLockupDiscardDraftGroup(Vec<FtLockupDiscardDraftGroup>),
     FtLockupDeleteDraft(Vec<FtLockupDeleteDraft>),
+    FtLockupStageUpgrade(FtLockupStageUpgrade),
+    FtLockupCancelUpgrade(FtLockupCancelUpgrade),
+    FtLockupDeployUpgrade(FtLockupDeployUpgrade),
+    FtLockupSetUpgradeDelay(FtLockupSetUpgradeDelay),
 }
 
 #[derive(Serialize, Debug)]
@@ -268,6 +569,58 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_ft_lockup_add_to_token_whitelist() {
+        testing_env!(get_context());
+
+        let account_ids: Vec<AccountId> = vec!["token.near", "usdc.near"]
+            .iter()
+            .map(|&x| x.into())
+            .collect();
+        emit(EventKind::FtLockupAddToTokenWhitelist(
+            FtLockupAddToTokenWhitelist { account_ids },
+        ));
+        assert_eq!(
+            test_utils::get_logs()[0],
+            format!(
+                r"EVENT_JSON:{}",
+                json!({
+                    "standard": PACKAGE_NAME,
+                    "version": VERSION,
+                    "event": "ft_lockup_add_to_token_whitelist",
+                    "data": { "account_ids": ["token.near", "usdc.near"] },
+                })
+                .to_string(),
+            )
+        );
+    }
+
+    #[test]
+    fn test_ft_lockup_remove_from_token_whitelist() {
+        testing_env!(get_context());
+
+        let account_ids: Vec<AccountId> = vec!["token.near", "usdc.near"]
+            .iter()
+            .map(|&x| x.into())
+            .collect();
+        emit(EventKind::FtLockupRemoveFromTokenWhitelist(
+            FtLockupRemoveFromTokenWhitelist { account_ids },
+        ));
+        assert_eq!(
+            test_utils::get_logs()[0],
+            format!(
+                r"EVENT_JSON:{}",
+                json!({
+                    "standard": PACKAGE_NAME,
+                    "version": VERSION,
+                    "event": "ft_lockup_remove_from_token_whitelist",
+                    "data": { "account_ids": ["token.near", "usdc.near"] },
+                })
+                .to_string(),
+            )
+        );
+    }
+
     #[test]
     fn test_ft_lockup_add_to_draft_operators_whitelist() {
         testing_env!(get_context());
@@ -321,19 +674,21 @@ mod tests {
     }
 
     #[test]
-    fn test_ft_lockup_create_lockup() {
+    fn test_ft_lockup_set_termination_approval_config() {
         testing_env!(get_context());
 
-        let account_id: AccountId = "alice.near".into();
-        let balance: WrappedBalance = 10_000.into();
-        let timestamp: TimestampSec = 1_500_000_000;
-        let lockup = Lockup::new_unlocked_since(account_id.clone(), balance.0, timestamp);
-        let lockup_id: LockupIndex = 100;
-        let draft_id: DraftIndex = 33;
-
-        let event: FtLockupCreateLockup = (100, lockup, Some(draft_id)).into();
-
-        emit(EventKind::FtLockupCreateLockup(vec![event]));
+        emit(EventKind::FtLockupSetTerminationApprovalConfig(
+            FtLockupSetTerminationApprovalConfig {
+                config: Some(TerminationApprovalConfig {
+                    approvers: vec![
+                        "alice.near".try_into().unwrap(),
+                        "bob.near".try_into().unwrap(),
+                    ],
+                    threshold: 2,
+                    request_duration_sec: 86400,
+                }),
+            },
+        ));
         assert_eq!(
             test_utils::get_logs()[0],
             format!(
@@ -341,18 +696,14 @@ mod tests {
                 json!({
                     "standard": PACKAGE_NAME,
                     "version": VERSION,
-                    "event": "ft_lockup_create_lockup",
-                    "data": [
-                        {
-                            "id": lockup_id,
-                            "account_id": account_id,
-                            "balance": balance,
-                            "start": timestamp - 1,
-                            "finish": timestamp,
-                            "terminatable": false,
-                            "draft_id": Some(draft_id),
+                    "event": "ft_lockup_set_termination_approval_config",
+                    "data": {
+                        "config": {
+                            "approvers": ["alice.near", "bob.near"],
+                            "threshold": 2,
+                            "request_duration_sec": 86400,
                         },
-                    ],
+                    },
                 })
                 .to_string(),
             )
@@ -360,18 +711,17 @@ mod tests {
     }
 
     #[test]
-    fn test_ft_lockup_claim_lockup() {
+    fn test_ft_lockup_request_termination() {
         testing_env!(get_context());
 
-        let lockup_id: LockupIndex = 100;
-        let amount: WrappedBalance = 10000.into();
-
-        let event = FtLockupClaimLockup {
-            id: lockup_id,
-            amount,
-        };
-
-        emit(EventKind::FtLockupClaimLockup(vec![event]));
+        emit(EventKind::FtLockupRequestTermination(
+            FtLockupRequestTermination {
+                id: 1,
+                lockup_id: 100,
+                requester_id: "owner.near".into(),
+                expiration_timestamp: 1_600_086_400,
+            },
+        ));
         assert_eq!(
             test_utils::get_logs()[0],
             format!(
@@ -379,11 +729,174 @@ mod tests {
                 json!({
                     "standard": PACKAGE_NAME,
                     "version": VERSION,
-                    "event": "ft_lockup_claim_lockup",
-                    "data": [
-                        {
+                    "event": "ft_lockup_request_termination",
+                    "data": {
+                        "id": 1,
+                        "lockup_id": 100,
+                        "requester_id": "owner.near",
+                        "expiration_timestamp": 1_600_086_400,
+                    },
+                })
+                .to_string(),
+            )
+        );
+    }
+
+    #[test]
+    fn test_ft_lockup_approve_termination() {
+        testing_env!(get_context());
+
+        emit(EventKind::FtLockupApproveTermination(
+            FtLockupApproveTermination {
+                id: 1,
+                account_id: "alice.near".into(),
+                approvals: 2,
+            },
+        ));
+        assert_eq!(
+            test_utils::get_logs()[0],
+            format!(
+                r"EVENT_JSON:{}",
+                json!({
+                    "standard": PACKAGE_NAME,
+                    "version": VERSION,
+                    "event": "ft_lockup_approve_termination",
+                    "data": { "id": 1, "account_id": "alice.near", "approvals": 2 },
+                })
+                .to_string(),
+            )
+        );
+    }
+
+    #[test]
+    fn test_ft_lockup_cancel_termination_request() {
+        testing_env!(get_context());
+
+        emit(EventKind::FtLockupCancelTerminationRequest(
+            FtLockupCancelTerminationRequest {
+                id: 1,
+                account_id: "owner.near".into(),
+            },
+        ));
+        assert_eq!(
+            test_utils::get_logs()[0],
+            format!(
+                r"EVENT_JSON:{}",
+                json!({
+                    "standard": PACKAGE_NAME,
+                    "version": VERSION,
+                    "event": "ft_lockup_cancel_termination_request",
+                    "data": { "id": 1, "account_id": "owner.near" },
+                })
+                .to_string(),
+            )
+        );
+    }
+
+    #[test]
+    fn test_ft_lockup_add_termination_delegates() {
+        testing_env!(get_context());
+
+        let beneficiary_id: AccountId = "owner.near".into();
+        let account_ids: Vec<AccountId> = vec!["alice.near", "bob.near"]
+            .iter()
+            .map(|&x| x.into())
+            .collect();
+        emit(EventKind::FtLockupAddTerminationDelegates(
+            FtLockupAddTerminationDelegates {
+                beneficiary_id,
+                account_ids,
+            },
+        ));
+        assert_eq!(
+            test_utils::get_logs()[0],
+            format!(
+                r"EVENT_JSON:{}",
+                json!({
+                    "standard": PACKAGE_NAME,
+                    "version": VERSION,
+                    "event": "ft_lockup_add_termination_delegates",
+                    "data": {
+                        "beneficiary_id": "owner.near",
+                        "account_ids": ["alice.near", "bob.near"],
+                    },
+                })
+                .to_string(),
+            )
+        );
+    }
+
+    #[test]
+    fn test_ft_lockup_remove_termination_delegates() {
+        testing_env!(get_context());
+
+        let beneficiary_id: AccountId = "owner.near".into();
+        let account_ids: Vec<AccountId> = vec!["alice.near", "bob.near"]
+            .iter()
+            .map(|&x| x.into())
+            .collect();
+        emit(EventKind::FtLockupRemoveTerminationDelegates(
+            FtLockupRemoveTerminationDelegates {
+                beneficiary_id,
+                account_ids,
+            },
+        ));
+        assert_eq!(
+            test_utils::get_logs()[0],
+            format!(
+                r"EVENT_JSON:{}",
+                json!({
+                    "standard": PACKAGE_NAME,
+                    "version": VERSION,
+                    "event": "ft_lockup_remove_termination_delegates",
+                    "data": {
+                        "beneficiary_id": "owner.near",
+                        "account_ids": ["alice.near", "bob.near"],
+                    },
+                })
+                .to_string(),
+            )
+        );
+    }
+
+    #[test]
+    fn test_ft_lockup_create_lockup() {
+        testing_env!(get_context());
+
+        let account_id: AccountId = "alice.near".into();
+        let balance: WrappedBalance = 10_000.into();
+        let timestamp: TimestampSec = 1_500_000_000;
+        let token_account_id: TokenAccountId = "token.near".into();
+        let lockup = Lockup::new_unlocked_since(
+            account_id.clone(),
+            balance.0,
+            timestamp,
+            token_account_id.clone(),
+        );
+        let lockup_id: LockupIndex = 100;
+        let draft_id: DraftIndex = 33;
+
+        let event: FtLockupCreateLockup = (100, lockup, Some(draft_id)).into();
+
+        emit(EventKind::FtLockupCreateLockup(vec![event]));
+        assert_eq!(
+            test_utils::get_logs()[0],
+            format!(
+                r"EVENT_JSON:{}",
+                json!({
+                    "standard": PACKAGE_NAME,
+                    "version": VERSION,
+                    "event": "ft_lockup_create_lockup",
+                    "data": [
+                        {
                             "id": lockup_id,
-                            "amount": amount,
+                            "account_id": account_id,
+                            "balance": balance,
+                            "start": timestamp - 1,
+                            "finish": timestamp,
+                            "terminatable": false,
+                            "draft_id": Some(draft_id),
+                            "token_account_id": token_account_id,
                         },
                     ],
                 })
@@ -393,20 +906,20 @@ mod tests {
     }
 
     #[test]
-    fn test_ft_lockup_terminate_lockup() {
+    fn test_ft_lockup_claim_lockup() {
         testing_env!(get_context());
 
         let lockup_id: LockupIndex = 100;
-        let termination_timestamp: TimestampSec = 1_800_000_000;
-        let unvested_balance: WrappedBalance = 10000.into();
+        let amount: WrappedBalance = 10000.into();
 
-        let event = FtLockupTerminateLockup {
+        let event = FtLockupClaimLockup {
             id: lockup_id,
-            termination_timestamp,
-            unvested_balance,
+            amount,
+            token_account_id: "token.near".into(),
+            operator_id: None,
         };
 
-        emit(EventKind::FtLockupTerminateLockup(vec![event]));
+        emit(EventKind::FtLockupClaimLockup(vec![event]));
         assert_eq!(
             test_utils::get_logs()[0],
             format!(
@@ -414,12 +927,12 @@ mod tests {
                 json!({
                     "standard": PACKAGE_NAME,
                     "version": VERSION,
-                    "event": "ft_lockup_terminate_lockup",
+                    "event": "ft_lockup_claim_lockup",
                     "data": [
                         {
                             "id": lockup_id,
-                            "termination_timestamp": termination_timestamp,
-                            "unvested_balance": unvested_balance,
+                            "amount": amount,
+                            "token_account_id": "token.near",
                         },
                     ],
                 })
@@ -429,14 +942,21 @@ mod tests {
     }
 
     #[test]
-    fn test_ft_lockup_create_draft_group() {
+    fn test_ft_lockup_claim_lockup_by_operator() {
         testing_env!(get_context());
 
-        let draft_group_id: DraftGroupIndex = 22;
+        let lockup_id: LockupIndex = 100;
+        let amount: WrappedBalance = 10000.into();
+        let operator_id: AccountId = "operator.near".into();
 
-        let event = FtLockupCreateDraftGroup { id: draft_group_id };
+        let event = FtLockupClaimLockup {
+            id: lockup_id,
+            amount,
+            token_account_id: "token.near".into(),
+            operator_id: Some(operator_id.clone()),
+        };
 
-        emit(EventKind::FtLockupCreateDraftGroup(vec![event]));
+        emit(EventKind::FtLockupClaimLockup(vec![event]));
         assert_eq!(
             test_utils::get_logs()[0],
             format!(
@@ -444,10 +964,13 @@ mod tests {
                 json!({
                     "standard": PACKAGE_NAME,
                     "version": VERSION,
-                    "event": "ft_lockup_create_draft_group",
+                    "event": "ft_lockup_claim_lockup",
                     "data": [
                         {
-                            "id": draft_group_id,
+                            "id": lockup_id,
+                            "amount": amount,
+                            "token_account_id": "token.near",
+                            "operator_id": operator_id,
                         },
                     ],
                 })
@@ -457,27 +980,25 @@ mod tests {
     }
 
     #[test]
-    fn test_ft_lockup_create_draft() {
+    fn test_ft_lockup_approve_claim_operator() {
         testing_env!(get_context());
 
-        let account_id: ValidAccountId = "alice.near".try_into().unwrap();
-        let balance: WrappedBalance = 10_000.into();
-        let timestamp: TimestampSec = 1_500_000_000;
-        let lockup_create = LockupCreate {
+        let account_id: AccountId = "alice.near".into();
+        let operator_id: AccountId = "operator.near".into();
+        let token_account_id: TokenAccountId = "token.near".into();
+        let allowance: WrappedBalance = 10000.into();
+
+        let event = FtLockupApproveClaimOperator {
             account_id: account_id.clone(),
-            schedule: Schedule::new_unlocked_since(balance.0, timestamp),
-            vesting_schedule: None,
-        };
-        let draft_group_id: DraftGroupIndex = 123;
-        let draft = Draft {
-            draft_group_id,
-            lockup_create,
+            operator_id: operator_id.clone(),
+            allowances: Some(
+                vec![(token_account_id.clone(), allowance)]
+                    .into_iter()
+                    .collect(),
+            ),
         };
-        let draft_id: DraftIndex = 33;
-
-        let event: FtLockupCreateDraft = (draft_id, draft).into();
 
-        emit(EventKind::FtLockupCreateDraft(vec![event]));
+        emit(EventKind::FtLockupApproveClaimOperator(vec![event]));
         assert_eq!(
             test_utils::get_logs()[0],
             format!(
@@ -485,16 +1006,14 @@ mod tests {
                 json!({
                     "standard": PACKAGE_NAME,
                     "version": VERSION,
-                    "event": "ft_lockup_create_draft",
+                    "event": "ft_lockup_approve_claim_operator",
                     "data": [
                         {
-                            "id": draft_id,
-                            "draft_group_id": draft_group_id,
-                            "account_id": account_id.to_string(),
-                            "balance": balance,
-                            "start": timestamp - 1,
-                            "finish": timestamp,
-                            "terminatable": false,
+                            "account_id": account_id,
+                            "operator_id": operator_id,
+                            "allowances": {
+                                token_account_id: allowance,
+                            },
                         },
                     ],
                 })
@@ -504,18 +1023,18 @@ mod tests {
     }
 
     #[test]
-    fn test_ft_lockup_fund_draft_group() {
+    fn test_ft_lockup_revoke_claim_operator() {
         testing_env!(get_context());
 
-        let draft_group_id: DraftGroupIndex = 22;
-        let amount: WrappedBalance = 10000.into();
+        let account_id: AccountId = "alice.near".into();
+        let operator_id: AccountId = "operator.near".into();
 
-        let event = FtLockupFundDraftGroup {
-            id: draft_group_id,
-            amount,
+        let event = FtLockupRevokeClaimOperator {
+            account_id: account_id.clone(),
+            operator_id: operator_id.clone(),
         };
 
-        emit(EventKind::FtLockupFundDraftGroup(vec![event]));
+        emit(EventKind::FtLockupRevokeClaimOperator(vec![event]));
         assert_eq!(
             test_utils::get_logs()[0],
             format!(
@@ -523,11 +1042,11 @@ mod tests {
                 json!({
                     "standard": PACKAGE_NAME,
                     "version": VERSION,
-                    "event": "ft_lockup_fund_draft_group",
+                    "event": "ft_lockup_revoke_claim_operator",
                     "data": [
                         {
-                            "id": draft_group_id,
-                            "amount": amount,
+                            "account_id": account_id,
+                            "operator_id": operator_id,
                         },
                     ],
                 })
@@ -537,14 +1056,21 @@ mod tests {
     }
 
     #[test]
-    fn test_ft_lockup_discard_draft_group() {
+    fn test_ft_lockup_terminate_lockup() {
         testing_env!(get_context());
 
-        let draft_group_id: DraftGroupIndex = 22;
+        let lockup_id: LockupIndex = 100;
+        let termination_timestamp: TimestampSec = 1_800_000_000;
+        let unvested_balance: WrappedBalance = 10000.into();
 
-        let event = FtLockupDiscardDraftGroup { id: draft_group_id };
+        let event = FtLockupTerminateLockup {
+            id: lockup_id,
+            termination_timestamp,
+            unvested_balance,
+            token_account_id: "token.near".into(),
+        };
 
-        emit(EventKind::FtLockupDiscardDraftGroup(vec![event]));
+        emit(EventKind::FtLockupTerminateLockup(vec![event]));
         assert_eq!(
             test_utils::get_logs()[0],
             format!(
@@ -552,10 +1078,13 @@ mod tests {
                 json!({
                     "standard": PACKAGE_NAME,
                     "version": VERSION,
-                    "event": "ft_lockup_discard_draft_group",
+                    "event": "ft_lockup_terminate_lockup",
                     "data": [
                         {
-                            "id": draft_group_id,
+                            "id": lockup_id,
+                            "termination_timestamp": termination_timestamp,
+                            "unvested_balance": unvested_balance,
+                            "token_account_id": "token.near",
                         },
                     ],
                 })
@@ -565,14 +1094,16 @@ mod tests {
     }
 
     #[test]
-    fn test_ft_lockup_delete_draft() {
+    fn test_ft_lockup_schedule_termination() {
         testing_env!(get_context());
 
-        let draft_id: DraftIndex = 22;
-
-        let event = FtLockupDeleteDraft { id: draft_id };
+        let lockup_id: LockupIndex = 100;
+        let event = FtLockupScheduleTermination {
+            id: lockup_id,
+            termination_timestamp: 1_600_000_000,
+        };
 
-        emit(EventKind::FtLockupDeleteDraft(vec![event]));
+        emit(EventKind::FtLockupScheduleTermination(vec![event]));
         assert_eq!(
             test_utils::get_logs()[0],
             format!(
@@ -580,10 +1111,11 @@ mod tests {
                 json!({
                     "standard": PACKAGE_NAME,
                     "version": VERSION,
-                    "event": "ft_lockup_delete_draft",
+                    "event": "ft_lockup_schedule_termination",
                     "data": [
                         {
-                            "id": draft_id,
+                            "id": lockup_id,
+                            "termination_timestamp": 1_600_000_000,
                         },
                     ],
                 })
@@ -591,4 +1123,813 @@ mod tests {
             )
         );
     }
+
+    #[test]
+    fn test_ft_lockup_cancel_scheduled_termination() {
+        testing_env!(get_context());
+
+        let lockup_id: LockupIndex = 100;
+        let event = FtLockupCancelScheduledTermination { id: lockup_id };
+
+        emit(EventKind::FtLockupCancelScheduledTermination(vec![event]));
+        assert_eq!(
+            test_utils::get_logs()[0],
+            format!(
+                r"EVENT_JSON:{}",
+                json!({
+                    "standard": PACKAGE_NAME,
+                    "version": VERSION,
+                    "event": "ft_lockup_cancel_scheduled_termination",
+                    "data": [{ "id": lockup_id }],
+                })
+                .to_string(),
+            )
+        );
+    }
+
+    #[test]
+    fn test_ft_lockup_reduce_lockup() {
+        testing_env!(get_context());
+
+        let lockup_id: LockupIndex = 100;
+        let amount: WrappedBalance = 10000.into();
+
+        let event = FtLockupReduceLockup {
+            id: lockup_id,
+            amount,
+            token_account_id: "token.near".into(),
+        };
+
+        emit(EventKind::FtLockupReduceLockup(vec![event]));
+        assert_eq!(
+            test_utils::get_logs()[0],
+            format!(
+                r"EVENT_JSON:{}",
+                json!({
+                    "standard": PACKAGE_NAME,
+                    "version": VERSION,
+                    "event": "ft_lockup_reduce_lockup",
+                    "data": [
+                        {
+                            "id": lockup_id,
+                            "amount": amount,
+                            "token_account_id": "token.near",
+                        },
+                    ],
+                })
+                .to_string(),
+            )
+        );
+    }
+
+    #[test]
+    fn test_ft_lockup_pause_vesting() {
+        testing_env!(get_context());
+
+        let lockup_id: LockupIndex = 100;
+        let paused_since: TimestampSec = 1_800_000_000;
+        let event = FtLockupPauseVesting {
+            id: lockup_id,
+            paused_since,
+        };
+
+        emit(EventKind::FtLockupPauseVesting(vec![event]));
+        assert_eq!(
+            test_utils::get_logs()[0],
+            format!(
+                r"EVENT_JSON:{}",
+                json!({
+                    "standard": PACKAGE_NAME,
+                    "version": VERSION,
+                    "event": "ft_lockup_pause_vesting",
+                    "data": [{ "id": lockup_id, "paused_since": paused_since }],
+                })
+                .to_string(),
+            )
+        );
+    }
+
+    #[test]
+    fn test_ft_lockup_resume_vesting() {
+        testing_env!(get_context());
+
+        let lockup_id: LockupIndex = 100;
+        let pause_duration: TimestampSec = 86400;
+        let event = FtLockupResumeVesting {
+            id: lockup_id,
+            pause_duration,
+        };
+
+        emit(EventKind::FtLockupResumeVesting(vec![event]));
+        assert_eq!(
+            test_utils::get_logs()[0],
+            format!(
+                r"EVENT_JSON:{}",
+                json!({
+                    "standard": PACKAGE_NAME,
+                    "version": VERSION,
+                    "event": "ft_lockup_resume_vesting",
+                    "data": [{ "id": lockup_id, "pause_duration": pause_duration }],
+                })
+                .to_string(),
+            )
+        );
+    }
+
+    #[test]
+    fn test_ft_lockup_transfer_lockup() {
+        testing_env!(get_context());
+
+        let lockup_id: LockupIndex = 100;
+        let account_id: AccountId = "alice.near".into();
+        let new_account_id: AccountId = "bob.near".into();
+
+        let event = FtLockupTransferLockup {
+            id: lockup_id,
+            account_id: account_id.clone(),
+            new_account_id: new_account_id.clone(),
+        };
+
+        emit(EventKind::FtLockupTransferLockup(vec![event]));
+        assert_eq!(
+            test_utils::get_logs()[0],
+            format!(
+                r"EVENT_JSON:{}",
+                json!({
+                    "standard": PACKAGE_NAME,
+                    "version": VERSION,
+                    "event": "ft_lockup_transfer_lockup",
+                    "data": [
+                        {
+                            "id": lockup_id,
+                            "account_id": account_id,
+                            "new_account_id": new_account_id,
+                        },
+                    ],
+                })
+                .to_string(),
+            )
+        );
+    }
+
+    #[test]
+    fn test_ft_lockup_split_lockup() {
+        testing_env!(get_context());
+
+        let lockup_id: LockupIndex = 100;
+        let new_lockup_id: LockupIndex = 101;
+        let new_account_id: AccountId = "bob.near".into();
+        let amount: WrappedBalance = 10000.into();
+
+        let event = FtLockupSplitLockup {
+            id: lockup_id,
+            new_id: new_lockup_id,
+            new_account_id: new_account_id.clone(),
+            amount,
+        };
+
+        emit(EventKind::FtLockupSplitLockup(vec![event]));
+        assert_eq!(
+            test_utils::get_logs()[0],
+            format!(
+                r"EVENT_JSON:{}",
+                json!({
+                    "standard": PACKAGE_NAME,
+                    "version": VERSION,
+                    "event": "ft_lockup_split_lockup",
+                    "data": [
+                        {
+                            "id": lockup_id,
+                            "new_id": new_lockup_id,
+                            "new_account_id": new_account_id,
+                            "amount": amount,
+                        },
+                    ],
+                })
+                .to_string(),
+            )
+        );
+    }
+
+    #[test]
+    fn test_ft_lockup_merge_lockups() {
+        testing_env!(get_context());
+
+        let lockup_id: LockupIndex = 100;
+        let account_id: AccountId = "alice.near".into();
+        let source_ids: Vec<LockupIndex> = vec![10, 20, 30];
+
+        let event = FtLockupMergeLockups {
+            id: lockup_id,
+            account_id: account_id.clone(),
+            source_ids: source_ids.clone(),
+        };
+
+        emit(EventKind::FtLockupMergeLockups(vec![event]));
+        assert_eq!(
+            test_utils::get_logs()[0],
+            format!(
+                r"EVENT_JSON:{}",
+                json!({
+                    "standard": PACKAGE_NAME,
+                    "version": VERSION,
+                    "event": "ft_lockup_merge_lockups",
+                    "data": [
+                        {
+                            "id": lockup_id,
+                            "account_id": account_id,
+                            "source_ids": source_ids,
+                        },
+                    ],
+                })
+                .to_string(),
+            )
+        );
+    }
+
+    #[test]
+    fn test_ft_lockup_create_draft_group() {
+        testing_env!(get_context());
+
+        let draft_group_id: DraftGroupIndex = 22;
+
+        let event = FtLockupCreateDraftGroup { id: draft_group_id };
+
+        emit(EventKind::FtLockupCreateDraftGroup(vec![event]));
+        assert_eq!(
+            test_utils::get_logs()[0],
+            format!(
+                r"EVENT_JSON:{}",
+                json!({
+                    "standard": PACKAGE_NAME,
+                    "version": VERSION,
+                    "event": "ft_lockup_create_draft_group",
+                    "data": [
+                        {
+                            "id": draft_group_id,
+                        },
+                    ],
+                })
+                .to_string(),
+            )
+        );
+    }
+
+    #[test]
+    fn test_ft_lockup_create_draft() {
+        testing_env!(get_context());
+
+        let account_id: ValidAccountId = "alice.near".try_into().unwrap();
+        let balance: WrappedBalance = 10_000.into();
+        let timestamp: TimestampSec = 1_500_000_000;
+        let lockup_create = LockupCreate {
+            account_id: account_id.clone(),
+            schedule: Schedule::new_unlocked_since(balance.0, timestamp),
+            vesting_schedule: None,
+            transferable: false,
+            clawback_receiver: None,
+        };
+        let draft_group_id: DraftGroupIndex = 123;
+        let draft = Draft {
+            draft_group_id,
+            lockup_create,
+        };
+        let draft_id: DraftIndex = 33;
+
+        let event: FtLockupCreateDraft = (draft_id, draft).into();
+
+        emit(EventKind::FtLockupCreateDraft(vec![event]));
+        assert_eq!(
+            test_utils::get_logs()[0],
+            format!(
+                r"EVENT_JSON:{}",
+                json!({
+                    "standard": PACKAGE_NAME,
+                    "version": VERSION,
+                    "event": "ft_lockup_create_draft",
+                    "data": [
+                        {
+                            "id": draft_id,
+                            "draft_group_id": draft_group_id,
+                            "account_id": account_id.to_string(),
+                            "balance": balance,
+                            "start": timestamp - 1,
+                            "finish": timestamp,
+                            "terminatable": false,
+                        },
+                    ],
+                })
+                .to_string(),
+            )
+        );
+    }
+
+    #[test]
+    fn test_ft_lockup_fund_draft_group() {
+        testing_env!(get_context());
+
+        let draft_group_id: DraftGroupIndex = 22;
+        let amount: WrappedBalance = 10000.into();
+
+        let event = FtLockupFundDraftGroup {
+            id: draft_group_id,
+            amount,
+            token_account_id: "token.near".into(),
+        };
+
+        emit(EventKind::FtLockupFundDraftGroup(vec![event]));
+        assert_eq!(
+            test_utils::get_logs()[0],
+            format!(
+                r"EVENT_JSON:{}",
+                json!({
+                    "standard": PACKAGE_NAME,
+                    "version": VERSION,
+                    "event": "ft_lockup_fund_draft_group",
+                    "data": [
+                        {
+                            "id": draft_group_id,
+                            "amount": amount,
+                            "token_account_id": "token.near",
+                        },
+                    ],
+                })
+                .to_string(),
+            )
+        );
+    }
+
+    #[test]
+    fn test_ft_lockup_discard_draft_group() {
+        testing_env!(get_context());
+
+        let draft_group_id: DraftGroupIndex = 22;
+
+        let event = FtLockupDiscardDraftGroup { id: draft_group_id };
+
+        emit(EventKind::FtLockupDiscardDraftGroup(vec![event]));
+        assert_eq!(
+            test_utils::get_logs()[0],
+            format!(
+                r"EVENT_JSON:{}",
+                json!({
+                    "standard": PACKAGE_NAME,
+                    "version": VERSION,
+                    "event": "ft_lockup_discard_draft_group",
+                    "data": [
+                        {
+                            "id": draft_group_id,
+                        },
+                    ],
+                })
+                .to_string(),
+            )
+        );
+    }
+
+    #[test]
+    fn test_ft_lockup_update_draft() {
+        testing_env!(get_context());
+
+        let account_id: ValidAccountId = "alice.near".try_into().unwrap();
+        let balance: WrappedBalance = 10_000.into();
+        let draft_group_id: DraftGroupIndex = 123;
+        let draft = Draft {
+            draft_group_id,
+            lockup_create: LockupCreate::new_unlocked(account_id.clone(), balance.0),
+        };
+        let draft_id: DraftIndex = 33;
+
+        let event: FtLockupUpdateDraft = (draft_id, draft).into();
+
+        emit(EventKind::FtLockupUpdateDraft(vec![event]));
+        assert_eq!(
+            test_utils::get_logs()[0],
+            format!(
+                r"EVENT_JSON:{}",
+                json!({
+                    "standard": PACKAGE_NAME,
+                    "version": VERSION,
+                    "event": "ft_lockup_update_draft",
+                    "data": [
+                        {
+                            "id": draft_id,
+                            "draft_group_id": draft_group_id,
+                            "account_id": account_id.to_string(),
+                            "balance": balance,
+                            "start": 0,
+                            "finish": 1,
+                            "terminatable": false,
+                        },
+                    ],
+                })
+                .to_string(),
+            )
+        );
+    }
+
+    #[test]
+    fn test_ft_lockup_remove_draft() {
+        testing_env!(get_context());
+
+        let draft_id: DraftIndex = 22;
+
+        let event = FtLockupRemoveDraft { id: draft_id };
+
+        emit(EventKind::FtLockupRemoveDraft(vec![event]));
+        assert_eq!(
+            test_utils::get_logs()[0],
+            format!(
+                r"EVENT_JSON:{}",
+                json!({
+                    "standard": PACKAGE_NAME,
+                    "version": VERSION,
+                    "event": "ft_lockup_remove_draft",
+                    "data": [
+                        {
+                            "id": draft_id,
+                        },
+                    ],
+                })
+                .to_string(),
+            )
+        );
+    }
+
+    #[test]
+    fn test_ft_lockup_delete_draft() {
+        testing_env!(get_context());
+
+        let draft_id: DraftIndex = 22;
+
+        let event = FtLockupDeleteDraft { id: draft_id };
+
+        emit(EventKind::FtLockupDeleteDraft(vec![event]));
+        assert_eq!(
+            test_utils::get_logs()[0],
+            format!(
+                r"EVENT_JSON:{}",
+                json!({
+                    "standard": PACKAGE_NAME,
+                    "version": VERSION,
+                    "event": "ft_lockup_delete_draft",
+                    "data": [
+                        {
+                            "id": draft_id,
+                        },
+                    ],
+                })
+                .to_string(),
+            )
+        );
+    }
+
+    #[test]
+    fn test_ft_lockup_stage_upgrade() {
+        testing_env!(get_context());
+
+        let code_hash: Base58CryptoHash = [1; 32].into();
+        let event = FtLockupStageUpgrade {
+            code_hash,
+            deploy_timestamp: 1_600_000_000,
+        };
+        emit(EventKind::FtLockupStageUpgrade(event));
+        assert_eq!(
+            test_utils::get_logs()[0],
+            format!(
+                r"EVENT_JSON:{}",
+                json!({
+                    "standard": PACKAGE_NAME,
+                    "version": VERSION,
+                    "event": "ft_lockup_stage_upgrade",
+                    "data": {
+                        "code_hash": code_hash,
+                        "deploy_timestamp": 1_600_000_000,
+                    },
+                })
+                .to_string(),
+            )
+        );
+    }
+
+    #[test]
+    fn test_ft_lockup_cancel_upgrade() {
+        testing_env!(get_context());
+
+        let code_hash: Base58CryptoHash = [1; 32].into();
+        emit(EventKind::FtLockupCancelUpgrade(FtLockupCancelUpgrade {
+            code_hash,
+        }));
+        assert_eq!(
+            test_utils::get_logs()[0],
+            format!(
+                r"EVENT_JSON:{}",
+                json!({
+                    "standard": PACKAGE_NAME,
+                    "version": VERSION,
+                    "event": "ft_lockup_cancel_upgrade",
+                    "data": { "code_hash": code_hash },
+                })
+                .to_string(),
+            )
+        );
+    }
+
+    #[test]
+    fn test_ft_lockup_deploy_upgrade() {
+        testing_env!(get_context());
+
+        let code_hash: Base58CryptoHash = [1; 32].into();
+        emit(EventKind::FtLockupDeployUpgrade(FtLockupDeployUpgrade {
+            code_hash,
+        }));
+        assert_eq!(
+            test_utils::get_logs()[0],
+            format!(
+                r"EVENT_JSON:{}",
+                json!({
+                    "standard": PACKAGE_NAME,
+                    "version": VERSION,
+                    "event": "ft_lockup_deploy_upgrade",
+                    "data": { "code_hash": code_hash },
+                })
+                .to_string(),
+            )
+        );
+    }
+
+    #[test]
+    fn test_ft_lockup_set_upgrade_delay() {
+        testing_env!(get_context());
+
+        emit(EventKind::FtLockupSetUpgradeDelay(
+            FtLockupSetUpgradeDelay {
+                upgrade_delay_sec: 86400,
+                effective_timestamp: 1_600_000_000,
+            },
+        ));
+        assert_eq!(
+            test_utils::get_logs()[0],
+            format!(
+                r"EVENT_JSON:{}",
+                json!({
+                    "standard": PACKAGE_NAME,
+                    "version": VERSION,
+                    "event": "ft_lockup_set_upgrade_delay",
+                    "data": {
+                        "upgrade_delay_sec": 86400,
+                        "effective_timestamp": 1_600_000_000,
+                    },
+                })
+                .to_string(),
+            )
+        );
+    }
+
+    #[test]
+    fn test_ft_lockup_grant_role() {
+        testing_env!(get_context());
+
+        let account_ids: Vec<AccountId> = vec!["alice.near", "bob.near"]
+            .iter()
+            .map(|&x| x.into())
+            .collect();
+        emit(EventKind::FtLockupGrantRole(FtLockupGrantRole {
+            role: Role::DraftOperator,
+            account_ids,
+        }));
+        assert_eq!(
+            test_utils::get_logs()[0],
+            format!(
+                r"EVENT_JSON:{}",
+                json!({
+                    "standard": PACKAGE_NAME,
+                    "version": VERSION,
+                    "event": "ft_lockup_grant_role",
+                    "data": {
+                        "role": "draft_operator",
+                        "account_ids": ["alice.near", "bob.near"],
+                    },
+                })
+                .to_string(),
+            )
+        );
+    }
+
+    #[test]
+    fn test_ft_lockup_revoke_role() {
+        testing_env!(get_context());
+
+        let account_ids: Vec<AccountId> = vec!["alice.near"].iter().map(|&x| x.into()).collect();
+        emit(EventKind::FtLockupRevokeRole(FtLockupRevokeRole {
+            role: Role::Terminator,
+            account_ids,
+        }));
+        assert_eq!(
+            test_utils::get_logs()[0],
+            format!(
+                r"EVENT_JSON:{}",
+                json!({
+                    "standard": PACKAGE_NAME,
+                    "version": VERSION,
+                    "event": "ft_lockup_revoke_role",
+                    "data": {
+                        "role": "terminator",
+                        "account_ids": ["alice.near"],
+                    },
+                })
+                .to_string(),
+            )
+        );
+    }
+
+    #[test]
+    fn test_ft_lockup_propose_admin_change() {
+        testing_env!(get_context());
+
+        emit(EventKind::FtLockupProposeAdminChange(
+            FtLockupProposeAdminChange {
+                id: 3,
+                proposer_id: "alice.near".into(),
+                change: AdminChange::RevokeRoles {
+                    roles: vec![Role::Depositor, Role::Admin],
+                    account_ids: vec!["bob.near".try_into().unwrap()],
+                },
+                accept_timestamp: 1_600_000_000,
+            },
+        ));
+        assert_eq!(
+            test_utils::get_logs()[0],
+            format!(
+                r"EVENT_JSON:{}",
+                json!({
+                    "standard": PACKAGE_NAME,
+                    "version": VERSION,
+                    "event": "ft_lockup_propose_admin_change",
+                    "data": {
+                        "id": 3,
+                        "proposer_id": "alice.near",
+                        "change": {
+                            "kind": "revoke_roles",
+                            "roles": ["depositor", "admin"],
+                            "account_ids": ["bob.near"],
+                        },
+                        "accept_timestamp": 1_600_000_000,
+                    },
+                })
+                .to_string(),
+            )
+        );
+    }
+
+    #[test]
+    fn test_ft_lockup_accept_admin_change() {
+        testing_env!(get_context());
+
+        emit(EventKind::FtLockupAcceptAdminChange(
+            FtLockupAcceptAdminChange {
+                id: 3,
+                account_id: "alice.near".into(),
+            },
+        ));
+        assert_eq!(
+            test_utils::get_logs()[0],
+            format!(
+                r"EVENT_JSON:{}",
+                json!({
+                    "standard": PACKAGE_NAME,
+                    "version": VERSION,
+                    "event": "ft_lockup_accept_admin_change",
+                    "data": { "id": 3, "account_id": "alice.near" },
+                })
+                .to_string(),
+            )
+        );
+    }
+
+    #[test]
+    fn test_ft_lockup_cancel_admin_change() {
+        testing_env!(get_context());
+
+        emit(EventKind::FtLockupCancelAdminChange(
+            FtLockupCancelAdminChange {
+                id: 3,
+                account_id: "bob.near".into(),
+            },
+        ));
+        assert_eq!(
+            test_utils::get_logs()[0],
+            format!(
+                r"EVENT_JSON:{}",
+                json!({
+                    "standard": PACKAGE_NAME,
+                    "version": VERSION,
+                    "event": "ft_lockup_cancel_admin_change",
+                    "data": { "id": 3, "account_id": "bob.near" },
+                })
+                .to_string(),
+            )
+        );
+    }
+
+    #[test]
+    fn test_ft_lockup_add_to_guardians_whitelist() {
+        testing_env!(get_context());
+
+        let account_ids: Vec<AccountId> = vec!["alice.near", "bob.near"]
+            .iter()
+            .map(|&x| x.into())
+            .collect();
+        emit(EventKind::FtLockupAddToGuardiansWhitelist(
+            FtLockupAddToGuardiansWhitelist { account_ids },
+        ));
+        assert_eq!(
+            test_utils::get_logs()[0],
+            format!(
+                r"EVENT_JSON:{}",
+                json!({
+                    "standard": PACKAGE_NAME,
+                    "version": VERSION,
+                    "event": "ft_lockup_add_to_guardians_whitelist",
+                    "data": { "account_ids": ["alice.near", "bob.near"] },
+                })
+                .to_string(),
+            )
+        );
+    }
+
+    #[test]
+    fn test_ft_lockup_remove_from_guardians_whitelist() {
+        testing_env!(get_context());
+
+        let account_ids: Vec<AccountId> = vec!["alice.near"].iter().map(|&x| x.into()).collect();
+        emit(EventKind::FtLockupRemoveFromGuardiansWhitelist(
+            FtLockupRemoveFromGuardiansWhitelist { account_ids },
+        ));
+        assert_eq!(
+            test_utils::get_logs()[0],
+            format!(
+                r"EVENT_JSON:{}",
+                json!({
+                    "standard": PACKAGE_NAME,
+                    "version": VERSION,
+                    "event": "ft_lockup_remove_from_guardians_whitelist",
+                    "data": { "account_ids": ["alice.near"] },
+                })
+                .to_string(),
+            )
+        );
+    }
+
+    #[test]
+    fn test_ft_lockup_pause() {
+        testing_env!(get_context());
+
+        emit(EventKind::FtLockupPause(FtLockupPause {
+            account_id: "guardian.near".into(),
+            flags: vec![PauseFlag::Claiming, PauseFlag::DraftConversion],
+        }));
+        assert_eq!(
+            test_utils::get_logs()[0],
+            format!(
+                r"EVENT_JSON:{}",
+                json!({
+                    "standard": PACKAGE_NAME,
+                    "version": VERSION,
+                    "event": "ft_lockup_pause",
+                    "data": {
+                        "account_id": "guardian.near",
+                        "flags": ["claiming", "draft_conversion"],
+                    },
+                })
+                .to_string(),
+            )
+        );
+    }
+
+    #[test]
+    fn test_ft_lockup_unpause() {
+        testing_env!(get_context());
+
+        emit(EventKind::FtLockupUnpause(FtLockupUnpause {
+            account_id: "guardian.near".into(),
+            flags: vec![PauseFlag::Creation, PauseFlag::Termination],
+        }));
+        assert_eq!(
+            test_utils::get_logs()[0],
+            format!(
+                r"EVENT_JSON:{}",
+                json!({
+                    "standard": PACKAGE_NAME,
+                    "version": VERSION,
+                    "event": "ft_lockup_unpause",
+                    "data": {
+                        "account_id": "guardian.near",
+                        "flags": ["creation", "termination"],
+                    },
+                })
+                .to_string(),
+            )
+        );
+    }
 }
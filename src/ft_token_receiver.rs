@@ -6,6 +6,9 @@ pub struct DraftGroupFunding {
     pub draft_group_id: DraftGroupIndex,
     // use remaining gas to try converting drafts
     pub try_convert: Option<bool>,
+    /// Receives the unvested balance of the group lockups instead of the payer.
+    #[serde(default)]
+    pub clawback_receiver: Option<ClawbackReceiver>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -24,20 +27,24 @@ impl FungibleTokenReceiver for Contract {
         amount: U128,
         msg: String,
     ) -> PromiseOrValue<U128> {
-        assert_eq!(
-            env::predecessor_account_id(),
-            self.token_account_id,
-            "Invalid token ID"
-        );
+        self.assert_not_paused(PauseFlag::Creation);
+        let token_account_id = env::predecessor_account_id();
+        self.assert_token_whitelist(&token_account_id);
         let amount = amount.into();
         self.assert_deposit_whitelist(sender_id.as_ref());
 
         let ft_message: FtMessage = serde_json::from_str(&msg).unwrap();
         match ft_message {
             FtMessage::LockupCreate(lockup_create) => {
-                let lockup = lockup_create.into_lockup(&sender_id);
+                let lockup = lockup_create.into_lockup(&sender_id, &token_account_id);
                 lockup.assert_new_valid(amount);
+                let initial_storage_usage = env::storage_usage();
                 let index = self.internal_add_lockup(&lockup);
+                self.internal_stake_storage(
+                    StorageStakeId::Lockup(index),
+                    sender_id.as_ref(),
+                    initial_storage_usage,
+                );
                 log!(
                     "Created new lockup for {} with index {}",
                     lockup.account_id.as_ref(),
@@ -56,7 +63,7 @@ impl FungibleTokenReceiver for Contract {
                     draft_group.total_amount, amount,
                     "The draft group total balance doesn't match the transferred balance",
                 );
-                draft_group.fund(&sender_id);
+                draft_group.fund(&sender_id, &token_account_id, funding.clawback_receiver);
                 self.draft_groups.insert(&draft_group_id as _, &draft_group);
                 log!("Funded draft group {}", draft_group_id);
 
@@ -78,6 +85,7 @@ impl FungibleTokenReceiver for Contract {
                 let event = FtLockupFundDraftGroup {
                     id: draft_group_id,
                     amount: amount.into(),
+                    token_account_id,
                 };
                 emit(EventKind::FtLockupFundDraftGroup(vec![event]));
             }
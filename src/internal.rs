@@ -1,33 +1,296 @@
 use crate::*;
 
+/// Transfers the tokens with `ft_transfer`, or native NEAR for the native lockups.
+pub(crate) fn transfer(
+    token_account_id: &TokenAccountId,
+    receiver_id: AccountId,
+    amount: Balance,
+    memo: Option<String>,
+) -> Promise {
+    if token_account_id == NATIVE_TOKEN_ID {
+        Promise::new(receiver_id).transfer(amount)
+    } else {
+        ext_fungible_token::ft_transfer(
+            receiver_id,
+            amount.into(),
+            memo,
+            token_account_id,
+            ONE_YOCTO,
+            GAS_FOR_FT_TRANSFER,
+        )
+    }
+}
+
+/// The native NEAR lockups are created with `create_native_lockup`, so the native token id
+/// can't be taken by a fungible token.
+pub(crate) fn assert_not_native_token(token_account_id: &str) {
+    assert_ne!(
+        token_account_id, NATIVE_TOKEN_ID,
+        "The native token can't be whitelisted"
+    );
+}
+
+/// Sends the unvested balance to the clawback receiver, with `ft_transfer_call` if it has
+/// a message. The part that isn't received is locked up for the receiver in the callback,
+/// the storage of that lockup is charged to the given payer.
+pub(crate) fn clawback(
+    token_account_id: &TokenAccountId,
+    clawback_receiver: ClawbackReceiver,
+    amount: Balance,
+    memo: Option<String>,
+    payer_id: Option<AccountId>,
+) -> Promise {
+    let ClawbackReceiver { account_id, msg } = clawback_receiver;
+    let account_id: AccountId = account_id.into();
+    let transfer = match msg {
+        Some(msg) => ext_fungible_token::ft_transfer_call(
+            account_id.clone(),
+            amount.into(),
+            memo,
+            msg,
+            token_account_id,
+            ONE_YOCTO,
+            GAS_FOR_FT_TRANSFER_CALL,
+        ),
+        None => transfer(token_account_id, account_id.clone(), amount, memo),
+    };
+    transfer.then(ext_self::after_lockup_termination(
+        account_id,
+        amount.into(),
+        token_account_id.clone(),
+        payer_id,
+        &env::current_account_id(),
+        NO_DEPOSIT,
+        GAS_FOR_AFTER_FT_TRANSFER,
+    ))
+}
+
+/// Defaults the termination timestamp to now, the past timestamps aren't allowed.
+pub(crate) fn termination_timestamp_or_now(
+    termination_timestamp: Option<TimestampSec>,
+) -> TimestampSec {
+    let current_timestamp = current_timestamp_sec();
+    let termination_timestamp = termination_timestamp.unwrap_or(current_timestamp);
+    assert!(
+        termination_timestamp >= current_timestamp,
+        "expected termination_timestamp >= now",
+    );
+    termination_timestamp
+}
+
 impl Contract {
     pub(crate) fn assert_deposit_whitelist(&self, account_id: &AccountId) {
         assert!(
-            self.deposit_whitelist.contains(account_id),
+            self.has_role(Role::Depositor, account_id),
             "Not in deposit whitelist"
         );
     }
 
+    pub(crate) fn assert_token_whitelist(&self, token_account_id: &TokenAccountId) {
+        assert!(
+            self.token_whitelist.contains(token_account_id),
+            "Token is not whitelisted"
+        );
+    }
+
     pub(crate) fn assert_draft_operators_whitelist(&self, account_id: &AccountId) {
         assert!(
-            (self.deposit_whitelist.contains(account_id)
-                || self.draft_operators_whitelist.contains(account_id)),
+            (self.has_role(Role::Admin, account_id)
+                || self.has_role(Role::DraftOperator, account_id)),
             "Not in draft operators whitelist"
         );
     }
 
+    /// Only the lockup's termination beneficiary, one of its delegates or a terminator
+    /// can terminate it.
+    pub(crate) fn assert_can_terminate(&self, lockup: &Lockup, account_id: &AccountId) {
+        let termination_config = lockup
+            .termination_config
+            .as_ref()
+            .expect("No termination config");
+        let beneficiary_id: &AccountId = termination_config.beneficiary_id.as_ref();
+        assert!(
+            beneficiary_id == account_id
+                || self.has_role(Role::Terminator, account_id)
+                || self
+                    .termination_delegates
+                    .get(beneficiary_id)
+                    .map(|delegates| delegates.contains(account_id))
+                    .unwrap_or(false),
+            "Not authorized to terminate the lockup"
+        );
+    }
+
+    /// Terminates the lockup without transferring the unvested balance.
+    /// Returns the termination event and the receiver of the unvested balance.
+    pub(crate) fn internal_terminate_lockup(
+        &mut self,
+        lockup_index: LockupIndex,
+        hashed_schedule: Option<Schedule>,
+        termination_timestamp: TimestampSec,
+        salt: Option<Vec<u8>>,
+    ) -> (FtLockupTerminateLockup, ClawbackReceiver) {
+        let mut lockup = self
+            .lockups
+            .get(lockup_index as _)
+            .expect("Lockup not found");
+        let (unvested_balance, clawback_receiver) =
+            lockup.terminate(hashed_schedule, termination_timestamp, salt);
+        self.lockups.replace(lockup_index as _, &lockup);
+
+        // no need to store empty lockup
+        if lockup.schedule.total_balance() == 0 {
+            self.internal_remove_account_lockup(lockup.account_id.as_ref(), lockup_index);
+            self.internal_release_storage(StorageStakeId::Lockup(lockup_index));
+        }
+
+        let event = FtLockupTerminateLockup {
+            id: lockup_index,
+            termination_timestamp,
+            unvested_balance: unvested_balance.into(),
+            token_account_id: lockup.token_account_id,
+        };
+        (event, clawback_receiver)
+    }
+
+    pub(crate) fn internal_terminate(
+        &mut self,
+        lockup_index: LockupIndex,
+        hashed_schedule: Option<Schedule>,
+        termination_timestamp: TimestampSec,
+        salt: Option<Vec<u8>>,
+    ) -> PromiseOrValue<WrappedBalance> {
+        let (event, clawback_receiver) = self.internal_terminate_lockup(
+            lockup_index,
+            hashed_schedule,
+            termination_timestamp,
+            salt,
+        );
+        let unvested_balance = event.unvested_balance.0;
+        let token_account_id = event.token_account_id.clone();
+        emit(EventKind::FtLockupTerminateLockup(vec![event]));
+
+        if unvested_balance > 0 {
+            clawback(
+                &token_account_id,
+                clawback_receiver,
+                unvested_balance,
+                Some(format!("Terminated lockup #{}", lockup_index)),
+                self.internal_storage_payer(&StorageStakeId::Lockup(lockup_index)),
+            )
+            .into()
+        } else {
+            PromiseOrValue::Value(0.into())
+        }
+    }
+
+    /// Terminates the lockups and sends the unvested balances with one transfer
+    /// per token and clawback receiver.
+    pub(crate) fn internal_terminate_batch(
+        &mut self,
+        lockups: Vec<(LockupIndex, Option<Schedule>, Option<Base64VecU8>)>,
+        termination_timestamp: TimestampSec,
+    ) -> PromiseOrValue<WrappedBalance> {
+        let account_id = env::predecessor_account_id();
+        let mut events: Vec<FtLockupTerminateLockup> = vec![];
+        // the unvested balances aggregated per token and receiver, with the storage payer
+        // of the first lockup
+        let mut clawbacks: Vec<(TokenAccountId, ClawbackReceiver, Balance, Option<AccountId>)> =
+            vec![];
+        for (lockup_index, hashed_schedule, salt) in lockups {
+            let lockup = self
+                .lockups
+                .get(lockup_index as _)
+                .expect("Lockup not found");
+            self.assert_can_terminate(&lockup, &account_id);
+            let (event, clawback_receiver) = self.internal_terminate_lockup(
+                lockup_index,
+                hashed_schedule,
+                termination_timestamp,
+                salt.map(|salt| salt.0),
+            );
+            let unvested_balance = event.unvested_balance.0;
+            if unvested_balance > 0 {
+                match clawbacks
+                    .iter_mut()
+                    .find(|(token_account_id, receiver, _, _)| {
+                        token_account_id == &event.token_account_id
+                            && receiver == &clawback_receiver
+                    }) {
+                    Some((_, _, amount, _)) => *amount += unvested_balance,
+                    None => clawbacks.push((
+                        event.token_account_id.clone(),
+                        clawback_receiver,
+                        unvested_balance,
+                        self.internal_storage_payer(&StorageStakeId::Lockup(lockup_index)),
+                    )),
+                }
+            }
+            events.push(event);
+        }
+        let num_lockups = events.len();
+        emit(EventKind::FtLockupTerminateLockup(events));
+
+        let num_transfers = clawbacks.len();
+        // each transfer restores its own amount in the callback if it fails
+        let transfers = match clawbacks
+            .into_iter()
+            .map(|(token_account_id, clawback_receiver, amount, payer_id)| {
+                clawback(
+                    &token_account_id,
+                    clawback_receiver,
+                    amount,
+                    Some(format!("Terminated {} lockups", num_lockups)),
+                    payer_id,
+                )
+            })
+            .reduce(|transfers, transfer| transfers.and(transfer))
+        {
+            Some(transfers) => transfers,
+            None => return PromiseOrValue::Value(0.into()),
+        };
+
+        if num_transfers == 1 {
+            transfers.into()
+        } else {
+            // a joint promise can't be returned, so the transferred amounts are summed up instead
+            transfers
+                .then(ext_self::after_ft_transfers(
+                    &env::current_account_id(),
+                    NO_DEPOSIT,
+                    GAS_FOR_AFTER_FT_TRANSFERS,
+                ))
+                .into()
+        }
+    }
+
     pub(crate) fn internal_add_lockup(&mut self, lockup: &Lockup) -> LockupIndex {
         let index = self.lockups.len() as LockupIndex;
         self.lockups.push(lockup);
-        let mut indices = self
-            .account_lockups
-            .get(lockup.account_id.as_ref())
-            .unwrap_or_default();
-        indices.insert(index);
-        self.internal_save_account_lockups(lockup.account_id.as_ref(), indices);
+        self.internal_add_account_lockup(lockup.account_id.as_ref(), index);
         index
     }
 
+    pub(crate) fn internal_add_account_lockup(
+        &mut self,
+        account_id: &AccountId,
+        lockup_index: LockupIndex,
+    ) {
+        let mut indices = self.account_lockups.get(account_id).unwrap_or_default();
+        indices.insert(lockup_index);
+        self.internal_save_account_lockups(account_id, indices);
+    }
+
+    pub(crate) fn internal_remove_account_lockup(
+        &mut self,
+        account_id: &AccountId,
+        lockup_index: LockupIndex,
+    ) {
+        let mut indices = self.account_lockups.get(account_id).unwrap_or_default();
+        indices.remove(&lockup_index);
+        self.internal_save_account_lockups(account_id, indices);
+    }
+
     pub(crate) fn internal_save_account_lockups(
         &mut self,
         account_id: &AccountId,
@@ -40,6 +303,19 @@ impl Contract {
         }
     }
 
+    pub(crate) fn internal_save_termination_delegates(
+        &mut self,
+        beneficiary_id: &AccountId,
+        delegates: HashSet<AccountId>,
+    ) {
+        if delegates.is_empty() {
+            self.termination_delegates.remove(beneficiary_id);
+        } else {
+            self.termination_delegates
+                .insert(beneficiary_id, &delegates);
+        }
+    }
+
     pub(crate) fn internal_get_account_lockups(
         &self,
         account_id: &AccountId,
@@ -52,6 +328,20 @@ impl Contract {
             .collect()
     }
 
+    pub(crate) fn internal_get_account_lockup(
+        &self,
+        account_id: &AccountId,
+        lockup_index: LockupIndex,
+    ) -> Lockup {
+        let account_lockup_ids = self.account_lockups.get(account_id).unwrap_or_default();
+        assert!(
+            account_lockup_ids.contains(&lockup_index),
+            "lockup not found for account: {}",
+            lockup_index,
+        );
+        self.lockups.get(lockup_index as _).unwrap()
+    }
+
     pub(crate) fn internal_get_account_lockups_by_id(
         &self,
         account_id: &AccountId,
@@ -72,4 +362,194 @@ impl Contract {
             })
             .collect()
     }
+
+    pub(crate) fn internal_claim(
+        &mut self,
+        account_id: AccountId,
+        amounts: Option<Vec<(LockupIndex, Option<WrappedBalance>)>>,
+        receiver_id: Option<AccountId>,
+        msg: Option<String>,
+        operator_id: Option<AccountId>,
+    ) -> PromiseOrValue<WrappedBalance> {
+        let (claim_amounts, mut lockups_by_id) = if let Some(amounts) = amounts {
+            let lockups_by_id: HashMap<LockupIndex, Lockup> = self
+                .internal_get_account_lockups_by_id(
+                    &account_id,
+                    &amounts.iter().map(|x| x.0).collect(),
+                )
+                .into_iter()
+                .collect();
+            let amounts: HashMap<LockupIndex, WrappedBalance> = amounts
+                .into_iter()
+                .map(|(lockup_id, amount)| {
+                    (
+                        lockup_id,
+                        match amount {
+                            Some(amount) => amount,
+                            None => {
+                                let lockup =
+                                    lockups_by_id.get(&lockup_id).expect("lockup not found");
+                                let unlocked_balance = lockup.unlocked_balance();
+                                (unlocked_balance - lockup.claimed_balance).into()
+                            }
+                        },
+                    )
+                })
+                .collect();
+            (amounts, lockups_by_id)
+        } else {
+            let lockups_by_id: HashMap<LockupIndex, Lockup> = self
+                .internal_get_account_lockups(&account_id)
+                .into_iter()
+                .collect();
+            let amounts: HashMap<LockupIndex, WrappedBalance> = lockups_by_id
+                .iter()
+                .map(|(lockup_id, lockup)| {
+                    let unlocked_balance = lockup.unlocked_balance();
+                    let amount: WrappedBalance = (unlocked_balance - lockup.claimed_balance).into();
+
+                    (lockup_id.clone(), amount)
+                })
+                .collect();
+            (amounts, lockups_by_id)
+        };
+
+        // the claims are batched into one transfer per token
+        let mut token_claims: HashMap<TokenAccountId, Vec<LockupClaim>> = HashMap::new();
+        let mut total_claim_amount = 0;
+        for (lockup_index, lockup_claim_amount) in claim_amounts {
+            let lockup = lockups_by_id.get_mut(&lockup_index).unwrap();
+            let lockup_claim = lockup.claim(lockup_index, lockup_claim_amount.0);
+
+            if lockup_claim.claim_amount.0 > 0 {
+                log!(
+                    "Claiming {} form lockup #{}",
+                    lockup_claim.claim_amount.0,
+                    lockup_index
+                );
+                total_claim_amount += lockup_claim.claim_amount.0;
+                self.lockups.replace(lockup_index as _, &lockup);
+                token_claims
+                    .entry(lockup.token_account_id.clone())
+                    .or_default()
+                    .push(lockup_claim);
+            }
+        }
+        log!("Total claim {}", total_claim_amount);
+
+        if let Some(operator_id) = operator_id.as_ref() {
+            for (token_account_id, lockup_claims) in &token_claims {
+                let claim_amount: Balance = lockup_claims
+                    .iter()
+                    .map(|lockup_claim| lockup_claim.claim_amount.0)
+                    .sum();
+                self.internal_spend_claim_allowance(
+                    &account_id,
+                    operator_id,
+                    token_account_id,
+                    claim_amount,
+                );
+            }
+        }
+
+        if total_claim_amount == 0 {
+            return PromiseOrValue::Value(0.into());
+        }
+
+        let receiver_id = receiver_id.unwrap_or_else(|| account_id.clone());
+        let mut token_claims: Vec<(TokenAccountId, Vec<LockupClaim>)> =
+            token_claims.into_iter().collect();
+        token_claims.sort_by(|a, b| a.0.cmp(&b.0));
+        let num_tokens = token_claims.len();
+        let transfers = token_claims
+            .into_iter()
+            .map(|(token_account_id, lockup_claims)| {
+                let claim_amount: Balance = lockup_claims
+                    .iter()
+                    .map(|lockup_claim| lockup_claim.claim_amount.0)
+                    .sum();
+                let memo = Some(format!(
+                    "Claiming unlocked {} balance from {}",
+                    claim_amount,
+                    env::current_account_id()
+                ));
+                let transfer: Promise = if let Some(msg) = msg.clone() {
+                    assert_ne!(
+                        token_account_id, NATIVE_TOKEN_ID,
+                        "Native lockups can't be claimed with a message"
+                    );
+                    ext_fungible_token::ft_transfer_call(
+                        receiver_id.clone(),
+                        claim_amount.into(),
+                        memo,
+                        msg,
+                        &token_account_id,
+                        ONE_YOCTO,
+                        GAS_FOR_FT_TRANSFER_CALL,
+                    )
+                } else {
+                    transfer(&token_account_id, receiver_id.clone(), claim_amount, memo)
+                };
+                transfer.then(ext_self::after_ft_transfer(
+                    account_id.clone(),
+                    lockup_claims,
+                    token_account_id,
+                    operator_id.clone(),
+                    &env::current_account_id(),
+                    NO_DEPOSIT,
+                    GAS_FOR_AFTER_FT_TRANSFER,
+                ))
+            })
+            .reduce(|transfers, transfer| transfers.and(transfer))
+            .unwrap();
+
+        if num_tokens == 1 {
+            transfers.into()
+        } else {
+            // a joint promise can't be returned, so the claimed amounts are summed up instead
+            transfers
+                .then(ext_self::after_ft_transfers(
+                    &env::current_account_id(),
+                    NO_DEPOSIT,
+                    GAS_FOR_AFTER_FT_TRANSFERS,
+                ))
+                .into()
+        }
+    }
+
+    pub(crate) fn internal_save_claim_operators(
+        &mut self,
+        account_id: &AccountId,
+        operators: HashMap<AccountId, Option<ClaimAllowances>>,
+    ) {
+        if operators.is_empty() {
+            self.claim_operators.remove(account_id);
+        } else {
+            self.claim_operators.insert(account_id, &operators);
+        }
+    }
+
+    pub(crate) fn internal_spend_claim_allowance(
+        &mut self,
+        account_id: &AccountId,
+        operator_id: &AccountId,
+        token_account_id: &TokenAccountId,
+        amount: Balance,
+    ) {
+        let mut operators = self.claim_operators.get(account_id).unwrap_or_default();
+        let allowances = operators
+            .get_mut(operator_id)
+            .expect("Not an approved claim operator");
+        if let Some(allowances) = allowances {
+            // the tokens without an allowance can't be claimed
+            let allowance = allowances
+                .get(token_account_id)
+                .copied()
+                .unwrap_or(0)
+                .checked_sub(amount)
+                .expect("The claim exceeds the operator allowance");
+            allowances.insert(token_account_id.clone(), allowance);
+            self.internal_save_claim_operators(account_id, operators);
+        }
+    }
 }
@@ -5,42 +5,64 @@ use near_contract_standards::fungible_token::receiver::FungibleTokenReceiver;
 use near_sdk::borsh::maybestd::collections::{HashMap, HashSet};
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
 use near_sdk::collections::{LookupMap, UnorderedMap, UnorderedSet, Vector};
-use near_sdk::json_types::{Base58CryptoHash, ValidAccountId, WrappedBalance, U128};
+use near_sdk::json_types::{Base58CryptoHash, Base64VecU8, ValidAccountId, WrappedBalance, U128};
 use near_sdk::serde::{Deserialize, Serialize};
 use near_sdk::{
-    assert_one_yocto, env, ext_contract, is_promise_success, log, near_bindgen, serde_json,
-    AccountId, Balance, BorshStorageKey, CryptoHash, Gas, PanicOnDefault, PromiseOrValue,
+    assert_one_yocto, env, ext_contract, log, near_bindgen, serde_json, AccountId, Balance,
+    BorshStorageKey, CryptoHash, Gas, PanicOnDefault, Promise, PromiseOrValue, PromiseResult,
     Timestamp,
 };
 
+pub mod admin_change;
 pub mod callbacks;
 pub mod draft;
 pub mod event;
 pub mod ft_token_receiver;
 pub mod internal;
 pub mod lockup;
+pub mod migration;
+pub mod pause;
+pub mod role;
 pub mod schedule;
+pub mod storage;
 pub mod termination;
+pub mod termination_approval;
+pub mod upgrade;
 pub mod util;
+pub mod vesting_pause;
 pub mod view;
 
+use crate::admin_change::*;
 use crate::draft::*;
 use crate::event::*;
+use crate::internal::*;
 use crate::lockup::*;
+use crate::migration::*;
+use crate::pause::*;
+use crate::role::*;
 use crate::schedule::*;
+use crate::storage::*;
 use crate::termination::*;
+use crate::termination_approval::*;
+use crate::upgrade::*;
 use crate::util::*;
 
 near_sdk::setup_alloc!();
 
 pub type TimestampSec = u32;
 pub type TokenAccountId = AccountId;
+pub type ClaimAllowances = HashMap<TokenAccountId, Balance>;
 
 pub const PACKAGE_NAME: &str = env!("CARGO_PKG_NAME");
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 
+/// The token account ID of the lockups of native NEAR.
+pub const NATIVE_TOKEN_ID: &str = "near";
+
 const GAS_FOR_FT_TRANSFER: Gas = 15_000_000_000_000;
+const GAS_FOR_FT_TRANSFER_CALL: Gas = 40_000_000_000_000;
 const GAS_FOR_AFTER_FT_TRANSFER: Gas = 20_000_000_000_000;
+const GAS_FOR_AFTER_FT_TRANSFERS: Gas = 5_000_000_000_000;
 const GAS_EXT_CALL_COST: Gas = 10_000_000_000_000;
 const GAS_MIN_FOR_CONVERT: Gas = 15_000_000_000_000;
 
@@ -57,12 +79,18 @@ pub trait SelfCallbacks {
         &mut self,
         account_id: AccountId,
         lockup_claims: Vec<LockupClaim>,
+        token_account_id: TokenAccountId,
+        operator_id: Option<AccountId>,
     ) -> WrappedBalance;
 
+    fn after_ft_transfers(&mut self) -> WrappedBalance;
+
     fn after_lockup_termination(
         &mut self,
         account_id: AccountId,
         amount: WrappedBalance,
+        token_account_id: TokenAccountId,
+        payer_id: Option<AccountId>,
     ) -> WrappedBalance;
 
     fn convert_drafts(&mut self, draft_ids: Vec<DraftIndex>) -> Vec<LockupIndex>;
@@ -71,22 +99,63 @@ pub trait SelfCallbacks {
 #[near_bindgen]
 #[derive(BorshDeserialize, BorshSerialize, PanicOnDefault)]
 pub struct Contract {
+    /// The primary token, used for the lockups created before multi-token support.
     pub token_account_id: TokenAccountId,
 
     pub lockups: Vector<Lockup>,
 
     pub account_lockups: LookupMap<AccountId, HashSet<LockupIndex>>,
 
-    /// account ids that can perform all actions:
-    /// - manage deposit_whitelist
-    /// - manage drafts, draft_groups
-    /// - create lockups, terminate lockups, fund draft_groups
+    /// account ids with the admin role, see `Role`
+    pub admins: UnorderedSet<AccountId>,
+
+    /// pending changes of the admins and the deposit whitelist
+    pub admin_change_delay_sec: TimestampSec,
+    pub next_admin_proposal_id: AdminProposalIndex,
+    pub admin_proposals: UnorderedMap<AdminProposalIndex, AdminProposal>,
+
+    /// account ids with the depositor role:
+    /// - create lockups, fund draft_groups
     pub deposit_whitelist: UnorderedSet<AccountId>,
 
-    /// account ids that can perform all actions on drafts:
+    /// account ids with the draft operator role:
     /// - manage drafts, draft_groups
     pub draft_operators_whitelist: UnorderedSet<AccountId>,
 
+    /// account ids with the terminator role, that can terminate any terminable lockup
+    pub terminators: UnorderedSet<AccountId>,
+
+    /// account ids that can terminate lockups on behalf of a termination beneficiary
+    pub termination_delegates: LookupMap<AccountId, HashSet<AccountId>>,
+
+    /// account ids that can claim lockups on behalf of an account, with optional allowances
+    /// per token
+    pub claim_operators: LookupMap<AccountId, HashMap<AccountId, Option<ClaimAllowances>>>,
+
+    /// optional M-of-N approvals of the terminations
+    pub termination_approval_config: Option<TerminationApprovalConfig>,
+    pub next_termination_request_id: TerminationRequestIndex,
+    pub termination_requests: UnorderedMap<TerminationRequestIndex, TerminationRequest>,
+
+    /// token account ids that can be locked up
+    pub token_whitelist: UnorderedSet<TokenAccountId>,
+
+    /// storage deposits of the accounts that pay for lockups and drafts
+    pub storage_accounts: LookupMap<AccountId, StorageAccount>,
+    pub storage_stakes: LookupMap<StorageStakeId, StorageStake>,
+
+    /// account ids with the pauser role, that can pause and unpause the contract methods
+    pub guardians_whitelist: UnorderedSet<AccountId>,
+    pub paused: HashSet<PauseFlag>,
+
+    /// the delay between staging an upgrade and deploying it
+    pub upgrade_delay_sec: TimestampSec,
+    pub staged_upgrade: Option<StagedUpgrade>,
+    pub staged_upgrade_delay: Option<StagedUpgradeDelay>,
+
+    /// the records of the previous state layout that are not rewritten yet
+    pub migration: Option<Migration>,
+
     pub next_draft_id: DraftIndex,
     pub drafts: LookupMap<DraftIndex, Draft>,
     pub next_draft_group_id: DraftGroupIndex,
@@ -101,6 +170,17 @@ pub(crate) enum StorageKey {
     DraftOperatorsWhitelist,
     Drafts,
     DraftGroups,
+    TerminationDelegates,
+    ClaimOperators,
+    TokenWhitelist,
+    StorageAccounts,
+    StorageStakes,
+    StateVersion,
+    GuardiansWhitelist,
+    Admins,
+    Terminators,
+    AdminProposals,
+    TerminationRequests,
 }
 
 #[near_bindgen]
@@ -113,6 +193,9 @@ impl Contract {
     ) -> Self {
         let mut deposit_whitelist_set = UnorderedSet::new(StorageKey::DepositWhitelist);
         deposit_whitelist_set.extend(deposit_whitelist.clone().into_iter().map(|a| a.into()));
+        // the initial deposit whitelist keeps managing the contract
+        let mut admins_set = UnorderedSet::new(StorageKey::Admins);
+        admins_set.extend(deposit_whitelist.clone().into_iter().map(|a| a.into()));
         let mut draft_operators_whitelist_set =
             UnorderedSet::new(StorageKey::DraftOperatorsWhitelist);
         draft_operators_whitelist_set.extend(
@@ -122,6 +205,10 @@ impl Contract {
                 .into_iter()
                 .map(|a| a.into()),
         );
+        write_state_version();
+        assert_not_native_token(token_account_id.as_ref());
+        let mut token_whitelist_set = UnorderedSet::new(StorageKey::TokenWhitelist);
+        token_whitelist_set.insert(token_account_id.as_ref());
         emit(EventKind::FtLockupNew(FtLockupNew {
             token_account_id: token_account_id.clone().into(),
         }));
@@ -143,8 +230,27 @@ impl Contract {
             lockups: Vector::new(StorageKey::Lockups),
             account_lockups: LookupMap::new(StorageKey::AccountLockups),
             token_account_id: token_account_id.into(),
+            admins: admins_set,
+            admin_change_delay_sec: DEFAULT_ADMIN_CHANGE_DELAY_SEC,
+            next_admin_proposal_id: 0,
+            admin_proposals: UnorderedMap::new(StorageKey::AdminProposals),
             deposit_whitelist: deposit_whitelist_set,
             draft_operators_whitelist: draft_operators_whitelist_set,
+            terminators: UnorderedSet::new(StorageKey::Terminators),
+            termination_delegates: LookupMap::new(StorageKey::TerminationDelegates),
+            claim_operators: LookupMap::new(StorageKey::ClaimOperators),
+            termination_approval_config: None,
+            next_termination_request_id: 0,
+            termination_requests: UnorderedMap::new(StorageKey::TerminationRequests),
+            token_whitelist: token_whitelist_set,
+            storage_accounts: LookupMap::new(StorageKey::StorageAccounts),
+            storage_stakes: LookupMap::new(StorageKey::StorageStakes),
+            guardians_whitelist: UnorderedSet::new(StorageKey::GuardiansWhitelist),
+            paused: HashSet::new(),
+            upgrade_delay_sec: DEFAULT_UPGRADE_DELAY_SEC,
+            staged_upgrade: None,
+            staged_upgrade_delay: None,
+            migration: None,
             next_draft_id: 0,
             drafts: LookupMap::new(StorageKey::Drafts),
             next_draft_group_id: 0,
@@ -152,225 +258,510 @@ impl Contract {
         }
     }
 
+    /// Claims unlocked balances of the caller's lockups. The tokens are sent to `receiver_id`
+    /// (the caller by default). If `msg` is given, they are sent using `ft_transfer_call`
+    /// and the claimed balance of the part refunded by the receiver is restored.
     pub fn claim(
         &mut self,
         amounts: Option<Vec<(LockupIndex, Option<WrappedBalance>)>>,
+        receiver_id: Option<ValidAccountId>,
+        msg: Option<String>,
     ) -> PromiseOrValue<WrappedBalance> {
+        self.assert_not_paused(PauseFlag::Claiming);
+        self.internal_claim(
+            env::predecessor_account_id(),
+            amounts,
+            receiver_id.map(|receiver_id| receiver_id.into()),
+            msg,
+            None,
+        )
+    }
+
+    /// Claims unlocked balances of the given account's lockups by an approved claim operator.
+    /// The tokens are always sent to the lockup owner.
+    pub fn claim_for(
+        &mut self,
+        account_id: ValidAccountId,
+        amounts: Option<Vec<(LockupIndex, Option<WrappedBalance>)>>,
+    ) -> PromiseOrValue<WrappedBalance> {
+        self.assert_not_paused(PauseFlag::Claiming);
+        let operator_id = env::predecessor_account_id();
+        let account_id: AccountId = account_id.into();
+        assert!(
+            self.claim_operators
+                .get(&account_id)
+                .map(|operators| operators.contains_key(&operator_id))
+                .unwrap_or(false),
+            "Not an approved claim operator"
+        );
+        self.internal_claim(account_id, amounts, None, None, Some(operator_id))
+    }
+
+    /// `None` allowances let the operator claim any token without a limit, otherwise only
+    /// the given tokens up to their allowances can be claimed.
+    #[payable]
+    pub fn approve_claim_operator(
+        &mut self,
+        operator_id: ValidAccountId,
+        allowances: Option<HashMap<TokenAccountId, WrappedBalance>>,
+    ) {
+        assert_one_yocto();
         let account_id = env::predecessor_account_id();
+        let operator_id: AccountId = operator_id.into();
+        assert_ne!(account_id, operator_id, "Cannot approve self");
+        let mut operators = self.claim_operators.get(&account_id).unwrap_or_default();
+        operators.insert(
+            operator_id.clone(),
+            allowances.as_ref().map(|allowances| {
+                allowances
+                    .iter()
+                    .map(|(token_account_id, allowance)| (token_account_id.clone(), allowance.0))
+                    .collect()
+            }),
+        );
+        self.internal_save_claim_operators(&account_id, operators);
 
-        let (claim_amounts, mut lockups_by_id) = if let Some(amounts) = amounts {
-            let lockups_by_id: HashMap<LockupIndex, Lockup> = self
-                .internal_get_account_lockups_by_id(
-                    &account_id,
-                    &amounts.iter().map(|x| x.0).collect(),
-                )
-                .into_iter()
-                .collect();
-            let amounts: HashMap<LockupIndex, WrappedBalance> = amounts
-                .into_iter()
-                .map(|(lockup_id, amount)| {
-                    (
-                        lockup_id,
-                        match amount {
-                            Some(amount) => amount,
-                            None => {
-                                let lockup =
-                                    lockups_by_id.get(&lockup_id).expect("lockup not found");
-                                let unlocked_balance =
-                                    lockup.schedule.unlocked_balance(current_timestamp_sec());
-                                (unlocked_balance - lockup.claimed_balance).into()
-                            }
-                        },
-                    )
-                })
-                .collect();
-            (amounts, lockups_by_id)
-        } else {
-            let lockups_by_id: HashMap<LockupIndex, Lockup> = self
-                .internal_get_account_lockups(&account_id)
-                .into_iter()
-                .collect();
-            let amounts: HashMap<LockupIndex, WrappedBalance> = lockups_by_id
-                .iter()
-                .map(|(lockup_id, lockup)| {
-                    let unlocked_balance =
-                        lockup.schedule.unlocked_balance(current_timestamp_sec());
-                    let amount: WrappedBalance = (unlocked_balance - lockup.claimed_balance).into();
-
-                    (lockup_id.clone(), amount)
-                })
-                .collect();
-            (amounts, lockups_by_id)
-        };
+        emit(EventKind::FtLockupApproveClaimOperator(vec![
+            FtLockupApproveClaimOperator {
+                account_id,
+                operator_id,
+                allowances,
+            },
+        ]));
+    }
 
+    #[payable]
+    pub fn revoke_claim_operator(&mut self, operator_id: ValidAccountId) {
+        assert_one_yocto();
         let account_id = env::predecessor_account_id();
-        let mut lockup_claims = vec![];
-        let mut total_claim_amount = 0;
-        for (lockup_index, lockup_claim_amount) in claim_amounts {
-            let lockup = lockups_by_id.get_mut(&lockup_index).unwrap();
-            let lockup_claim = lockup.claim(lockup_index, lockup_claim_amount.0);
-
-            if lockup_claim.claim_amount.0 > 0 {
-                log!(
-                    "Claiming {} form lockup #{}",
-                    lockup_claim.claim_amount.0,
-                    lockup_index
-                );
-                total_claim_amount += lockup_claim.claim_amount.0;
-                self.lockups.replace(lockup_index as _, &lockup);
-                lockup_claims.push(lockup_claim);
-            }
-        }
-        log!("Total claim {}", total_claim_amount);
-
-        if total_claim_amount > 0 {
-            ext_fungible_token::ft_transfer(
-                account_id.clone(),
-                total_claim_amount.into(),
-                Some(format!(
-                    "Claiming unlocked {} balance from {}",
-                    total_claim_amount,
-                    env::current_account_id()
-                )),
-                &self.token_account_id,
-                ONE_YOCTO,
-                GAS_FOR_FT_TRANSFER,
-            )
-            .then(ext_self::after_ft_transfer(
+        let operator_id: AccountId = operator_id.into();
+        let mut operators = self.claim_operators.get(&account_id).unwrap_or_default();
+        assert!(
+            operators.remove(&operator_id).is_some(),
+            "Not an approved claim operator"
+        );
+        self.internal_save_claim_operators(&account_id, operators);
+
+        emit(EventKind::FtLockupRevokeClaimOperator(vec![
+            FtLockupRevokeClaimOperator {
                 account_id,
-                lockup_claims,
-                &env::current_account_id(),
-                NO_DEPOSIT,
-                GAS_FOR_AFTER_FT_TRANSFER,
-            ))
-            .into()
-        } else {
-            PromiseOrValue::Value(0.into())
-        }
+                operator_id,
+            },
+        ]));
     }
 
+    /// Creates a lockup of native NEAR from the attached deposit.
+    #[payable]
+    pub fn create_native_lockup(&mut self, lockup_create: LockupCreate) -> LockupIndex {
+        self.assert_not_paused(PauseFlag::Creation);
+        let payer_id: ValidAccountId = env::predecessor_account_id().try_into().unwrap();
+        self.assert_deposit_whitelist(payer_id.as_ref());
+        let lockup = lockup_create.into_lockup(&payer_id, &NATIVE_TOKEN_ID.to_string());
+        lockup.assert_new_valid(env::attached_deposit());
+        let initial_storage_usage = env::storage_usage();
+        let index = self.internal_add_lockup(&lockup);
+        self.internal_stake_storage(
+            StorageStakeId::Lockup(index),
+            payer_id.as_ref(),
+            initial_storage_usage,
+        );
+        log!(
+            "Created new native lockup for {} with index {}",
+            lockup.account_id.as_ref(),
+            index
+        );
+        let event: FtLockupCreateLockup = (index, lockup, None).into();
+        emit(EventKind::FtLockupCreateLockup(vec![event]));
+
+        index
+    }
+
+    /// Terminates the lockup right away. If termination approvals are configured,
+    /// the termination has to be requested with `request_termination` instead.
     #[payable]
     pub fn terminate(
         &mut self,
         lockup_index: LockupIndex,
         hashed_schedule: Option<Schedule>,
         termination_timestamp: Option<TimestampSec>,
+        salt: Option<Base64VecU8>,
     ) -> PromiseOrValue<WrappedBalance> {
         assert_one_yocto();
-        self.assert_deposit_whitelist(&env::predecessor_account_id());
+        self.assert_not_paused(PauseFlag::Termination);
+        assert!(
+            self.termination_approval_config.is_none(),
+            "The termination requires approvals"
+        );
+        let lockup = self
+            .lockups
+            .get(lockup_index as _)
+            .expect("Lockup not found");
+        self.assert_can_terminate(&lockup, &env::predecessor_account_id());
+        self.internal_terminate(
+            lockup_index,
+            hashed_schedule,
+            termination_timestamp_or_now(termination_timestamp),
+            salt.map(|salt| salt.0),
+        )
+    }
+
+    /// Terminates the lockups at the same timestamp. Each lockup is given with its revealed
+    /// vesting schedule and salt, if they are hidden. The unvested balances are sent with
+    /// one transfer per token and receiver, the total transferred balance is returned.
+    #[payable]
+    pub fn terminate_batch(
+        &mut self,
+        lockups: Vec<(LockupIndex, Option<Schedule>, Option<Base64VecU8>)>,
+        termination_timestamp: Option<TimestampSec>,
+    ) -> PromiseOrValue<WrappedBalance> {
+        assert_one_yocto();
+        self.assert_not_paused(PauseFlag::Termination);
+        assert!(
+            self.termination_approval_config.is_none(),
+            "The termination requires approvals"
+        );
+        assert!(!lockups.is_empty(), "expected lockups to be non-empty");
+        self.internal_terminate_batch(lockups, termination_timestamp_or_now(termination_timestamp))
+    }
+
+    /// Schedules the termination at a future timestamp. Until then the lockup keeps vesting
+    /// and the termination can be cancelled, afterwards anyone can execute it.
+    #[payable]
+    pub fn schedule_termination(
+        &mut self,
+        lockup_index: LockupIndex,
+        hashed_schedule: Option<Schedule>,
+        termination_timestamp: TimestampSec,
+        salt: Option<Base64VecU8>,
+    ) {
+        assert_one_yocto();
+        self.assert_not_paused(PauseFlag::Termination);
+        assert!(
+            self.termination_approval_config.is_none(),
+            "The termination requires approvals"
+        );
         let mut lockup = self
             .lockups
             .get(lockup_index as _)
             .expect("Lockup not found");
-        let current_timestamp = current_timestamp_sec();
-        let termination_timestamp = termination_timestamp.unwrap_or(current_timestamp);
+        self.assert_can_terminate(&lockup, &env::predecessor_account_id());
+        assert!(
+            termination_timestamp > current_timestamp_sec(),
+            "expected termination_timestamp > now"
+        );
         assert!(
-            termination_timestamp >= current_timestamp,
-            "expected termination_timestamp >= now",
+            lockup.scheduled_termination.is_none(),
+            "The termination is already scheduled"
         );
-        let (unvested_balance, beneficiary_id) =
-            lockup.terminate(hashed_schedule, termination_timestamp);
+        // the revealed schedule is checked now, so the termination can be executed later
+        lockup.termination_vested_balance(
+            hashed_schedule.as_ref(),
+            termination_timestamp,
+            salt.as_ref().map(|salt| salt.0.as_slice()),
+        );
+        lockup.scheduled_termination = Some(ScheduledTermination {
+            termination_timestamp,
+            hashed_schedule,
+            salt,
+        });
         self.lockups.replace(lockup_index as _, &lockup);
 
-        // no need to store empty lockup
-        if lockup.schedule.total_balance() == 0 {
-            let lockup_account_id: AccountId = lockup.account_id.into();
-            let mut indices = self
-                .account_lockups
-                .get(&lockup_account_id)
-                .unwrap_or_default();
-            indices.remove(&lockup_index);
-            self.internal_save_account_lockups(&lockup_account_id, indices);
+        emit(EventKind::FtLockupScheduleTermination(vec![
+            FtLockupScheduleTermination {
+                id: lockup_index,
+                termination_timestamp,
+            },
+        ]));
+    }
+
+    /// Takes back the given amount from the unvested balance of the lockup and sends it to
+    /// the termination beneficiary. The rest of the grant can still be terminated.
+    #[payable]
+    pub fn reduce_lockup(
+        &mut self,
+        lockup_index: LockupIndex,
+        amount: WrappedBalance,
+    ) -> PromiseOrValue<WrappedBalance> {
+        assert_one_yocto();
+        self.assert_not_paused(PauseFlag::Termination);
+        assert!(
+            self.termination_approval_config.is_none(),
+            "The termination requires approvals"
+        );
+        let mut lockup = self
+            .lockups
+            .get(lockup_index as _)
+            .expect("Lockup not found");
+        self.assert_can_terminate(&lockup, &env::predecessor_account_id());
+        let clawback_receiver = lockup.reduce(amount.0);
+        self.lockups.replace(lockup_index as _, &lockup);
+
+        emit(EventKind::FtLockupReduceLockup(vec![
+            FtLockupReduceLockup {
+                id: lockup_index,
+                amount,
+                token_account_id: lockup.token_account_id.clone(),
+            },
+        ]));
+
+        clawback(
+            &lockup.token_account_id,
+            clawback_receiver,
+            amount.0,
+            Some(format!("Reduced lockup #{}", lockup_index)),
+            self.internal_storage_payer(&StorageStakeId::Lockup(lockup_index)),
+        )
+        .into()
+    }
+
+    #[payable]
+    pub fn cancel_scheduled_termination(&mut self, lockup_index: LockupIndex) {
+        assert_one_yocto();
+        let mut lockup = self
+            .lockups
+            .get(lockup_index as _)
+            .expect("Lockup not found");
+        self.assert_can_terminate(&lockup, &env::predecessor_account_id());
+        let scheduled_termination = lockup
+            .scheduled_termination
+            .take()
+            .expect("No scheduled termination");
+        assert!(
+            current_timestamp_sec() < scheduled_termination.termination_timestamp,
+            "The scheduled termination can't be cancelled after {}",
+            scheduled_termination.termination_timestamp
+        );
+        self.lockups.replace(lockup_index as _, &lockup);
+
+        emit(EventKind::FtLockupCancelScheduledTermination(vec![
+            FtLockupCancelScheduledTermination { id: lockup_index },
+        ]));
+    }
+
+    /// Executes the scheduled termination once its timestamp passes, can be called by anyone.
+    pub fn execute_scheduled_termination(
+        &mut self,
+        lockup_index: LockupIndex,
+    ) -> PromiseOrValue<WrappedBalance> {
+        self.assert_not_paused(PauseFlag::Termination);
+        let lockup = self
+            .lockups
+            .get(lockup_index as _)
+            .expect("Lockup not found");
+        let ScheduledTermination {
+            termination_timestamp,
+            hashed_schedule,
+            salt,
+        } = lockup
+            .scheduled_termination
+            .expect("No scheduled termination");
+        assert!(
+            current_timestamp_sec() >= termination_timestamp,
+            "The termination is scheduled at {}",
+            termination_timestamp
+        );
+        self.internal_terminate(
+            lockup_index,
+            hashed_schedule,
+            termination_timestamp,
+            salt.map(|salt| salt.0),
+        )
+    }
+
+    #[payable]
+    pub fn transfer_lockup(&mut self, lockup_index: LockupIndex, new_account_id: ValidAccountId) {
+        assert_one_yocto();
+        let account_id = env::predecessor_account_id();
+        let mut lockup = self.internal_get_account_lockup(&account_id, lockup_index);
+        assert!(lockup.transferable, "The lockup is not transferable");
+        assert_ne!(
+            new_account_id.as_ref(),
+            &account_id,
+            "The lockup already belongs to the account"
+        );
+
+        lockup.account_id = new_account_id.clone();
+        self.lockups.replace(lockup_index as _, &lockup);
+        self.internal_remove_account_lockup(&account_id, lockup_index);
+        self.internal_add_account_lockup(new_account_id.as_ref(), lockup_index);
+
+        emit(EventKind::FtLockupTransferLockup(vec![
+            FtLockupTransferLockup {
+                id: lockup_index,
+                account_id,
+                new_account_id: new_account_id.into(),
+            },
+        ]));
+    }
+
+    /// The owner pays for the storage of the new lockup, so it has to be registered.
+    #[payable]
+    pub fn split_lockup(
+        &mut self,
+        lockup_index: LockupIndex,
+        amount: WrappedBalance,
+        new_account_id: ValidAccountId,
+    ) -> LockupIndex {
+        assert_one_yocto();
+        let account_id = env::predecessor_account_id();
+        let mut lockup = self.internal_get_account_lockup(&account_id, lockup_index);
+        assert!(
+            lockup.transferable || new_account_id.as_ref() == &account_id,
+            "The lockup is not transferable"
+        );
+
+        let new_lockup = lockup.split(amount.0, new_account_id.clone());
+        self.lockups.replace(lockup_index as _, &lockup);
+        let initial_storage_usage = env::storage_usage();
+        let new_lockup_index = self.internal_add_lockup(&new_lockup);
+        self.internal_stake_storage(
+            StorageStakeId::Lockup(new_lockup_index),
+            &account_id,
+            initial_storage_usage,
+        );
+
+        // no need to keep fully claimed lockups
+        if lockup.claimed_balance == lockup.schedule.total_balance() {
+            self.internal_remove_account_lockup(&account_id, lockup_index);
+            self.internal_release_storage(StorageStakeId::Lockup(lockup_index));
+        }
+        if new_lockup.claimed_balance == new_lockup.schedule.total_balance() {
+            self.internal_remove_account_lockup(new_account_id.as_ref(), new_lockup_index);
+            self.internal_release_storage(StorageStakeId::Lockup(new_lockup_index));
         }
 
-        let event = FtLockupTerminateLockup {
+        emit(EventKind::FtLockupSplitLockup(vec![FtLockupSplitLockup {
             id: lockup_index,
-            termination_timestamp,
-            unvested_balance: unvested_balance.into(),
-        };
-        emit(EventKind::FtLockupTerminateLockup(vec![event]));
-
-        if unvested_balance > 0 {
-            ext_fungible_token::ft_transfer(
-                beneficiary_id.clone(),
-                unvested_balance.into(),
-                Some(format!("Terminated lockup #{}", lockup_index)),
-                &self.token_account_id,
-                ONE_YOCTO,
-                GAS_FOR_FT_TRANSFER,
-            )
-            .then(ext_self::after_lockup_termination(
-                beneficiary_id,
-                unvested_balance.into(),
-                &env::current_account_id(),
-                NO_DEPOSIT,
-                GAS_FOR_AFTER_FT_TRANSFER,
-            ))
-            .into()
-        } else {
-            PromiseOrValue::Value(0.into())
+            new_id: new_lockup_index,
+            new_account_id: new_account_id.into(),
+            amount,
+        }]));
+
+        new_lockup_index
+    }
+
+    /// The owner pays for the storage of the merged lockup, so it has to be registered.
+    #[payable]
+    pub fn merge_lockups(&mut self, lockup_indices: Vec<LockupIndex>) -> LockupIndex {
+        assert_one_yocto();
+        let account_id = env::predecessor_account_id();
+        let lockup_ids: HashSet<LockupIndex> = lockup_indices.into_iter().collect();
+        assert!(lockup_ids.len() >= 2, "At least two lockups are required");
+        let mut lockups = self.internal_get_account_lockups_by_id(&account_id, &lockup_ids);
+        lockups.sort_by_key(|(index, _)| *index);
+
+        let lockup = Lockup::merge(account_id.clone().try_into().unwrap(), &lockups);
+        let mut source_ids: Vec<LockupIndex> = vec![];
+        for (index, mut source) in lockups {
+            // the source only keeps the claimed balance, so a refund of a claim in flight
+            // makes exactly the refunded amount claimable from it again
+            source
+                .schedule
+                .terminate(source.claimed_balance, current_timestamp_sec());
+            self.lockups.replace(index as _, &source);
+            source_ids.push(index);
         }
+        for index in &source_ids {
+            self.internal_remove_account_lockup(&account_id, *index);
+            self.internal_release_storage(StorageStakeId::Lockup(*index));
+        }
+        let initial_storage_usage = env::storage_usage();
+        let lockup_index = self.internal_add_lockup(&lockup);
+        self.internal_stake_storage(
+            StorageStakeId::Lockup(lockup_index),
+            &account_id,
+            initial_storage_usage,
+        );
+
+        emit(EventKind::FtLockupMergeLockups(vec![
+            FtLockupMergeLockups {
+                id: lockup_index,
+                account_id,
+                source_ids,
+            },
+        ]));
+
+        lockup_index
     }
 
     // preserving both options for API compatibility
+    /// Proposes to grant both the admin and the depositor roles,
+    /// as the deposit whitelist used to have.
     #[payable]
     pub fn add_to_deposit_whitelist(
         &mut self,
         account_id: Option<ValidAccountId>,
         account_ids: Option<Vec<ValidAccountId>>,
-    ) {
+    ) -> AdminProposalIndex {
         assert_one_yocto();
-        self.assert_deposit_whitelist(&env::predecessor_account_id());
+        self.assert_whitelist_admin(&env::predecessor_account_id());
         let account_ids = if let Some(account_ids) = account_ids {
             account_ids
         } else {
             vec![account_id.expect("expected either account_id or account_ids")]
         };
-        for account_id in &account_ids {
-            self.deposit_whitelist.insert(account_id.as_ref());
-        }
-        emit(EventKind::FtLockupAddToDepositWhitelist(
-            FtLockupAddToDepositWhitelist {
-                account_ids: account_ids.into_iter().map(|x| x.into()).collect(),
-            },
-        ));
+        self.internal_propose_admin_change(AdminChange::GrantRoles {
+            roles: vec![Role::Admin, Role::Depositor],
+            account_ids,
+        })
     }
 
     // preserving both options for API compatibility
+    /// Proposes to revoke both the admin and the depositor roles.
     #[payable]
     pub fn remove_from_deposit_whitelist(
         &mut self,
         account_id: Option<ValidAccountId>,
         account_ids: Option<Vec<ValidAccountId>>,
-    ) {
+    ) -> AdminProposalIndex {
         assert_one_yocto();
-        self.assert_deposit_whitelist(&env::predecessor_account_id());
+        self.assert_whitelist_admin(&env::predecessor_account_id());
         let account_ids = if let Some(account_ids) = account_ids {
             account_ids
         } else {
             vec![account_id.expect("expected either account_id or account_ids")]
         };
+        self.internal_propose_admin_change(AdminChange::RevokeRoles {
+            roles: vec![Role::Depositor, Role::Admin],
+            account_ids,
+        })
+    }
+
+    #[payable]
+    pub fn add_to_token_whitelist(&mut self, account_ids: Vec<ValidAccountId>) {
+        assert_one_yocto();
+        self.assert_admin(&env::predecessor_account_id());
         for account_id in &account_ids {
-            self.deposit_whitelist.remove(&account_id.to_string());
+            assert_not_native_token(account_id.as_ref());
+            self.token_whitelist.insert(account_id.as_ref());
         }
-        assert!(
-            !self.deposit_whitelist.is_empty(),
-            "cannot remove all accounts from deposit whitelist",
-        );
-        emit(EventKind::FtLockupRemoveFromDepositWhitelist(
-            FtLockupRemoveFromDepositWhitelist {
+        emit(EventKind::FtLockupAddToTokenWhitelist(
+            FtLockupAddToTokenWhitelist {
                 account_ids: account_ids.into_iter().map(|x| x.into()).collect(),
             },
         ));
     }
 
+    /// Existing lockups of the removed tokens can still be claimed and terminated.
     #[payable]
-    pub fn add_to_draft_operators_whitelist(&mut self, account_ids: Vec<ValidAccountId>) {
+    pub fn remove_from_token_whitelist(&mut self, account_ids: Vec<ValidAccountId>) {
         assert_one_yocto();
-        self.assert_deposit_whitelist(&env::predecessor_account_id());
+        self.assert_admin(&env::predecessor_account_id());
         for account_id in &account_ids {
-            self.draft_operators_whitelist.insert(account_id.as_ref());
+            self.token_whitelist.remove(account_id.as_ref());
         }
+        emit(EventKind::FtLockupRemoveFromTokenWhitelist(
+            FtLockupRemoveFromTokenWhitelist {
+                account_ids: account_ids.into_iter().map(|x| x.into()).collect(),
+            },
+        ));
+    }
+
+    #[payable]
+    pub fn add_to_draft_operators_whitelist(&mut self, account_ids: Vec<ValidAccountId>) {
+        assert_one_yocto();
+        self.assert_whitelist_admin(&env::predecessor_account_id());
+        self.internal_grant_role(Role::DraftOperator, &account_ids);
         emit(EventKind::FtLockupAddToDraftOperatorsWhitelist(
             FtLockupAddToDraftOperatorsWhitelist {
                 account_ids: account_ids.into_iter().map(|x| x.into()).collect(),
@@ -381,10 +772,8 @@ impl Contract {
     #[payable]
     pub fn remove_from_draft_operators_whitelist(&mut self, account_ids: Vec<ValidAccountId>) {
         assert_one_yocto();
-        self.assert_deposit_whitelist(&env::predecessor_account_id());
-        for account_id in &account_ids {
-            self.draft_operators_whitelist.remove(account_id.as_ref());
-        }
+        self.assert_whitelist_admin(&env::predecessor_account_id());
+        self.internal_revoke_role(Role::DraftOperator, &account_ids);
         emit(EventKind::FtLockupRemoveFromDraftOperatorsWhitelist(
             FtLockupRemoveFromDraftOperatorsWhitelist {
                 account_ids: account_ids.into_iter().map(|x| x.into()).collect(),
@@ -392,6 +781,46 @@ impl Contract {
         ));
     }
 
+    #[payable]
+    pub fn add_termination_delegates(&mut self, account_ids: Vec<ValidAccountId>) {
+        assert_one_yocto();
+        let beneficiary_id = env::predecessor_account_id();
+        let mut delegates = self
+            .termination_delegates
+            .get(&beneficiary_id)
+            .unwrap_or_default();
+        for account_id in &account_ids {
+            delegates.insert(account_id.to_string());
+        }
+        self.internal_save_termination_delegates(&beneficiary_id, delegates);
+        emit(EventKind::FtLockupAddTerminationDelegates(
+            FtLockupAddTerminationDelegates {
+                beneficiary_id,
+                account_ids: account_ids.into_iter().map(|x| x.into()).collect(),
+            },
+        ));
+    }
+
+    #[payable]
+    pub fn remove_termination_delegates(&mut self, account_ids: Vec<ValidAccountId>) {
+        assert_one_yocto();
+        let beneficiary_id = env::predecessor_account_id();
+        let mut delegates = self
+            .termination_delegates
+            .get(&beneficiary_id)
+            .unwrap_or_default();
+        for account_id in &account_ids {
+            delegates.remove(account_id.as_ref());
+        }
+        self.internal_save_termination_delegates(&beneficiary_id, delegates);
+        emit(EventKind::FtLockupRemoveTerminationDelegates(
+            FtLockupRemoveTerminationDelegates {
+                beneficiary_id,
+                account_ids: account_ids.into_iter().map(|x| x.into()).collect(),
+            },
+        ));
+    }
+
     pub fn create_draft_group(&mut self) -> DraftGroupIndex {
         self.assert_draft_operators_whitelist(&env::predecessor_account_id());
 
@@ -415,7 +844,8 @@ impl Contract {
     }
 
     pub fn create_drafts(&mut self, drafts: Vec<Draft>) -> Vec<DraftIndex> {
-        self.assert_draft_operators_whitelist(&env::predecessor_account_id());
+        let payer_id = env::predecessor_account_id();
+        self.assert_draft_operators_whitelist(&payer_id);
         let mut draft_group_lookup: HashMap<DraftGroupIndex, DraftGroup> = HashMap::new();
         let mut events: Vec<FtLockupCreateDraft> = vec![];
         let draft_ids: Vec<DraftIndex> = drafts
@@ -433,7 +863,13 @@ impl Contract {
 
                 let index = self.next_draft_id;
                 self.next_draft_id += 1;
+                let initial_storage_usage = env::storage_usage();
                 assert!(self.drafts.insert(&index, &draft).is_none(), "Invariant");
+                self.internal_stake_storage(
+                    StorageStakeId::Draft(index),
+                    &payer_id,
+                    initial_storage_usage,
+                );
                 draft_group.total_amount = draft_group
                     .total_amount
                     .checked_add(draft.total_balance())
@@ -461,6 +897,7 @@ impl Contract {
     }
 
     pub fn convert_drafts(&mut self, draft_ids: Vec<DraftIndex>) -> Vec<LockupIndex> {
+        self.assert_not_paused(PauseFlag::DraftConversion);
         let mut draft_group_lookup: HashMap<DraftGroupIndex, DraftGroup> = HashMap::new();
         let mut events: Vec<FtLockupCreateLockup> = vec![];
         let lockup_ids: Vec<LockupIndex> = draft_ids
@@ -477,16 +914,34 @@ impl Contract {
                 draft_group.assert_can_convert_draft();
                 let payer_id = draft_group
                     .payer_id
-                    .as_mut()
+                    .as_ref()
                     .expect("expected present payer_id");
+                let token_account_id = draft_group
+                    .token_account_id
+                    .clone()
+                    .unwrap_or_else(|| self.token_account_id.clone());
 
                 assert!(draft_group.draft_indices.remove(&draft_id), "Invariant");
                 let amount = draft.total_balance();
                 assert!(draft_group.total_amount >= amount, "Invariant");
                 draft_group.total_amount -= amount;
 
-                let lockup = draft.lockup_create.into_lockup(&payer_id);
+                let mut lockup = draft.lockup_create.into_lockup(payer_id, &token_account_id);
+                if let Some(termination_config) = lockup.termination_config.as_mut() {
+                    termination_config.clawback_receiver = draft_group.clawback_receiver.clone();
+                }
+                // the draft storage payer pays for the lockup storage instead
+                let storage_payer_id =
+                    self.internal_release_storage(StorageStakeId::Draft(*draft_id));
+                let initial_storage_usage = env::storage_usage();
                 let index = self.internal_add_lockup(&lockup);
+                if let Some(storage_payer_id) = storage_payer_id {
+                    self.internal_stake_storage(
+                        StorageStakeId::Lockup(index),
+                        &storage_payer_id,
+                        initial_storage_usage,
+                    );
+                }
 
                 let event: FtLockupCreateLockup = (index, lockup, Some(draft_id.clone())).into();
                 events.push(event);
@@ -510,6 +965,67 @@ impl Contract {
         lockup_ids
     }
 
+    /// Replaces the draft of a draft group that isn't funded yet.
+    pub fn update_draft(&mut self, draft_id: DraftIndex, draft: Draft) {
+        self.assert_draft_operators_whitelist(&env::predecessor_account_id());
+        let old_draft = self.drafts.get(&draft_id).expect("draft not found");
+        assert_eq!(
+            old_draft.draft_group_id, draft.draft_group_id,
+            "cannot move draft to another draft group"
+        );
+        let mut draft_group = self
+            .draft_groups
+            .get(&draft.draft_group_id as _)
+            .expect("draft group not found");
+        draft_group.assert_can_edit_draft();
+        draft.assert_new_valid();
+
+        draft_group.total_amount = (draft_group.total_amount - old_draft.total_balance())
+            .checked_add(draft.total_balance())
+            .expect("attempt to add with overflow");
+        self.draft_groups
+            .insert(&draft.draft_group_id as _, &draft_group);
+
+        // the draft storage payer pays for the updated draft
+        let storage_payer_id = self.internal_release_storage(StorageStakeId::Draft(draft_id));
+        self.drafts.remove(&draft_id);
+        let initial_storage_usage = env::storage_usage();
+        self.drafts.insert(&draft_id, &draft);
+        if let Some(storage_payer_id) = storage_payer_id {
+            self.internal_stake_storage(
+                StorageStakeId::Draft(draft_id),
+                &storage_payer_id,
+                initial_storage_usage,
+            );
+        }
+
+        let event: FtLockupUpdateDraft = (draft_id, draft).into();
+        emit(EventKind::FtLockupUpdateDraft(vec![event]));
+    }
+
+    /// Removes the draft from a draft group that isn't funded yet.
+    pub fn remove_draft(&mut self, draft_id: DraftIndex) {
+        self.assert_draft_operators_whitelist(&env::predecessor_account_id());
+        let draft = self.drafts.remove(&draft_id).expect("draft not found");
+        let mut draft_group = self
+            .draft_groups
+            .get(&draft.draft_group_id as _)
+            .expect("draft group not found");
+        draft_group.assert_can_edit_draft();
+
+        let amount = draft.total_balance();
+        assert!(draft_group.total_amount >= amount, "Invariant");
+        draft_group.total_amount -= amount;
+        assert!(draft_group.draft_indices.remove(&draft_id), "Invariant");
+        self.draft_groups
+            .insert(&draft.draft_group_id as _, &draft_group);
+        self.internal_release_storage(StorageStakeId::Draft(draft_id));
+
+        emit(EventKind::FtLockupRemoveDraft(vec![FtLockupRemoveDraft {
+            id: draft_id,
+        }]));
+    }
+
     pub fn discard_draft_group(&mut self, draft_group_id: DraftGroupIndex) {
         self.assert_draft_operators_whitelist(&env::predecessor_account_id());
 
@@ -550,6 +1066,7 @@ impl Contract {
             draft_group.total_amount -= amount;
 
             assert!(draft_group.draft_indices.remove(draft_id), "Invariant");
+            self.internal_release_storage(StorageStakeId::Draft(*draft_id));
 
             let event = FtLockupDeleteDraft {
                 id: draft_id.clone(),
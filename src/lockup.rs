@@ -24,6 +24,17 @@ pub struct Lockup {
     pub claimed_balance: Balance,
     /// An optional configuration that allows vesting/lockup termination.
     pub termination_config: Option<TerminationConfig>,
+    /// Whether the lockup owner can transfer the lockup to another account.
+    #[serde(default)]
+    pub transferable: bool,
+    /// The account ID of the locked up fungible token.
+    pub token_account_id: TokenAccountId,
+    /// The pending termination at a future timestamp.
+    #[serde(default)]
+    pub scheduled_termination: Option<ScheduledTermination>,
+    /// The timestamp since which the vesting is paused.
+    #[serde(default)]
+    pub vesting_paused_since: Option<TimestampSec>,
 }
 
 impl Lockup {
@@ -31,21 +42,30 @@ impl Lockup {
         account_id: AccountId,
         total_balance: Balance,
         timestamp: TimestampSec,
+        token_account_id: TokenAccountId,
     ) -> Self {
         Self {
             account_id: account_id.try_into().unwrap(),
             schedule: Schedule::new_unlocked_since(total_balance, timestamp),
             claimed_balance: 0,
             termination_config: None,
+            transferable: false,
+            token_account_id,
+            scheduled_termination: None,
+            vesting_paused_since: None,
         }
     }
 
-    pub fn new_unlocked(account_id: AccountId, total_balance: Balance) -> Self {
-        Self::new_unlocked_since(account_id, total_balance, 1)
+    pub fn new_unlocked(
+        account_id: AccountId,
+        total_balance: Balance,
+        token_account_id: TokenAccountId,
+    ) -> Self {
+        Self::new_unlocked_since(account_id, total_balance, 1, token_account_id)
     }
 
     pub fn claim(&mut self, index: LockupIndex, claim_amount: Balance) -> LockupClaim {
-        let unlocked_balance = self.schedule.unlocked_balance(current_timestamp_sec());
+        let unlocked_balance = self.unlocked_balance();
         let balance_claimed_new = self
             .claimed_balance
             .checked_add(claim_amount)
@@ -64,6 +84,126 @@ impl Lockup {
         }
     }
 
+    /// Merges non-terminatable lockups of the same account into one lockup.
+    pub fn merge(account_id: ValidAccountId, lockups: &[(LockupIndex, Lockup)]) -> Lockup {
+        for (index, lockup) in lockups {
+            assert!(
+                lockup.termination_config.is_none(),
+                "Cannot merge terminatable lockup #{}",
+                index
+            );
+        }
+        let token_account_id = lockups[0].1.token_account_id.clone();
+        assert!(
+            lockups
+                .iter()
+                .all(|(_, lockup)| lockup.token_account_id == token_account_id),
+            "Cannot merge lockups of different tokens"
+        );
+        let schedules: Vec<&Schedule> =
+            lockups.iter().map(|(_, lockup)| &lockup.schedule).collect();
+        Lockup {
+            account_id,
+            schedule: Schedule::sum(&schedules),
+            claimed_balance: lockups
+                .iter()
+                .map(|(_, lockup)| lockup.claimed_balance)
+                .sum(),
+            termination_config: None,
+            transferable: lockups.iter().all(|(_, lockup)| lockup.transferable),
+            token_account_id,
+            scheduled_termination: None,
+            vesting_paused_since: None,
+        }
+    }
+
+    /// Splits the given amount into a new lockup for the given account.
+    /// Schedules, vesting schedules and claimed balance are split pro rata.
+    pub fn split(&mut self, amount: Balance, account_id: ValidAccountId) -> Lockup {
+        let total_balance = self.schedule.total_balance();
+        assert!(
+            amount > 0 && amount < total_balance,
+            "expected amount to be positive and less than the lockup total balance"
+        );
+        assert!(
+            self.scheduled_termination.is_none(),
+            "Cannot split a lockup with a scheduled termination"
+        );
+        assert!(
+            self.vesting_paused_since.is_none(),
+            "Cannot split a lockup with a paused vesting"
+        );
+        let (schedule, vesting_schedule) = match self
+            .termination_config
+            .as_mut()
+            .map(|termination_config| &mut termination_config.vesting_schedule)
+        {
+            Some(VestingConditions::Hash(_)) | Some(VestingConditions::HashWithSalt(_)) => {
+                env::panic(b"Cannot split a lockup with a hidden vesting schedule")
+            }
+            Some(VestingConditions::Schedule(vesting_schedule)) => {
+                let (schedule, vesting_schedule) =
+                    self.schedule.split_with_vesting(vesting_schedule, amount);
+                (schedule, Some(vesting_schedule))
+            }
+            Some(VestingConditions::SameAsLockupSchedule) | None => {
+                (self.schedule.split(amount), None)
+            }
+        };
+        let termination_config =
+            self.termination_config
+                .as_ref()
+                .map(|termination_config| TerminationConfig {
+                    beneficiary_id: termination_config.beneficiary_id.clone(),
+                    clawback_receiver: termination_config.clawback_receiver.clone(),
+                    vesting_schedule: vesting_schedule
+                        .map(VestingConditions::Schedule)
+                        .unwrap_or(VestingConditions::SameAsLockupSchedule),
+                });
+        let mut lockup = Lockup {
+            account_id,
+            schedule,
+            claimed_balance: 0,
+            termination_config,
+            transferable: self.transferable,
+            token_account_id: self.token_account_id.clone(),
+            scheduled_termination: None,
+            vesting_paused_since: None,
+        };
+
+        // Rounding down the claimed balance and moving the rounding excess to the new lockup
+        // if the remaining lockup can't cover it.
+        let timestamp = current_timestamp_sec();
+        let claimed_balance = std::cmp::min(
+            (U256::from(self.claimed_balance) * U256::from(amount) / U256::from(total_balance))
+                .as_u128(),
+            lockup.schedule.unlocked_balance(timestamp),
+        );
+        let remaining_claimed_balance = self.claimed_balance - claimed_balance;
+        let excess_claimed_balance =
+            remaining_claimed_balance.saturating_sub(self.schedule.unlocked_balance(timestamp));
+        self.claimed_balance = remaining_claimed_balance - excess_claimed_balance;
+        lockup.claimed_balance = claimed_balance + excess_claimed_balance;
+        assert!(
+            lockup.claimed_balance <= lockup.schedule.unlocked_balance(timestamp),
+            "The claimed balance can't be split at the current timestamp"
+        );
+
+        self.assert_valid_vesting_schedule();
+        lockup.assert_valid_vesting_schedule();
+        lockup
+    }
+
+    fn assert_valid_vesting_schedule(&self) {
+        if let Some(TerminationConfig {
+            vesting_schedule: VestingConditions::Schedule(schedule),
+            ..
+        }) = &self.termination_config
+        {
+            self.schedule.assert_valid_termination_schedule(schedule);
+        }
+    }
+
     pub fn assert_new_valid(&self, total_balance: Balance) {
         assert_eq!(
             self.claimed_balance, 0,
@@ -72,11 +212,17 @@ impl Lockup {
         self.schedule.assert_valid(total_balance);
 
         if let Some(termination_config) = &self.termination_config {
+            if let Some(clawback_receiver) = &termination_config.clawback_receiver {
+                assert!(
+                    clawback_receiver.msg.is_none() || self.token_account_id != NATIVE_TOKEN_ID,
+                    "Native lockups can't be clawed back with a message"
+                );
+            }
             match &termination_config.vesting_schedule {
                 VestingConditions::SameAsLockupSchedule => {
                     // Ok, using lockup schedule.
                 }
-                VestingConditions::Hash(_hash) => {
+                VestingConditions::Hash(_) | VestingConditions::HashWithSalt(_) => {
                     // Ok, using unknown hash. Can't verify.
                 }
                 VestingConditions::Schedule(schedule) => {
@@ -93,8 +239,16 @@ impl Lockup {
 #[cfg_attr(not(target_arch = "wasm32"), derive(Debug, PartialEq, Clone))]
 pub struct LockupCreate {
     pub account_id: ValidAccountId,
+    /// Either a list of checkpoints or a `ScheduleSpec` to expand.
+    #[serde(deserialize_with = "deserialize_schedule")]
     pub schedule: Schedule,
     pub vesting_schedule: Option<VestingConditions>,
+    /// Whether the lockup owner can transfer the lockup to another account.
+    #[serde(default)]
+    pub transferable: bool,
+    /// Receives the unvested balance on termination instead of the payer.
+    #[serde(default)]
+    pub clawback_receiver: Option<ClawbackReceiver>,
 }
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -104,12 +258,18 @@ impl LockupCreate {
             account_id,
             schedule: Schedule::new_unlocked(total_balance),
             vesting_schedule: None,
+            transferable: false,
+            clawback_receiver: None,
         }
     }
 }
 
 impl LockupCreate {
-    pub fn into_lockup(&self, payer_id: &ValidAccountId) -> Lockup {
+    pub fn into_lockup(
+        &self,
+        payer_id: &ValidAccountId,
+        token_account_id: &TokenAccountId,
+    ) -> Lockup {
         let vesting_schedule = self.vesting_schedule.clone();
         Lockup {
             account_id: self.account_id.clone(),
@@ -120,8 +280,13 @@ impl LockupCreate {
                 Some(vesting_schedule) => Some(TerminationConfig {
                     beneficiary_id: payer_id.clone(),
                     vesting_schedule,
+                    clawback_receiver: self.clawback_receiver.clone(),
                 }),
             },
+            transferable: self.transferable,
+            token_account_id: token_account_id.clone(),
+            scheduled_termination: None,
+            vesting_paused_since: None,
         }
     }
 }
@@ -0,0 +1,447 @@
+use crate::*;
+
+pub type StateVersion = u32;
+
+/// The version of the current state layout.
+/// The state of the contract version 1.0.0 has no version stored and is version 1.
+pub const STATE_VERSION: StateVersion = 2;
+
+/// The termination config layout of the state version 1.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct TerminationConfigV1 {
+    pub beneficiary_id: ValidAccountId,
+    pub vesting_schedule: VestingConditions,
+}
+
+impl From<TerminationConfigV1> for TerminationConfig {
+    fn from(termination_config: TerminationConfigV1) -> Self {
+        Self {
+            beneficiary_id: termination_config.beneficiary_id,
+            vesting_schedule: termination_config.vesting_schedule,
+            clawback_receiver: None,
+        }
+    }
+}
+
+/// The lockup layout of the state version 1.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct LockupV1 {
+    pub account_id: ValidAccountId,
+    pub schedule: Schedule,
+    pub claimed_balance: Balance,
+    pub termination_config: Option<TerminationConfigV1>,
+}
+
+/// The lockup create layout of the state version 1.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct LockupCreateV1 {
+    pub account_id: ValidAccountId,
+    pub schedule: Schedule,
+    pub vesting_schedule: Option<VestingConditions>,
+}
+
+/// The draft layout of the state version 1.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct DraftV1 {
+    pub draft_group_id: DraftGroupIndex,
+    pub lockup_create: LockupCreateV1,
+}
+
+/// The draft group layout of the state version 1.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct DraftGroupV1 {
+    pub total_amount: Balance,
+    pub payer_id: Option<ValidAccountId>,
+    pub draft_indices: HashSet<DraftIndex>,
+    pub discarded: bool,
+}
+
+/// The contract state layout of the state version 1.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct ContractV1 {
+    pub token_account_id: TokenAccountId,
+    pub lockups: Vector<LockupV1>,
+    pub account_lockups: LookupMap<AccountId, HashSet<LockupIndex>>,
+    pub deposit_whitelist: UnorderedSet<AccountId>,
+    pub draft_operators_whitelist: UnorderedSet<AccountId>,
+    pub next_draft_id: DraftIndex,
+    pub drafts: LookupMap<DraftIndex, DraftV1>,
+    pub next_draft_group_id: DraftGroupIndex,
+    pub draft_groups: UnorderedMap<DraftGroupIndex, DraftGroupV1>,
+}
+
+/// The contract state of any known layout.
+pub enum VersionedContract {
+    V1(Box<ContractV1>),
+    Current(Box<Contract>),
+}
+
+impl VersionedContract {
+    pub fn read() -> Self {
+        let state_version = read_state_version();
+        match state_version {
+            1 => Self::V1(Box::new(
+                env::state_read().expect("Contract is not initialized"),
+            )),
+            STATE_VERSION => Self::Current(Box::new(
+                env::state_read().expect("Contract is not initialized"),
+            )),
+            _ => env::panic(format!("Unknown state version {}", state_version).as_bytes()),
+        }
+    }
+
+    /// The admins of the version 1 are the deposit whitelist accounts.
+    pub fn assert_owner(&self, account_id: &AccountId) {
+        let admins = match self {
+            Self::V1(contract) => &contract.deposit_whitelist,
+            Self::Current(contract) => &contract.admins,
+        };
+        assert!(
+            account_id == &env::current_account_id() || admins.contains(account_id),
+            "Not an admin"
+        );
+    }
+
+    /// Upgrades the state to the current layout. The stored lockups and drafts keep the old
+    /// layout until they are rewritten with `migrate_batch`.
+    pub fn into_current(self) -> Contract {
+        match self {
+            Self::V1(contract) => (*contract).into(),
+            Self::Current(contract) => *contract,
+        }
+    }
+}
+
+/// The progress of rewriting the records of the state version 1 to the current layout.
+/// The records at and above the `num_*` indices are created after the migration.
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Migration {
+    pub next_lockup_index: LockupIndex,
+    pub num_lockups: LockupIndex,
+    pub next_draft_id: DraftIndex,
+    pub num_drafts: DraftIndex,
+    pub next_draft_group_id: DraftGroupIndex,
+    pub num_draft_groups: DraftGroupIndex,
+}
+
+impl Migration {
+    pub fn is_done(&self) -> bool {
+        self.next_lockup_index == self.num_lockups
+            && self.next_draft_id == self.num_drafts
+            && self.next_draft_group_id == self.num_draft_groups
+    }
+}
+
+impl From<ContractV1> for Contract {
+    fn from(contract: ContractV1) -> Self {
+        let ContractV1 {
+            token_account_id,
+            lockups,
+            account_lockups,
+            deposit_whitelist,
+            draft_operators_whitelist,
+            next_draft_id,
+            drafts,
+            next_draft_group_id,
+            draft_groups,
+        } = contract;
+
+        // the collections are reinterpreted, the entries are rewritten by `migrate_batch`
+        let migration = Migration {
+            next_lockup_index: 0,
+            num_lockups: lockups.len() as LockupIndex,
+            next_draft_id: 0,
+            num_drafts: next_draft_id,
+            next_draft_group_id: 0,
+            num_draft_groups: next_draft_group_id,
+        };
+        let lockups = reinterpret(&lockups);
+        let drafts = reinterpret(&drafts);
+        let draft_groups = reinterpret(&draft_groups);
+
+        let mut token_whitelist = UnorderedSet::new(StorageKey::TokenWhitelist);
+        token_whitelist.insert(&token_account_id);
+        let mut admins = UnorderedSet::new(StorageKey::Admins);
+        admins.extend(deposit_whitelist.iter());
+
+        Self {
+            token_account_id,
+            lockups,
+            account_lockups,
+            admins,
+            admin_change_delay_sec: DEFAULT_ADMIN_CHANGE_DELAY_SEC,
+            next_admin_proposal_id: 0,
+            admin_proposals: UnorderedMap::new(StorageKey::AdminProposals),
+            deposit_whitelist,
+            draft_operators_whitelist,
+            terminators: UnorderedSet::new(StorageKey::Terminators),
+            termination_delegates: LookupMap::new(StorageKey::TerminationDelegates),
+            claim_operators: LookupMap::new(StorageKey::ClaimOperators),
+            termination_approval_config: None,
+            next_termination_request_id: 0,
+            termination_requests: UnorderedMap::new(StorageKey::TerminationRequests),
+            token_whitelist,
+            storage_accounts: LookupMap::new(StorageKey::StorageAccounts),
+            storage_stakes: LookupMap::new(StorageKey::StorageStakes),
+            guardians_whitelist: UnorderedSet::new(StorageKey::GuardiansWhitelist),
+            paused: HashSet::new(),
+            upgrade_delay_sec: DEFAULT_UPGRADE_DELAY_SEC,
+            staged_upgrade: None,
+            staged_upgrade_delay: None,
+            migration: Some(migration).filter(|migration| !migration.is_done()),
+            next_draft_id,
+            drafts,
+            next_draft_group_id,
+            draft_groups,
+        }
+    }
+}
+
+/// Reads a collection of the old layout as the same collection of the current layout.
+fn reinterpret<T: BorshSerialize, U: BorshDeserialize>(collection: &T) -> U {
+    U::try_from_slice(&collection.try_to_vec().unwrap()).unwrap()
+}
+
+impl Contract {
+    fn internal_migrate_lockup(&mut self, index: LockupIndex) {
+        let lockups_v1: Vector<LockupV1> = reinterpret(&self.lockups);
+        let LockupV1 {
+            account_id,
+            schedule,
+            claimed_balance,
+            termination_config,
+        } = lockups_v1.get(index as _).unwrap();
+        // the raw replace doesn't read the previous value of the old layout
+        self.lockups.replace_raw(
+            index as _,
+            &Lockup {
+                account_id,
+                schedule,
+                claimed_balance,
+                termination_config: termination_config.map(|x| x.into()),
+                transferable: false,
+                token_account_id: self.token_account_id.clone(),
+                scheduled_termination: None,
+                vesting_paused_since: None,
+            }
+            .try_to_vec()
+            .unwrap(),
+        );
+    }
+
+    fn internal_migrate_draft(&mut self, draft_id: DraftIndex) {
+        let drafts_v1: LookupMap<DraftIndex, DraftV1> = reinterpret(&self.drafts);
+        // the converted and deleted drafts are gone
+        if let Some(DraftV1 {
+            draft_group_id,
+            lockup_create:
+                LockupCreateV1 {
+                    account_id,
+                    schedule,
+                    vesting_schedule,
+                },
+        }) = drafts_v1.get(&draft_id)
+        {
+            self.drafts.insert_raw(
+                &draft_id.try_to_vec().unwrap(),
+                &Draft {
+                    draft_group_id,
+                    lockup_create: LockupCreate {
+                        account_id,
+                        schedule,
+                        vesting_schedule,
+                        transferable: false,
+                        clawback_receiver: None,
+                    },
+                }
+                .try_to_vec()
+                .unwrap(),
+            );
+        }
+    }
+
+    fn internal_migrate_draft_group(&mut self, draft_group_id: DraftGroupIndex) {
+        let draft_groups_v1: UnorderedMap<DraftGroupIndex, DraftGroupV1> =
+            reinterpret(&self.draft_groups);
+        if let Some(DraftGroupV1 {
+            total_amount,
+            payer_id,
+            draft_indices,
+            discarded,
+        }) = draft_groups_v1.get(&draft_group_id)
+        {
+            let token_account_id = payer_id.as_ref().map(|_| self.token_account_id.clone());
+            self.draft_groups.insert_raw(
+                &draft_group_id.try_to_vec().unwrap(),
+                &DraftGroup {
+                    total_amount,
+                    payer_id,
+                    draft_indices,
+                    discarded,
+                    token_account_id,
+                    clawback_receiver: None,
+                }
+                .try_to_vec()
+                .unwrap(),
+            );
+        }
+    }
+}
+
+pub(crate) fn read_state_version() -> StateVersion {
+    env::storage_read(&StorageKey::StateVersion.try_to_vec().unwrap())
+        .map(|value| StateVersion::try_from_slice(&value).expect("Invalid state version"))
+        .unwrap_or(1)
+}
+
+pub(crate) fn write_state_version() {
+    env::storage_write(
+        &StorageKey::StateVersion.try_to_vec().unwrap(),
+        &STATE_VERSION.try_to_vec().unwrap(),
+    );
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Upgrades the state of the previous contract versions to the current layout.
+    /// Can only be called by the contract itself or an admin.
+    #[init(ignore_state)]
+    pub fn migrate() -> Self {
+        let contract = VersionedContract::read();
+        contract.assert_owner(&env::predecessor_account_id());
+        let contract = contract.into_current();
+        write_state_version();
+        contract
+    }
+
+    /// Rewrites up to `limit` lockups, drafts and draft groups of the previous state layout,
+    /// in this order. The records can't be used until they are rewritten.
+    /// Returns the remaining migration, if any.
+    pub fn migrate_batch(&mut self, limit: u32) -> Option<Migration> {
+        let mut migration = self.migration.take().expect("No migration in progress");
+        for _ in 0..limit {
+            if migration.next_lockup_index < migration.num_lockups {
+                self.internal_migrate_lockup(migration.next_lockup_index);
+                migration.next_lockup_index += 1;
+            } else if migration.next_draft_id < migration.num_drafts {
+                self.internal_migrate_draft(migration.next_draft_id);
+                migration.next_draft_id += 1;
+            } else if migration.next_draft_group_id < migration.num_draft_groups {
+                self.internal_migrate_draft_group(migration.next_draft_group_id);
+                migration.next_draft_group_id += 1;
+            } else {
+                break;
+            }
+        }
+        self.migration = Some(migration).filter(|migration| !migration.is_done());
+        self.migration.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::test_utils::{accounts, VMContextBuilder};
+    use near_sdk::{testing_env, MockedBlockchain};
+
+    #[test]
+    fn test_migrate_from_v1() {
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(accounts(0))
+            .build());
+
+        let token_account_id: TokenAccountId = "token.near".into();
+        let schedule = Schedule::new_unlocked(100);
+        let mut lockups = Vector::new(StorageKey::Lockups);
+        lockups.push(&LockupV1 {
+            account_id: accounts(1),
+            schedule: schedule.clone(),
+            claimed_balance: 10,
+            termination_config: None,
+        });
+        let mut deposit_whitelist = UnorderedSet::new(StorageKey::DepositWhitelist);
+        deposit_whitelist.insert(accounts(0).as_ref());
+        let mut drafts = LookupMap::new(StorageKey::Drafts);
+        drafts.insert(
+            &0,
+            &DraftV1 {
+                draft_group_id: 0,
+                lockup_create: LockupCreateV1 {
+                    account_id: accounts(2),
+                    schedule: schedule.clone(),
+                    vesting_schedule: None,
+                },
+            },
+        );
+        let mut draft_groups = UnorderedMap::new(StorageKey::DraftGroups);
+        draft_groups.insert(
+            &0,
+            &DraftGroupV1 {
+                total_amount: 100,
+                payer_id: Some(accounts(0)),
+                draft_indices: vec![0].into_iter().collect(),
+                discarded: false,
+            },
+        );
+        env::state_write(&ContractV1 {
+            token_account_id: token_account_id.clone(),
+            lockups,
+            account_lockups: LookupMap::new(StorageKey::AccountLockups),
+            deposit_whitelist,
+            draft_operators_whitelist: UnorderedSet::new(StorageKey::DraftOperatorsWhitelist),
+            next_draft_id: 1,
+            drafts,
+            next_draft_group_id: 1,
+            draft_groups,
+        });
+        assert_eq!(read_state_version(), 1);
+
+        let mut contract = Contract::migrate();
+        assert_eq!(read_state_version(), STATE_VERSION);
+        assert!(contract.token_whitelist.contains(&token_account_id));
+        assert_eq!(contract.admins.to_vec(), vec![accounts(0).to_string()]);
+
+        // the records are rewritten in batches
+        let migration = contract.migrate_batch(2).unwrap();
+        assert_eq!(migration.next_lockup_index, 1);
+        assert_eq!(migration.next_draft_id, 1);
+        assert_eq!(migration.next_draft_group_id, 0);
+        assert_eq!(contract.get_migration(), Some(migration));
+        assert!(contract.migrate_batch(10).is_none());
+        assert!(contract.get_migration().is_none());
+
+        let lockup = contract.lockups.get(0).unwrap();
+        assert_eq!(lockup.account_id, accounts(1));
+        assert_eq!(lockup.claimed_balance, 10);
+        assert_eq!(lockup.token_account_id, token_account_id);
+        assert!(!lockup.transferable);
+        let draft = contract.drafts.get(&0).unwrap();
+        assert_eq!(draft.lockup_create.account_id, accounts(2));
+        assert_eq!(draft.lockup_create.schedule, schedule);
+        let draft_group = contract.draft_groups.get(&0).unwrap();
+        assert_eq!(draft_group.total_amount, 100);
+        assert_eq!(draft_group.token_account_id, Some(token_account_id.clone()));
+        assert_eq!(contract.draft_groups.len(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "No migration in progress")]
+    fn test_migrate_batch_without_migration() {
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(accounts(0))
+            .build());
+        let mut contract = Contract::new(accounts(3), vec![accounts(0)], None);
+        contract.migrate_batch(10);
+    }
+
+    #[test]
+    #[should_panic(expected = "Not an admin")]
+    fn test_migrate_not_owner() {
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(accounts(1))
+            .build());
+        env::state_write(&Contract::new(accounts(3), vec![accounts(0)], None));
+        Contract::migrate();
+    }
+}
@@ -0,0 +1,103 @@
+use crate::*;
+
+#[derive(
+    BorshDeserialize,
+    BorshSerialize,
+    Serialize,
+    Deserialize,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Debug,
+)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "snake_case")]
+pub enum PauseFlag {
+    /// Creating lockups and funding draft groups.
+    Creation,
+    Claiming,
+    Termination,
+    DraftConversion,
+}
+
+impl Contract {
+    pub(crate) fn assert_guardians_whitelist(&self, account_id: &AccountId) {
+        assert!(
+            (self.has_role(Role::Admin, account_id) || self.has_role(Role::Pauser, account_id)),
+            "Not in guardians whitelist"
+        );
+    }
+
+    pub(crate) fn assert_not_paused(&self, flag: PauseFlag) {
+        if self.paused.contains(&flag) {
+            env::panic(
+                match flag {
+                    PauseFlag::Creation => "Lockup creation is paused",
+                    PauseFlag::Claiming => "Claiming is paused",
+                    PauseFlag::Termination => "Termination is paused",
+                    PauseFlag::DraftConversion => "Draft conversion is paused",
+                }
+                .as_bytes(),
+            );
+        }
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    #[payable]
+    pub fn pause(&mut self, flags: Vec<PauseFlag>) {
+        assert_one_yocto();
+        let account_id = env::predecessor_account_id();
+        self.assert_guardians_whitelist(&account_id);
+        for flag in &flags {
+            self.paused.insert(*flag);
+        }
+        emit(EventKind::FtLockupPause(FtLockupPause {
+            account_id,
+            flags,
+        }));
+    }
+
+    #[payable]
+    pub fn unpause(&mut self, flags: Vec<PauseFlag>) {
+        assert_one_yocto();
+        let account_id = env::predecessor_account_id();
+        self.assert_guardians_whitelist(&account_id);
+        for flag in &flags {
+            self.paused.remove(flag);
+        }
+        emit(EventKind::FtLockupUnpause(FtLockupUnpause {
+            account_id,
+            flags,
+        }));
+    }
+
+    #[payable]
+    pub fn add_to_guardians_whitelist(&mut self, account_ids: Vec<ValidAccountId>) {
+        assert_one_yocto();
+        self.assert_whitelist_admin(&env::predecessor_account_id());
+        self.internal_grant_role(Role::Pauser, &account_ids);
+        emit(EventKind::FtLockupAddToGuardiansWhitelist(
+            FtLockupAddToGuardiansWhitelist {
+                account_ids: account_ids.into_iter().map(|x| x.into()).collect(),
+            },
+        ));
+    }
+
+    #[payable]
+    pub fn remove_from_guardians_whitelist(&mut self, account_ids: Vec<ValidAccountId>) {
+        assert_one_yocto();
+        self.assert_whitelist_admin(&env::predecessor_account_id());
+        self.internal_revoke_role(Role::Pauser, &account_ids);
+        emit(EventKind::FtLockupRemoveFromGuardiansWhitelist(
+            FtLockupRemoveFromGuardiansWhitelist {
+                account_ids: account_ids.into_iter().map(|x| x.into()).collect(),
+            },
+        ));
+    }
+}
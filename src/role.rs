@@ -0,0 +1,139 @@
+use crate::*;
+
+#[derive(
+    BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug,
+)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    /// Manages the roles, the token whitelist and the upgrades.
+    /// Also has the permissions of the draft operators and the pausers.
+    Admin,
+    /// Creates lockups and funds draft groups.
+    Depositor,
+    /// Terminates any terminable lockup.
+    Terminator,
+    /// Manages drafts and draft groups.
+    DraftOperator,
+    /// Pauses and unpauses the contract methods.
+    Pauser,
+}
+
+impl Contract {
+    pub(crate) fn role_members(&self, role: Role) -> &UnorderedSet<AccountId> {
+        match role {
+            Role::Admin => &self.admins,
+            Role::Depositor => &self.deposit_whitelist,
+            Role::Terminator => &self.terminators,
+            Role::DraftOperator => &self.draft_operators_whitelist,
+            Role::Pauser => &self.guardians_whitelist,
+        }
+    }
+
+    fn role_members_mut(&mut self, role: Role) -> &mut UnorderedSet<AccountId> {
+        match role {
+            Role::Admin => &mut self.admins,
+            Role::Depositor => &mut self.deposit_whitelist,
+            Role::Terminator => &mut self.terminators,
+            Role::DraftOperator => &mut self.draft_operators_whitelist,
+            Role::Pauser => &mut self.guardians_whitelist,
+        }
+    }
+
+    pub(crate) fn has_role(&self, role: Role, account_id: &AccountId) -> bool {
+        self.role_members(role).contains(account_id)
+    }
+
+    pub(crate) fn assert_admin(&self, account_id: &AccountId) {
+        assert!(self.has_role(Role::Admin, account_id), "Not an admin");
+    }
+
+    /// The whitelist methods predate the roles, so they keep their error message.
+    pub(crate) fn assert_whitelist_admin(&self, account_id: &AccountId) {
+        assert!(
+            self.has_role(Role::Admin, account_id),
+            "Not in deposit whitelist"
+        );
+    }
+
+    pub(crate) fn internal_grant_role(&mut self, role: Role, account_ids: &[ValidAccountId]) {
+        let members = self.role_members_mut(role);
+        for account_id in account_ids {
+            members.insert(account_id.as_ref());
+        }
+    }
+
+    pub(crate) fn internal_revoke_role(&mut self, role: Role, account_ids: &[ValidAccountId]) {
+        let members = self.role_members_mut(role);
+        for account_id in account_ids {
+            members.remove(account_id.as_ref());
+        }
+        assert!(!self.admins.is_empty(), "Cannot remove all admins");
+        assert!(
+            !self.deposit_whitelist.is_empty(),
+            "cannot remove all accounts from deposit whitelist",
+        );
+    }
+
+    fn assert_not_admin_role(role: Role) {
+        assert!(
+            role != Role::Admin,
+            "The admin role can only be changed with a proposal"
+        );
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// The admin role is granted with `propose_admin_change`. The depositor and terminator
+    /// roles are granted with a proposal too, its index is returned.
+    #[payable]
+    pub fn grant_role(
+        &mut self,
+        role: Role,
+        account_ids: Vec<ValidAccountId>,
+    ) -> Option<AdminProposalIndex> {
+        assert_one_yocto();
+        self.assert_admin(&env::predecessor_account_id());
+        Self::assert_not_admin_role(role);
+        if role == Role::Depositor || role == Role::Terminator {
+            return Some(self.internal_propose_admin_change(AdminChange::GrantRoles {
+                roles: vec![role],
+                account_ids,
+            }));
+        }
+        self.internal_grant_role(role, &account_ids);
+        emit(EventKind::FtLockupGrantRole(FtLockupGrantRole {
+            role,
+            account_ids: account_ids.into_iter().map(|x| x.into()).collect(),
+        }));
+        None
+    }
+
+    /// The admin role is revoked with `propose_admin_change`. The depositor role is revoked
+    /// with a proposal too, its index is returned.
+    #[payable]
+    pub fn revoke_role(
+        &mut self,
+        role: Role,
+        account_ids: Vec<ValidAccountId>,
+    ) -> Option<AdminProposalIndex> {
+        assert_one_yocto();
+        self.assert_admin(&env::predecessor_account_id());
+        Self::assert_not_admin_role(role);
+        if role == Role::Depositor {
+            return Some(
+                self.internal_propose_admin_change(AdminChange::RevokeRoles {
+                    roles: vec![role],
+                    account_ids,
+                }),
+            );
+        }
+        self.internal_revoke_role(role, &account_ids);
+        emit(EventKind::FtLockupRevokeRole(FtLockupRevokeRole {
+            role,
+            account_ids: account_ids.into_iter().map(|x| x.into()).collect(),
+        }));
+        None
+    }
+}
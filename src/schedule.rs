@@ -1,5 +1,26 @@
 use crate::*;
 
+/// How the balance changes from a checkpoint to the next one.
+#[derive(
+    BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy, PartialEq, Default,
+)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug))]
+pub enum SegmentKind {
+    /// The balance is interpolated linearly until the next checkpoint.
+    #[default]
+    Linear,
+    /// The balance stays flat and jumps to the next checkpoint balance at its timestamp.
+    Step,
+}
+
+impl SegmentKind {
+    pub fn is_linear(&self) -> bool {
+        *self == Self::Linear
+    }
+}
+
 #[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
 #[serde(crate = "near_sdk::serde")]
 #[cfg_attr(not(target_arch = "wasm32"), derive(Debug, PartialEq))]
@@ -8,13 +29,135 @@ pub struct Checkpoint {
     pub timestamp: TimestampSec,
     #[serde(with = "u128_dec_format")]
     pub balance: Balance,
+    /// The kind of the segment that starts at this checkpoint.
+    /// Stored separately by `Schedule`, so linear checkpoints keep their original layout.
+    #[borsh_skip]
+    #[serde(default, skip_serializing_if = "SegmentKind::is_linear")]
+    pub segment: SegmentKind,
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[derive(Serialize, Deserialize, Clone)]
 #[serde(crate = "near_sdk::serde")]
 #[cfg_attr(not(target_arch = "wasm32"), derive(Debug, PartialEq))]
 pub struct Schedule(pub Vec<Checkpoint>);
 
+/// Flags the length prefix of a schedule that is followed by the segment kinds.
+const SEGMENTS_FLAG: u32 = 1 << 31;
+
+/// Schedules of linear segments only are serialized exactly as `Vec<Checkpoint>`,
+/// which keeps the existing state and schedule hashes valid. Otherwise the length prefix
+/// is flagged and the checkpoints are followed by their segment kinds.
+impl BorshSerialize for Schedule {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        if self
+            .0
+            .iter()
+            .all(|checkpoint| checkpoint.segment.is_linear())
+        {
+            return BorshSerialize::serialize(&self.0, writer);
+        }
+        let len: u32 = self.0.len().try_into().unwrap();
+        BorshSerialize::serialize(&(len | SEGMENTS_FLAG), writer)?;
+        for checkpoint in &self.0 {
+            BorshSerialize::serialize(checkpoint, writer)?;
+        }
+        for checkpoint in &self.0 {
+            BorshSerialize::serialize(&checkpoint.segment, writer)?;
+        }
+        Ok(())
+    }
+}
+
+impl BorshDeserialize for Schedule {
+    fn deserialize(buf: &mut &[u8]) -> std::io::Result<Self> {
+        let len: u32 = BorshDeserialize::deserialize(buf)?;
+        let mut checkpoints: Vec<Checkpoint> = vec![];
+        for _ in 0..(len & !SEGMENTS_FLAG) {
+            checkpoints.push(BorshDeserialize::deserialize(buf)?);
+        }
+        if len & SEGMENTS_FLAG != 0 {
+            for checkpoint in checkpoints.iter_mut() {
+                checkpoint.segment = BorshDeserialize::deserialize(buf)?;
+            }
+        }
+        Ok(Self(checkpoints))
+    }
+}
+
+/// A compact description of a common schedule shape that is expanded into checkpoints.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug, PartialEq))]
+pub enum ScheduleSpec {
+    /// See [`Schedule::cliff_linear`].
+    CliffLinear {
+        start: TimestampSec,
+        cliff: TimestampSec,
+        end: TimestampSec,
+        #[serde(with = "u128_dec_format")]
+        total_balance: Balance,
+    },
+    /// See [`Schedule::periodic`].
+    Periodic {
+        start: TimestampSec,
+        period: TimestampSec,
+        count: u32,
+        #[serde(with = "u128_dec_format")]
+        total_balance: Balance,
+    },
+    /// See [`Schedule::monthly`].
+    Monthly {
+        start: TimestampSec,
+        months: u32,
+        #[serde(with = "u128_dec_format")]
+        total_balance: Balance,
+    },
+}
+
+impl From<ScheduleSpec> for Schedule {
+    fn from(spec: ScheduleSpec) -> Self {
+        match spec {
+            ScheduleSpec::CliffLinear {
+                start,
+                cliff,
+                end,
+                total_balance,
+            } => Schedule::cliff_linear(start, cliff, end, total_balance),
+            ScheduleSpec::Periodic {
+                start,
+                period,
+                count,
+                total_balance,
+            } => Schedule::periodic(start, period, count, total_balance),
+            ScheduleSpec::Monthly {
+                start,
+                months,
+                total_balance,
+            } => Schedule::monthly(start, months, total_balance),
+        }
+    }
+}
+
+/// Deserializes a schedule given either as a list of checkpoints or as a `ScheduleSpec`.
+pub(crate) fn deserialize_schedule<'de, D>(deserializer: D) -> Result<Schedule, D::Error>
+where
+    D: near_sdk::serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(crate = "near_sdk::serde")]
+    #[serde(untagged)]
+    enum ScheduleOrSpec {
+        Schedule(Schedule),
+        Spec(ScheduleSpec),
+    }
+
+    Ok(match ScheduleOrSpec::deserialize(deserializer)? {
+        ScheduleOrSpec::Schedule(schedule) => schedule,
+        ScheduleOrSpec::Spec(spec) => spec.into(),
+    })
+}
+
 impl Schedule {
     pub fn new_zero_balance_from_to(
         start_timestamp: TimestampSec,
@@ -26,10 +169,12 @@ impl Schedule {
             Checkpoint {
                 timestamp: start_timestamp,
                 balance: 0,
+                segment: SegmentKind::Linear,
             },
             Checkpoint {
                 timestamp: finish_timestamp,
                 balance: 0,
+                segment: SegmentKind::Linear,
             },
         ])
     }
@@ -40,10 +185,12 @@ impl Schedule {
             Checkpoint {
                 timestamp: timestamp - 1,
                 balance: 0,
+                segment: SegmentKind::Linear,
             },
             Checkpoint {
                 timestamp: timestamp,
                 balance: total_balance,
+                segment: SegmentKind::Linear,
             },
         ])
     }
@@ -53,6 +200,118 @@ impl Schedule {
         Self::new_unlocked_since(total_balance, 1)
     }
 
+    /// Nothing is unlocked before the `cliff`. At the `cliff` the balance accrued linearly
+    /// since the `start` is unlocked at once, then the rest unlocks linearly until the `end`.
+    pub fn cliff_linear(
+        start: TimestampSec,
+        cliff: TimestampSec,
+        end: TimestampSec,
+        total_balance: Balance,
+    ) -> Self {
+        assert!(
+            start <= cliff && cliff <= end && start < end,
+            "Expected start <= cliff <= end and start < end"
+        );
+        if cliff == start {
+            return Self(vec![
+                Checkpoint {
+                    timestamp: start,
+                    balance: 0,
+                    segment: SegmentKind::Linear,
+                },
+                Checkpoint {
+                    timestamp: end,
+                    balance: total_balance,
+                    segment: SegmentKind::Linear,
+                },
+            ]);
+        }
+        let cliff_balance = (U256::from(total_balance) * U256::from(cliff - start)
+            / U256::from(end - start))
+        .as_u128();
+        let mut checkpoints = vec![
+            Checkpoint {
+                timestamp: cliff - 1,
+                balance: 0,
+                segment: SegmentKind::Linear,
+            },
+            Checkpoint {
+                timestamp: cliff,
+                balance: cliff_balance,
+                segment: SegmentKind::Linear,
+            },
+        ];
+        if cliff < end {
+            checkpoints.push(Checkpoint {
+                timestamp: end,
+                balance: total_balance,
+                segment: SegmentKind::Linear,
+            });
+        }
+        Self(checkpoints)
+    }
+
+    /// Unlocks the total balance in `count` equal parts, one at the end of every `period`
+    /// after the `start`. The rounding remainder is unlocked with the last part.
+    pub fn periodic(
+        start: TimestampSec,
+        period: TimestampSec,
+        count: u32,
+        total_balance: Balance,
+    ) -> Self {
+        assert!(period > 0, "Expected positive period");
+        assert!(count > 0, "Expected positive count");
+        period
+            .checked_mul(count)
+            .and_then(|duration| start.checked_add(duration))
+            .expect("The schedule end timestamp overflows");
+        Self::from_release_timestamps(
+            (1..=count).map(|i| start + period * i).collect(),
+            total_balance,
+        )
+    }
+
+    /// Unlocks the total balance in `months` equal parts, one every calendar month after the
+    /// `start` on the same UTC day of month and time of day. The day is clamped to the last day
+    /// of shorter months, e.g. a grant starting on January 31st unlocks on February 28th (29th
+    /// in leap years), March 31st, April 30th and so on.
+    /// The rounding remainder is unlocked with the last part.
+    pub fn monthly(start: TimestampSec, months: u32, total_balance: Balance) -> Self {
+        assert!(months > 0, "Expected positive number of months");
+        Self::from_release_timestamps(
+            (1..=months).map(|i| add_months(start, i)).collect(),
+            total_balance,
+        )
+    }
+
+    /// Unlocks equal parts of the total balance at the given increasing timestamps,
+    /// keeping the balance flat in between.
+    fn from_release_timestamps(timestamps: Vec<TimestampSec>, total_balance: Balance) -> Self {
+        let count = timestamps.len();
+        let mut checkpoints: Vec<Checkpoint> = vec![];
+        let mut balance = 0;
+        for (i, timestamp) in timestamps.into_iter().enumerate() {
+            if checkpoints
+                .last()
+                .map(|checkpoint| checkpoint.timestamp < timestamp - 1)
+                .unwrap_or(true)
+            {
+                checkpoints.push(Checkpoint {
+                    timestamp: timestamp - 1,
+                    balance,
+                    segment: SegmentKind::Linear,
+                });
+            }
+            balance = (U256::from(total_balance) * U256::from(i + 1) / U256::from(count)).as_u128();
+            checkpoints.push(Checkpoint {
+                timestamp,
+                balance,
+                segment: SegmentKind::Linear,
+            });
+        }
+        Self(checkpoints)
+    }
+
     pub fn assert_valid(&self, total_balance: Balance) {
         assert!(self.0.len() >= 2, "At least two checkpoints is required");
         assert_eq!(
@@ -64,6 +323,10 @@ impl Schedule {
             assert!(self.0[i - 1].timestamp < self.0[i].timestamp, "The timestamp of checkpoint #{} should be less than the timestamp of the next checkpoint", i - 1);
             assert!(self.0[i - 1].balance <= self.0[i].balance, "The balance of checkpoint #{} should be not greater than the balance of the next checkpoint", i - 1);
         }
+        assert!(
+            self.0.last().unwrap().segment.is_linear(),
+            "The last checkpoint can't start a step segment"
+        );
         assert!(
             self.total_balance() > 0,
             "expected total balance to be positive",
@@ -78,20 +341,33 @@ impl Schedule {
     /// Verifies that this schedule is ahead of the given termination schedule at any point of time.
     /// Assumes they have equal total balance and both schedules are valid.
     pub fn assert_valid_termination_schedule(&self, termination_schedule: &Schedule) {
-        for checkpoint in &self.0 {
+        // Both schedules are linear between their checkpoints, except for the jumps at the end
+        // of step segments, so checking right at and right before every checkpoint is enough.
+        for timestamp in Self::breakpoints(&[self, termination_schedule]) {
             assert!(
-                checkpoint.balance <= termination_schedule.unlocked_balance(checkpoint.timestamp),
+                self.unlocked_balance(timestamp)
+                    <= termination_schedule.unlocked_balance(timestamp),
                 "The lockup schedule is ahead of the termination schedule at timestamp {}",
-                checkpoint.timestamp
+                timestamp
             );
         }
-        for checkpoint in &termination_schedule.0 {
-            assert!(
-                checkpoint.balance >= self.unlocked_balance(checkpoint.timestamp),
-                "The lockup schedule is ahead of the termination schedule at timestamp {}",
-                checkpoint.timestamp
-            );
+    }
+
+    /// Returns sorted timestamps of all checkpoints of the given schedules,
+    /// including the last second before the end of every step segment.
+    fn breakpoints(schedules: &[&Schedule]) -> Vec<TimestampSec> {
+        let mut timestamps: Vec<TimestampSec> = vec![];
+        for schedule in schedules {
+            for (i, checkpoint) in schedule.0.iter().enumerate() {
+                timestamps.push(checkpoint.timestamp);
+                if i > 0 && !schedule.0[i - 1].segment.is_linear() {
+                    timestamps.push(checkpoint.timestamp - 1);
+                }
+            }
         }
+        timestamps.sort_unstable();
+        timestamps.dedup();
+        timestamps
     }
 
     pub fn unlocked_balance(&self, current_timestamp: TimestampSec) -> Balance {
@@ -116,6 +392,9 @@ impl Schedule {
             // The last checkpoint. Fully unlocked.
             return checkpoint.balance;
         }
+        if !checkpoint.segment.is_linear() {
+            return checkpoint.balance;
+        }
         let next_checkpoint = &self.0[index + 1];
 
         let total_duration = next_checkpoint.timestamp - checkpoint.timestamp;
@@ -154,6 +433,15 @@ impl Schedule {
         while let Some(checkpoint) = self.0.pop() {
             if self.0.last().unwrap().balance < new_total_balance {
                 let prev_checkpoint = self.0.last().unwrap().clone();
+                if !prev_checkpoint.segment.is_linear() {
+                    // The step to the new total balance happens at the same time
+                    self.0.push(Checkpoint {
+                        timestamp: checkpoint.timestamp,
+                        balance: new_total_balance,
+                        segment: SegmentKind::Linear,
+                    });
+                    return;
+                }
                 let timestamp_diff = checkpoint.timestamp - prev_checkpoint.timestamp;
                 let balance_diff = checkpoint.balance - prev_checkpoint.balance;
                 let required_balance_diff = new_total_balance - prev_checkpoint.balance;
@@ -166,6 +454,7 @@ impl Schedule {
                 self.0.push(Checkpoint {
                     timestamp: new_timestamp,
                     balance: new_total_balance,
+                    segment: SegmentKind::Linear,
                 });
                 return;
             }
@@ -173,6 +462,239 @@ impl Schedule {
         unreachable!();
     }
 
+    /// Sums up the given schedules checkpoint-wise.
+    /// The resulting schedule has a checkpoint at every checkpoint timestamp of the given schedules
+    /// and unlocks exactly the sum of their balances at every such timestamp.
+    /// Between the checkpoints each schedule rounds its balance down on its own, so the result
+    /// can be ahead of their sum by less than the number of schedules, and never behind it.
+    /// Step segments are expressed as one-second linear segments.
+    pub fn sum(schedules: &[&Schedule]) -> Self {
+        Self::from_breakpoints(&Self::breakpoints(schedules), |timestamp| {
+            schedules
+                .iter()
+                .map(|schedule| schedule.unlocked_balance(timestamp))
+                .sum()
+        })
+    }
+
+    /// Builds a linear schedule with the given balance at every given timestamp.
+    fn from_breakpoints<F: Fn(TimestampSec) -> Balance>(
+        timestamps: &[TimestampSec],
+        balance: F,
+    ) -> Self {
+        Self(
+            timestamps
+                .iter()
+                .map(|&timestamp| Checkpoint {
+                    timestamp,
+                    balance: balance(timestamp),
+                    segment: SegmentKind::Linear,
+                })
+                .collect(),
+        )
+    }
+
+    /// Carves the given amount out of this schedule proportionally at every checkpoint.
+    /// Returns the carved schedule, while this schedule keeps the remaining balance.
+    /// Balances of both schedules sum up exactly to the original balance at every checkpoint.
+    /// Assumes the amount is positive and less than the total balance.
+    pub fn split(&mut self, amount: Balance) -> Schedule {
+        let total_balance = self.total_balance();
+        assert!(amount > 0 && amount < total_balance, "Invariant");
+        let checkpoints = self
+            .0
+            .iter_mut()
+            .map(|checkpoint| {
+                // Rounding down keeps both schedules non-decreasing
+                let balance = (U256::from(checkpoint.balance) * U256::from(amount)
+                    / U256::from(total_balance))
+                .as_u128();
+                checkpoint.balance -= balance;
+                Checkpoint {
+                    timestamp: checkpoint.timestamp,
+                    balance,
+                    segment: checkpoint.segment,
+                }
+            })
+            .collect();
+        Self(checkpoints)
+    }
+
+    /// Carves the given amount out of this lockup schedule and its vesting schedule
+    /// proportionally, like `split`. Both schedules are evaluated at the checkpoints of either
+    /// of them and the carved lockup schedule is clamped to the carved vesting schedule,
+    /// so both lockup schedules stay behind their vesting schedules.
+    /// Returns the carved lockup and vesting schedules.
+    /// Assumes this schedule is behind the vesting schedule and they have equal total balance.
+    pub fn split_with_vesting(
+        &mut self,
+        vesting_schedule: &mut Schedule,
+        amount: Balance,
+    ) -> (Schedule, Schedule) {
+        let total_balance = self.total_balance();
+        assert!(amount > 0 && amount < total_balance, "Invariant");
+        let scale = |balance: Balance| -> Balance {
+            (U256::from(balance) * U256::from(amount) / U256::from(total_balance)).as_u128()
+        };
+        let timestamps = Self::breakpoints(&[self, vesting_schedule]);
+        let carved_vesting_schedule = Self::from_breakpoints(&timestamps, |timestamp| {
+            scale(vesting_schedule.unlocked_balance(timestamp))
+        });
+        let carved_schedule = Self::from_breakpoints(&timestamps, |timestamp| {
+            std::cmp::min(
+                scale(self.unlocked_balance(timestamp)),
+                carved_vesting_schedule.unlocked_balance(timestamp),
+            )
+        });
+        let remaining_vesting_schedule = Self::from_breakpoints(&timestamps, |timestamp| {
+            vesting_schedule.unlocked_balance(timestamp)
+                - carved_vesting_schedule.unlocked_balance(timestamp)
+        });
+        let remaining_schedule = Self::from_breakpoints(&timestamps, |timestamp| {
+            self.unlocked_balance(timestamp) - carved_schedule.unlocked_balance(timestamp)
+        });
+        *vesting_schedule = remaining_vesting_schedule;
+        *self = remaining_schedule;
+        (carved_schedule, carved_vesting_schedule)
+    }
+
+    /// Scales the part of the schedule above `base_balance` down, so the total balance
+    /// is reduced by the given amount. The balances up to `base_balance` are kept.
+    /// The scaling is monotonic, so a schedule ahead of another one stays ahead of it.
+    /// Assumes the amount is not greater than the total balance above `base_balance`.
+    pub fn reduce(&mut self, base_balance: Balance, amount: Balance) {
+        let total_balance = self.total_balance();
+        assert!(
+            base_balance <= total_balance && amount <= total_balance - base_balance,
+            "Invariant"
+        );
+        let scale = |balance: Balance| -> Balance {
+            reduced_balance(balance, base_balance, total_balance, amount)
+        };
+        let mut checkpoints: Vec<Checkpoint> = Vec::with_capacity(self.0.len() + 1);
+        for (i, checkpoint) in self.0.iter().enumerate() {
+            if i > 0 {
+                // The segment crossing the base balance gets a checkpoint at the crossing,
+                // so both of its parts stay linear.
+                if let Some(timestamp) = self.crossing_timestamp(i, base_balance) {
+                    checkpoints.push(Checkpoint {
+                        timestamp,
+                        balance: base_balance,
+                        segment: SegmentKind::Linear,
+                    });
+                }
+            }
+            checkpoints.push(Checkpoint {
+                timestamp: checkpoint.timestamp,
+                balance: scale(checkpoint.balance),
+                segment: checkpoint.segment,
+            });
+        }
+        self.0 = checkpoints;
+    }
+
+    /// Reduces this lockup schedule and its vesting schedule like `reduce`. Both schedules are
+    /// evaluated at the checkpoints and the base balance crossings of either of them and the
+    /// lockup schedule is clamped to the reduced vesting schedule, so it stays behind it.
+    /// Assumes this schedule is behind the vesting schedule and they have equal total balance.
+    pub fn reduce_with_vesting(
+        &mut self,
+        vesting_schedule: &mut Schedule,
+        base_balance: Balance,
+        amount: Balance,
+    ) {
+        let total_balance = self.total_balance();
+        assert!(
+            base_balance <= total_balance && amount <= total_balance - base_balance,
+            "Invariant"
+        );
+        let scale = |balance: Balance| -> Balance {
+            reduced_balance(balance, base_balance, total_balance, amount)
+        };
+        let mut timestamps = Self::breakpoints(&[self, vesting_schedule]);
+        for schedule in &[&*self, &*vesting_schedule] {
+            timestamps.extend(
+                (1..schedule.0.len()).filter_map(|i| schedule.crossing_timestamp(i, base_balance)),
+            );
+        }
+        timestamps.sort_unstable();
+        timestamps.dedup();
+        let reduced_vesting_schedule = Self::from_breakpoints(&timestamps, |timestamp| {
+            scale(vesting_schedule.unlocked_balance(timestamp))
+        });
+        let reduced_schedule = Self::from_breakpoints(&timestamps, |timestamp| {
+            std::cmp::min(
+                scale(self.unlocked_balance(timestamp)),
+                reduced_vesting_schedule.unlocked_balance(timestamp),
+            )
+        });
+        *vesting_schedule = reduced_vesting_schedule;
+        *self = reduced_schedule;
+    }
+
+    /// Returns the timestamp, rounding up, at which the linear segment ending at the checkpoint
+    /// `i` reaches the given balance, if it's strictly inside the segment.
+    fn crossing_timestamp(&self, i: usize, balance: Balance) -> Option<TimestampSec> {
+        let prev_checkpoint = &self.0[i - 1];
+        let checkpoint = &self.0[i];
+        if !prev_checkpoint.segment.is_linear()
+            || prev_checkpoint.balance >= balance
+            || checkpoint.balance <= balance
+        {
+            return None;
+        }
+        let timestamp_diff = checkpoint.timestamp - prev_checkpoint.timestamp;
+        let balance_diff = checkpoint.balance - prev_checkpoint.balance;
+        let required_balance_diff = balance - prev_checkpoint.balance;
+        let timestamp = prev_checkpoint.timestamp
+            + ((U256::from(timestamp_diff) * U256::from(required_balance_diff)
+                + U256::from(balance_diff - 1))
+                / U256::from(balance_diff))
+            .as_u32();
+        Some(timestamp).filter(|&timestamp| timestamp < checkpoint.timestamp)
+    }
+
+    /// Inserts a flat segment of the given duration at the timestamp, shifting all the later
+    /// checkpoints by the duration. The balances up to the timestamp are kept.
+    pub fn pause(&mut self, timestamp: TimestampSec, duration: TimestampSec) {
+        if duration == 0 {
+            return;
+        }
+        let index = match self
+            .0
+            .iter()
+            .position(|checkpoint| checkpoint.timestamp > timestamp)
+        {
+            Some(index) => index,
+            // fully unlocked by the timestamp
+            None => return,
+        };
+        let mut flat_checkpoints = vec![];
+        if index > 0 {
+            let balance = self.unlocked_balance(timestamp);
+            let prev_checkpoint = &mut self.0[index - 1];
+            let segment = prev_checkpoint.segment;
+            if prev_checkpoint.timestamp < timestamp {
+                flat_checkpoints.push(Checkpoint {
+                    timestamp,
+                    balance,
+                    segment: SegmentKind::Linear,
+                });
+            } else {
+                prev_checkpoint.segment = SegmentKind::Linear;
+            }
+            flat_checkpoints.push(Checkpoint {
+                timestamp: timestamp + duration,
+                balance,
+                segment,
+            });
+        }
+        for checkpoint in self.0[index..].iter_mut() {
+            checkpoint.timestamp += duration;
+        }
+        self.0.splice(index..index, flat_checkpoints);
+    }
+
     pub fn hash(&self) -> CryptoHash {
         let value_hash = env::sha256(&self.try_to_vec().unwrap());
         let mut res = CryptoHash::default();
@@ -180,4 +702,371 @@ impl Schedule {
 
         res
     }
+
+    /// Hashes the schedule followed by the salt, so the schedule can't be guessed from the hash.
+    pub fn hash_with_salt(&self, salt: &[u8]) -> CryptoHash {
+        let mut value = self.try_to_vec().unwrap();
+        value.extend_from_slice(salt);
+        let value_hash = env::sha256(&value);
+        let mut res = CryptoHash::default();
+        res.copy_from_slice(&value_hash);
+
+        res
+    }
+}
+
+/// Scales the part of the balance above `base_balance` down by the ratio of reducing
+/// the total balance by the given amount.
+fn reduced_balance(
+    balance: Balance,
+    base_balance: Balance,
+    total_balance: Balance,
+    amount: Balance,
+) -> Balance {
+    if balance <= base_balance {
+        balance
+    } else {
+        base_balance
+            + (U256::from(balance - base_balance)
+                * U256::from(total_balance - base_balance - amount)
+                / U256::from(total_balance - base_balance))
+            .as_u128()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cliff_linear() {
+        let schedule = Schedule::cliff_linear(1000, 1250, 2000, 4000);
+        schedule.assert_valid(4000);
+        assert_eq!(schedule.unlocked_balance(1249), 0);
+        assert_eq!(schedule.unlocked_balance(1250), 1000);
+        assert_eq!(schedule.unlocked_balance(1500), 2000);
+        assert_eq!(schedule.unlocked_balance(2000), 4000);
+
+        let schedule = Schedule::cliff_linear(1000, 1000, 2000, 4000);
+        schedule.assert_valid(4000);
+        assert_eq!(schedule.unlocked_balance(1500), 2000);
+
+        let schedule = Schedule::cliff_linear(1000, 2000, 2000, 4000);
+        schedule.assert_valid(4000);
+        assert_eq!(schedule.unlocked_balance(1999), 0);
+        assert_eq!(schedule.unlocked_balance(2000), 4000);
+    }
+
+    #[test]
+    fn test_periodic() {
+        let schedule = Schedule::periodic(1000, 100, 3, 1000);
+        schedule.assert_valid(1000);
+        assert_eq!(schedule.unlocked_balance(1099), 0);
+        assert_eq!(schedule.unlocked_balance(1100), 333);
+        assert_eq!(schedule.unlocked_balance(1199), 333);
+        assert_eq!(schedule.unlocked_balance(1200), 666);
+        assert_eq!(schedule.unlocked_balance(1300), 1000);
+
+        let schedule = Schedule::periodic(1000, 1, 4, 1000);
+        schedule.assert_valid(1000);
+        assert_eq!(schedule.unlocked_balance(1002), 500);
+        assert_eq!(schedule.unlocked_balance(1004), 1000);
+    }
+
+    #[test]
+    fn test_monthly() {
+        // 2024-01-31T12:00:00Z
+        let start = 1_706_702_400;
+        let schedule = Schedule::monthly(start, 4, 4000);
+        schedule.assert_valid(4000);
+        let release_timestamps: Vec<TimestampSec> = schedule
+            .0
+            .iter()
+            .skip(1)
+            .step_by(2)
+            .map(|checkpoint| checkpoint.timestamp)
+            .collect();
+        assert_eq!(
+            release_timestamps,
+            vec![
+                1_709_208_000, // 2024-02-29T12:00:00Z
+                1_711_886_400, // 2024-03-31T12:00:00Z
+                1_714_478_400, // 2024-04-30T12:00:00Z
+                1_717_156_800, // 2024-05-31T12:00:00Z
+            ]
+        );
+        assert_eq!(schedule.unlocked_balance(1_709_208_000 - 1), 0);
+        assert_eq!(schedule.unlocked_balance(1_709_208_000), 1000);
+        assert_eq!(schedule.unlocked_balance(1_714_478_400), 3000);
+        assert_eq!(schedule.unlocked_balance(1_717_156_800), 4000);
+    }
+
+    fn step_schedule() -> Schedule {
+        Schedule(vec![
+            Checkpoint {
+                timestamp: 1000,
+                balance: 0,
+                segment: SegmentKind::Step,
+            },
+            Checkpoint {
+                timestamp: 2000,
+                balance: 1000,
+                segment: SegmentKind::Step,
+            },
+            Checkpoint {
+                timestamp: 3000,
+                balance: 4000,
+                segment: SegmentKind::Linear,
+            },
+        ])
+    }
+
+    #[test]
+    fn test_step_unlocked_balance() {
+        let schedule = step_schedule();
+        schedule.assert_valid(4000);
+        assert_eq!(schedule.unlocked_balance(1999), 0);
+        assert_eq!(schedule.unlocked_balance(2000), 1000);
+        assert_eq!(schedule.unlocked_balance(2999), 1000);
+        assert_eq!(schedule.unlocked_balance(3000), 4000);
+    }
+
+    #[test]
+    fn test_step_borsh() {
+        // linear schedules keep the layout of the checkpoints vector
+        let schedule = Schedule::cliff_linear(1000, 1250, 2000, 4000);
+        assert_eq!(
+            schedule.try_to_vec().unwrap(),
+            schedule.0.try_to_vec().unwrap()
+        );
+        assert_eq!(
+            Schedule::try_from_slice(&schedule.try_to_vec().unwrap()).unwrap(),
+            schedule
+        );
+
+        let schedule = step_schedule();
+        assert_eq!(
+            Schedule::try_from_slice(&schedule.try_to_vec().unwrap()).unwrap(),
+            schedule
+        );
+        let mut linear_schedule = schedule.clone();
+        linear_schedule.0[0].segment = SegmentKind::Linear;
+        linear_schedule.0[1].segment = SegmentKind::Linear;
+        assert_ne!(
+            schedule.try_to_vec().unwrap(),
+            linear_schedule.try_to_vec().unwrap()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "The lockup schedule is ahead of the termination schedule")]
+    fn test_step_termination_schedule_ahead() {
+        // the linear schedule is ahead of the steps right before 2000
+        let mut schedule = step_schedule();
+        schedule.0[1].segment = SegmentKind::Linear;
+        schedule.assert_valid_termination_schedule(&step_schedule());
+    }
+
+    #[test]
+    fn test_step_termination_schedule() {
+        let mut schedule = step_schedule();
+        schedule.0[0].segment = SegmentKind::Linear;
+        step_schedule().assert_valid_termination_schedule(&schedule);
+    }
+
+    #[test]
+    fn test_step_terminate() {
+        let mut schedule = step_schedule();
+        schedule.terminate(2000, 2500);
+        schedule.assert_valid(2000);
+        assert_eq!(schedule.unlocked_balance(2999), 1000);
+        assert_eq!(schedule.unlocked_balance(3000), 2000);
+
+        let mut schedule = step_schedule();
+        schedule.terminate(1000, 2500);
+        schedule.assert_valid(1000);
+        assert_eq!(schedule.unlocked_balance(1999), 0);
+        assert_eq!(schedule.unlocked_balance(2000), 1000);
+    }
+
+    #[test]
+    fn test_reduce() {
+        let mut schedule = Schedule::cliff_linear(1000, 1000, 2000, 4000);
+        schedule.reduce(1000, 1500);
+        schedule.assert_valid(2500);
+        // the crossing of the base balance becomes a checkpoint
+        assert_eq!(schedule.0.len(), 3);
+        assert_eq!(schedule.unlocked_balance(1250), 1000);
+        assert_eq!(schedule.unlocked_balance(1500), 1500);
+        assert_eq!(schedule.unlocked_balance(2000), 2500);
+
+        let mut schedule = step_schedule();
+        schedule.reduce(1000, 3000);
+        schedule.assert_valid(1000);
+        assert_eq!(schedule.unlocked_balance(2000), 1000);
+        assert_eq!(schedule.unlocked_balance(3000), 1000);
+
+        // the lockup schedule stays behind the vesting schedule
+        let mut lockup_schedule = Schedule::cliff_linear(1000, 1500, 3000, 4000);
+        let mut vesting_schedule = Schedule::cliff_linear(1000, 1000, 2000, 4000);
+        lockup_schedule.reduce(1000, 2000);
+        vesting_schedule.reduce(1000, 2000);
+        lockup_schedule.assert_valid(2000);
+        vesting_schedule.assert_valid(2000);
+        lockup_schedule.assert_valid_termination_schedule(&vesting_schedule);
+    }
+
+    fn linear_schedule(checkpoints: &[(TimestampSec, Balance)]) -> Schedule {
+        Schedule(
+            checkpoints
+                .iter()
+                .map(|&(timestamp, balance)| Checkpoint {
+                    timestamp,
+                    balance,
+                    segment: SegmentKind::Linear,
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_reduce_with_vesting() {
+        let lockup_schedule = linear_schedule(&[(0, 0), (3, 4), (5, 7)]);
+        let vesting_schedule = linear_schedule(&[(0, 0), (5, 7)]);
+        lockup_schedule.assert_valid_termination_schedule(&vesting_schedule);
+
+        // reducing each schedule on its own puts the lockup ahead at timestamp 3
+        let mut reduced_schedule = lockup_schedule.clone();
+        let mut reduced_vesting_schedule = vesting_schedule.clone();
+        reduced_schedule.reduce(5, 1);
+        reduced_vesting_schedule.reduce(5, 1);
+        assert!(
+            reduced_schedule.unlocked_balance(3) > reduced_vesting_schedule.unlocked_balance(3)
+        );
+
+        let mut reduced_schedule = lockup_schedule.clone();
+        let mut reduced_vesting_schedule = vesting_schedule.clone();
+        reduced_schedule.reduce_with_vesting(&mut reduced_vesting_schedule, 5, 1);
+        reduced_schedule.assert_valid(6);
+        reduced_vesting_schedule.assert_valid(6);
+        reduced_schedule.assert_valid_termination_schedule(&reduced_vesting_schedule);
+        // the balances up to the base balance are kept
+        assert_eq!(reduced_schedule.unlocked_balance(3), 4);
+        assert_eq!(reduced_vesting_schedule.unlocked_balance(4), 5);
+    }
+
+    #[test]
+    fn test_split_with_vesting() {
+        let lockup_schedule = linear_schedule(&[(0, 0), (3, 1), (5, 2)]);
+        let vesting_schedule = linear_schedule(&[(0, 0), (5, 2)]);
+        lockup_schedule.assert_valid_termination_schedule(&vesting_schedule);
+
+        // splitting each schedule on its own puts the remaining lockup ahead at timestamp 3
+        let mut remaining_schedule = lockup_schedule.clone();
+        let mut remaining_vesting_schedule = vesting_schedule.clone();
+        remaining_schedule.split(1);
+        remaining_vesting_schedule.split(1);
+        assert!(
+            remaining_schedule.unlocked_balance(3) > remaining_vesting_schedule.unlocked_balance(3)
+        );
+
+        let mut remaining_schedule = lockup_schedule.clone();
+        let mut remaining_vesting_schedule = vesting_schedule.clone();
+        let (schedule, vesting_schedule) =
+            remaining_schedule.split_with_vesting(&mut remaining_vesting_schedule, 1);
+        schedule.assert_valid(1);
+        vesting_schedule.assert_valid(1);
+        remaining_schedule.assert_valid(1);
+        remaining_vesting_schedule.assert_valid(1);
+        schedule.assert_valid_termination_schedule(&vesting_schedule);
+        remaining_schedule.assert_valid_termination_schedule(&remaining_vesting_schedule);
+        for &timestamp in &[0, 3, 5] {
+            assert_eq!(
+                schedule.unlocked_balance(timestamp)
+                    + remaining_schedule.unlocked_balance(timestamp),
+                lockup_schedule.unlocked_balance(timestamp)
+            );
+        }
+    }
+
+    #[test]
+    fn test_pause() {
+        let mut schedule = Schedule::cliff_linear(1000, 1000, 2000, 4000);
+        schedule.pause(1500, 500);
+        schedule.assert_valid(4000);
+        assert_eq!(schedule.unlocked_balance(1500), 2000);
+        assert_eq!(schedule.unlocked_balance(2000), 2000);
+        assert_eq!(schedule.unlocked_balance(2250), 3000);
+        assert_eq!(schedule.unlocked_balance(2500), 4000);
+
+        // the step is delayed when paused at a checkpoint
+        let mut schedule = step_schedule();
+        schedule.pause(2000, 1000);
+        schedule.assert_valid(4000);
+        assert_eq!(schedule.unlocked_balance(2000), 1000);
+        assert_eq!(schedule.unlocked_balance(3999), 1000);
+        assert_eq!(schedule.unlocked_balance(4000), 4000);
+
+        // the whole schedule is shifted when paused before its start
+        let mut schedule = step_schedule();
+        schedule.pause(500, 1000);
+        schedule.assert_valid(4000);
+        assert_eq!(schedule.unlocked_balance(2999), 0);
+        assert_eq!(schedule.unlocked_balance(3000), 1000);
+    }
+
+    #[test]
+    fn test_sum_with_steps() {
+        let step = step_schedule();
+        let linear = Schedule::cliff_linear(1000, 1000, 3000, 4000);
+        let sum = Schedule::sum(&[&step, &linear]);
+        sum.assert_valid(8000);
+        for timestamp in (900..3100).step_by(50).chain(vec![1999, 2999]) {
+            assert_eq!(
+                sum.unlocked_balance(timestamp),
+                step.unlocked_balance(timestamp) + linear.unlocked_balance(timestamp)
+            );
+        }
+    }
+
+    #[test]
+    fn test_sum_between_checkpoints() {
+        let schedule = linear_schedule(&[(0, 0), (3, 1)]);
+        let sum = Schedule::sum(&[&schedule, &schedule]);
+        sum.assert_valid(2);
+        assert_eq!(sum.0.len(), 2);
+        assert_eq!(sum.unlocked_balance(1), 0);
+        // 2 * floor(2 / 3) vs floor(4 / 3)
+        assert_eq!(schedule.unlocked_balance(2) * 2, 0);
+        assert_eq!(sum.unlocked_balance(2), 1);
+        assert_eq!(sum.unlocked_balance(3), 2);
+    }
+
+    #[test]
+    fn test_deserialize_schedule_spec() {
+        #[derive(Deserialize)]
+        #[serde(crate = "near_sdk::serde")]
+        struct Wrapper {
+            #[serde(deserialize_with = "deserialize_schedule")]
+            schedule: Schedule,
+        }
+
+        let wrapper: Wrapper = near_sdk::serde_json::from_str(
+            r#"{"schedule": {"cliff_linear": {"start": 1000, "cliff": 1250, "end": 2000, "total_balance": "4000"}}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            wrapper.schedule,
+            Schedule::cliff_linear(1000, 1250, 2000, 4000)
+        );
+
+        let wrapper: Wrapper = near_sdk::serde_json::from_str(
+            r#"{"schedule": [{"timestamp": 1000, "balance": "0"}, {"timestamp": 2000, "balance": "4000"}]}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            wrapper.schedule,
+            Schedule::cliff_linear(1000, 1000, 2000, 4000)
+        );
+    }
 }
@@ -0,0 +1,222 @@
+use crate::*;
+use near_contract_standards::storage_management::{
+    StorageBalance, StorageBalanceBounds, StorageManagement,
+};
+use near_sdk::StorageUsage;
+
+/// The storage usage of a registered account record.
+pub const STORAGE_ACCOUNT_USAGE: StorageUsage = 125;
+/// The storage usage of a lockup index in the account lockups.
+const LOCKUP_INDEX_STORAGE_USAGE: StorageUsage = 4;
+
+/// The storage deposit of an account that pays for the lockups and drafts it creates.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct StorageAccount {
+    pub deposit: Balance,
+    pub storage_usage: StorageUsage,
+}
+
+impl StorageAccount {
+    pub fn available(&self) -> Balance {
+        self.deposit
+            .saturating_sub(Balance::from(self.storage_usage) * env::storage_byte_cost())
+    }
+
+    pub fn to_storage_balance(&self) -> StorageBalance {
+        StorageBalance {
+            total: self.deposit.into(),
+            available: self.available().into(),
+        }
+    }
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub enum StorageStakeId {
+    Lockup(LockupIndex),
+    Draft(DraftIndex),
+}
+
+/// The storage used by a single lockup or draft, the freed part is refunded to the payer
+/// once it's dropped.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct StorageStake {
+    pub payer_id: AccountId,
+    pub storage_usage: StorageUsage,
+}
+
+impl Contract {
+    /// Charges the payer for the storage used since `initial_storage_usage`,
+    /// including the stake record itself.
+    pub(crate) fn internal_stake_storage(
+        &mut self,
+        stake_id: StorageStakeId,
+        payer_id: &AccountId,
+        initial_storage_usage: StorageUsage,
+    ) {
+        if let Err(err) = self.internal_try_stake_storage(stake_id, payer_id, initial_storage_usage)
+        {
+            env::panic(err.as_bytes());
+        }
+    }
+
+    /// Like `internal_stake_storage`, but leaves the storage unpaid for instead of failing,
+    /// for the callbacks that can't fail.
+    pub(crate) fn internal_try_stake_storage(
+        &mut self,
+        stake_id: StorageStakeId,
+        payer_id: &AccountId,
+        initial_storage_usage: StorageUsage,
+    ) -> Result<(), &'static str> {
+        let mut account = self
+            .storage_accounts
+            .get(payer_id)
+            .ok_or("The payer is not registered for storage")?;
+        let mut stake = StorageStake {
+            payer_id: payer_id.clone(),
+            storage_usage: 0,
+        };
+        self.storage_stakes.insert(&stake_id, &stake);
+        stake.storage_usage = env::storage_usage().saturating_sub(initial_storage_usage);
+        self.storage_stakes.insert(&stake_id, &stake);
+
+        account.storage_usage += stake.storage_usage;
+        if Balance::from(account.storage_usage) * env::storage_byte_cost() > account.deposit {
+            self.storage_stakes.remove(&stake_id);
+            return Err("Not enough storage deposit");
+        }
+        self.storage_accounts.insert(payer_id, &account);
+        Ok(())
+    }
+
+    /// Returns the account that paid for the storage of the lockup or draft.
+    pub(crate) fn internal_storage_payer(&self, stake_id: &StorageStakeId) -> Option<AccountId> {
+        self.storage_stakes
+            .get(stake_id)
+            .map(|stake| stake.payer_id)
+    }
+
+    /// Gives the storage freed by a dropped lockup or draft back to its payer.
+    /// A dropped lockup is kept in the lockups vector, so only its index and the stake record
+    /// are freed, the lockup record stays paid for.
+    /// Returns the payer, if the storage was paid for.
+    pub(crate) fn internal_release_storage(
+        &mut self,
+        stake_id: StorageStakeId,
+    ) -> Option<AccountId> {
+        let initial_storage_usage = env::storage_usage();
+        let stake = self.storage_stakes.remove(&stake_id)?;
+        let released_storage_usage = match stake_id {
+            StorageStakeId::Lockup(_) => std::cmp::min(
+                initial_storage_usage - env::storage_usage() + LOCKUP_INDEX_STORAGE_USAGE,
+                stake.storage_usage,
+            ),
+            StorageStakeId::Draft(_) => stake.storage_usage,
+        };
+        if let Some(mut account) = self.storage_accounts.get(&stake.payer_id) {
+            account.storage_usage -= released_storage_usage;
+            self.storage_accounts.insert(&stake.payer_id, &account);
+        }
+        Some(stake.payer_id)
+    }
+}
+
+#[near_bindgen]
+impl StorageManagement for Contract {
+    #[payable]
+    fn storage_deposit(
+        &mut self,
+        account_id: Option<ValidAccountId>,
+        registration_only: Option<bool>,
+    ) -> StorageBalance {
+        let amount = env::attached_deposit();
+        let account_id: AccountId = account_id
+            .map(|account_id| account_id.into())
+            .unwrap_or_else(env::predecessor_account_id);
+        let min_balance = self.storage_balance_bounds().min.0;
+        let (account, refund) = match self.storage_accounts.get(&account_id) {
+            Some(account) if registration_only.unwrap_or(false) => (account, amount),
+            Some(mut account) => {
+                account.deposit += amount;
+                (account, 0)
+            }
+            None => {
+                assert!(
+                    amount >= min_balance,
+                    "The attached deposit is less than the minimum storage balance"
+                );
+                let deposit = if registration_only.unwrap_or(false) {
+                    min_balance
+                } else {
+                    amount
+                };
+                let account = StorageAccount {
+                    deposit,
+                    storage_usage: STORAGE_ACCOUNT_USAGE,
+                };
+                (account, amount - deposit)
+            }
+        };
+        self.storage_accounts.insert(&account_id, &account);
+        if refund > 0 {
+            Promise::new(env::predecessor_account_id()).transfer(refund);
+        }
+        account.to_storage_balance()
+    }
+
+    #[payable]
+    fn storage_withdraw(&mut self, amount: Option<U128>) -> StorageBalance {
+        assert_one_yocto();
+        let account_id = env::predecessor_account_id();
+        let mut account = self
+            .storage_accounts
+            .get(&account_id)
+            .expect("The account is not registered for storage");
+        let available = account.available();
+        let amount = amount.map(|amount| amount.0).unwrap_or(available);
+        assert!(
+            amount <= available,
+            "The amount is greater than the available storage balance"
+        );
+        account.deposit -= amount;
+        self.storage_accounts.insert(&account_id, &account);
+        if amount > 0 {
+            Promise::new(account_id).transfer(amount);
+        }
+        account.to_storage_balance()
+    }
+
+    /// Forced unregistration isn't supported, the storage in use has to be released first.
+    #[payable]
+    fn storage_unregister(&mut self, force: Option<bool>) -> bool {
+        assert_one_yocto();
+        assert!(
+            !force.unwrap_or(false),
+            "Forced unregistration is not supported"
+        );
+        let account_id = env::predecessor_account_id();
+        if let Some(account) = self.storage_accounts.get(&account_id) {
+            assert_eq!(
+                account.storage_usage, STORAGE_ACCOUNT_USAGE,
+                "Can't unregister the account with storage in use"
+            );
+            self.storage_accounts.remove(&account_id);
+            Promise::new(account_id).transfer(account.deposit);
+            true
+        } else {
+            false
+        }
+    }
+
+    fn storage_balance_bounds(&self) -> StorageBalanceBounds {
+        StorageBalanceBounds {
+            min: (Balance::from(STORAGE_ACCOUNT_USAGE) * env::storage_byte_cost()).into(),
+            max: None,
+        }
+    }
+
+    fn storage_balance_of(&self, account_id: ValidAccountId) -> Option<StorageBalance> {
+        self.storage_accounts
+            .get(account_id.as_ref())
+            .map(|account| account.to_storage_balance())
+    }
+}
@@ -6,7 +6,31 @@ use crate::*;
 pub enum VestingConditions {
     SameAsLockupSchedule,
     Hash(Base58CryptoHash),
-    Schedule(Schedule),
+    Schedule(#[serde(deserialize_with = "deserialize_schedule")] Schedule),
+    /// The hash of the schedule committed with a secret salt, see `Schedule::hash_with_salt`.
+    HashWithSalt(Base58CryptoHash),
+}
+
+/// A termination that is executed at its timestamp, unless it's cancelled before.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug, PartialEq, Clone))]
+pub struct ScheduledTermination {
+    pub termination_timestamp: TimestampSec,
+    /// The revealed vesting schedule, if it's hidden.
+    pub hashed_schedule: Option<Schedule>,
+    pub salt: Option<Base64VecU8>,
+}
+
+/// The account that receives the unvested balance taken back from a lockup.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug))]
+pub struct ClawbackReceiver {
+    pub account_id: ValidAccountId,
+    /// If given, the tokens are sent with `ft_transfer_call` using this message.
+    #[serde(default)]
+    pub msg: Option<String>,
 }
 
 #[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
@@ -18,25 +42,41 @@ pub struct TerminationConfig {
     pub beneficiary_id: ValidAccountId,
     /// An optional vesting schedule
     pub vesting_schedule: VestingConditions,
+    /// Receives the unvested balance instead of the beneficiary.
+    #[serde(default)]
+    pub clawback_receiver: Option<ClawbackReceiver>,
+}
+
+impl TerminationConfig {
+    pub fn clawback_receiver(&self) -> ClawbackReceiver {
+        self.clawback_receiver
+            .clone()
+            .unwrap_or_else(|| ClawbackReceiver {
+                account_id: self.beneficiary_id.clone(),
+                msg: None,
+            })
+    }
 }
 
 impl Lockup {
-    pub fn terminate(
-        &mut self,
-        hashed_schedule: Option<Schedule>,
+    /// Returns the vested balance at the termination timestamp,
+    /// checking the revealed vesting schedule if it's hidden.
+    pub fn termination_vested_balance(
+        &self,
+        hashed_schedule: Option<&Schedule>,
         termination_timestamp: TimestampSec,
-    ) -> (Balance, AccountId) {
+        salt: Option<&[u8]>,
+    ) -> Balance {
         let termination_config = self
             .termination_config
-            .take()
+            .as_ref()
             .expect("No termination config");
         let total_balance = self.schedule.total_balance();
         let vested_balance = match &termination_config.vesting_schedule {
             VestingConditions::SameAsLockupSchedule => &self.schedule,
             VestingConditions::Hash(hash) => {
-                let schedule = hashed_schedule
-                    .as_ref()
-                    .expect("Revealed schedule required for the termination");
+                let schedule =
+                    hashed_schedule.expect("Revealed schedule required for the termination");
                 let hash: CryptoHash = (*hash).into();
                 assert_eq!(
                     hash,
@@ -48,13 +88,96 @@ impl Lockup {
                 schedule
             }
             VestingConditions::Schedule(schedule) => &schedule,
+            VestingConditions::HashWithSalt(hash) => {
+                let schedule =
+                    hashed_schedule.expect("Revealed schedule required for the termination");
+                let salt = salt.expect("Revealed salt required for the termination");
+                let hash: CryptoHash = (*hash).into();
+                assert_eq!(
+                    hash,
+                    schedule.hash_with_salt(salt),
+                    "The revealed schedule hash doesn't match"
+                );
+                schedule.assert_valid(total_balance);
+                self.schedule.assert_valid_termination_schedule(schedule);
+                schedule
+            }
         }
         .unlocked_balance(termination_timestamp);
+        // a past termination can't take back the balance claimed since then
+        std::cmp::max(vested_balance, self.claimed_balance)
+    }
+
+    pub fn terminate(
+        &mut self,
+        hashed_schedule: Option<Schedule>,
+        termination_timestamp: TimestampSec,
+        salt: Option<Vec<u8>>,
+    ) -> (Balance, ClawbackReceiver) {
+        // the leave ends with the termination
+        if self.vesting_paused_since.is_some() {
+            self.resume_vesting();
+        }
+        let total_balance = self.schedule.total_balance();
+        let vested_balance = self.termination_vested_balance(
+            hashed_schedule.as_ref(),
+            termination_timestamp,
+            salt.as_deref(),
+        );
+        let termination_config = self.termination_config.take().unwrap();
+        self.scheduled_termination = None;
         let unvested_balance = total_balance - vested_balance;
         if unvested_balance > 0 {
             self.schedule
                 .terminate(vested_balance, termination_timestamp);
         }
-        (unvested_balance, termination_config.beneficiary_id.into())
+        (unvested_balance, termination_config.clawback_receiver())
+    }
+
+    /// Takes back the amount from the unvested balance, scaling down the unvested parts
+    /// of the lockup and vesting schedules. The lockup stays terminatable.
+    pub fn reduce(&mut self, amount: Balance) -> ClawbackReceiver {
+        assert!(
+            self.scheduled_termination.is_none(),
+            "Cannot reduce a lockup with a scheduled termination"
+        );
+        assert!(
+            self.vesting_paused_since.is_none(),
+            "Cannot reduce a lockup with a paused vesting"
+        );
+        let total_balance = self.schedule.total_balance();
+        let termination_config = self
+            .termination_config
+            .as_mut()
+            .expect("No termination config");
+        let timestamp = current_timestamp_sec();
+        let vested_balance = match &termination_config.vesting_schedule {
+            VestingConditions::SameAsLockupSchedule => &self.schedule,
+            VestingConditions::Schedule(schedule) => schedule,
+            VestingConditions::Hash(_) | VestingConditions::HashWithSalt(_) => {
+                env::panic(b"Cannot reduce a lockup with a hidden vesting schedule")
+            }
+        }
+        .unlocked_balance(timestamp);
+        let vested_balance = std::cmp::max(vested_balance, self.claimed_balance);
+        assert!(amount > 0, "expected amount > 0");
+        assert!(
+            amount <= total_balance - vested_balance,
+            "The amount exceeds the unvested balance"
+        );
+        assert!(amount < total_balance, "Cannot reduce the whole lockup");
+
+        if let VestingConditions::Schedule(schedule) = &mut termination_config.vesting_schedule {
+            self.schedule
+                .reduce_with_vesting(schedule, vested_balance, amount);
+            schedule.assert_valid(total_balance - amount);
+        } else {
+            self.schedule.reduce(vested_balance, amount);
+        }
+        self.schedule.assert_valid(total_balance - amount);
+        if let VestingConditions::Schedule(schedule) = &termination_config.vesting_schedule {
+            self.schedule.assert_valid_termination_schedule(schedule);
+        }
+        termination_config.clawback_receiver()
     }
 }
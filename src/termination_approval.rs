@@ -0,0 +1,215 @@
+use crate::*;
+
+pub type TerminationRequestIndex = u32;
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+#[cfg_attr(not(target_arch = "wasm32"), derive(PartialEq))]
+pub struct TerminationApprovalConfig {
+    /// The accounts that approve the termination requests.
+    pub approvers: Vec<ValidAccountId>,
+    /// The number of approvals that executes a termination.
+    pub threshold: u32,
+    /// The time to collect the approvals, the request expires afterwards.
+    pub request_duration_sec: TimestampSec,
+}
+
+impl TerminationApprovalConfig {
+    pub fn assert_valid(&self) {
+        assert!(self.threshold > 0, "The threshold should be positive");
+        assert!(
+            self.threshold as usize <= self.approvers.len(),
+            "The threshold is greater than the number of approvers"
+        );
+    }
+
+    pub fn is_approver(&self, account_id: &AccountId) -> bool {
+        self.approvers
+            .iter()
+            .any(|approver| approver.as_ref() == account_id)
+    }
+}
+
+/// A termination waiting for the approvals, with the arguments of `terminate`.
+#[derive(BorshDeserialize, BorshSerialize, Serialize)]
+#[serde(crate = "near_sdk::serde")]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug, PartialEq, Deserialize))]
+pub struct TerminationRequest {
+    pub lockup_index: LockupIndex,
+    pub requester_id: AccountId,
+    pub hashed_schedule: Option<Schedule>,
+    pub termination_timestamp: Option<TimestampSec>,
+    pub salt: Option<Base64VecU8>,
+    pub approvals: Vec<AccountId>,
+    pub expiration_timestamp: TimestampSec,
+}
+
+impl Contract {
+    fn internal_get_termination_approval_config(&self) -> &TerminationApprovalConfig {
+        self.termination_approval_config
+            .as_ref()
+            .expect("Termination approvals are not configured")
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Proposes the approvers of the terminations, `None` lets `terminate` execute right away.
+    /// The change is delayed like the other admin changes, the proposal index is returned.
+    #[payable]
+    pub fn set_termination_approval_config(
+        &mut self,
+        config: Option<TerminationApprovalConfig>,
+    ) -> AdminProposalIndex {
+        assert_one_yocto();
+        self.assert_admin(&env::predecessor_account_id());
+        self.internal_propose_admin_change(AdminChange::SetTerminationApprovalConfig { config })
+    }
+
+    /// Requests the termination with the arguments of `terminate`, it's executed
+    /// once the approvers reach the threshold.
+    #[payable]
+    pub fn request_termination(
+        &mut self,
+        lockup_index: LockupIndex,
+        hashed_schedule: Option<Schedule>,
+        termination_timestamp: Option<TimestampSec>,
+        salt: Option<Base64VecU8>,
+    ) -> TerminationRequestIndex {
+        assert_one_yocto();
+        let requester_id = env::predecessor_account_id();
+        let lockup = self
+            .lockups
+            .get(lockup_index as _)
+            .expect("Lockup not found");
+        self.assert_can_terminate(&lockup, &requester_id);
+        termination_timestamp_or_now(termination_timestamp);
+        let expiration_timestamp = current_timestamp_sec()
+            + self
+                .internal_get_termination_approval_config()
+                .request_duration_sec;
+
+        let index = self.next_termination_request_id;
+        self.next_termination_request_id += 1;
+        self.termination_requests.insert(
+            &index,
+            &TerminationRequest {
+                lockup_index,
+                requester_id: requester_id.clone(),
+                hashed_schedule,
+                termination_timestamp,
+                salt,
+                approvals: vec![],
+                expiration_timestamp,
+            },
+        );
+
+        emit(EventKind::FtLockupRequestTermination(
+            FtLockupRequestTermination {
+                id: index,
+                lockup_id: lockup_index,
+                requester_id,
+                expiration_timestamp,
+            },
+        ));
+
+        index
+    }
+
+    /// Approves the termination request. The approval that reaches the threshold executes
+    /// the termination and returns the unvested balance, otherwise 0 is returned.
+    #[payable]
+    pub fn approve_termination(
+        &mut self,
+        request_id: TerminationRequestIndex,
+    ) -> PromiseOrValue<WrappedBalance> {
+        assert_one_yocto();
+        let account_id = env::predecessor_account_id();
+        let config = self.internal_get_termination_approval_config();
+        assert!(
+            config.is_approver(&account_id),
+            "Not a termination approver"
+        );
+        let threshold = config.threshold as usize;
+        let mut request = self
+            .termination_requests
+            .get(&request_id)
+            .expect("Termination request not found");
+        assert!(
+            current_timestamp_sec() < request.expiration_timestamp,
+            "The termination request has expired"
+        );
+        assert!(!request.approvals.contains(&account_id), "Already approved");
+        request.approvals.push(account_id.clone());
+        // the approvals of the removed approvers don't count
+        let approvals = request
+            .approvals
+            .iter()
+            .filter(|approver_id| config.is_approver(approver_id))
+            .count();
+
+        emit(EventKind::FtLockupApproveTermination(
+            FtLockupApproveTermination {
+                id: request_id,
+                account_id,
+                approvals: approvals as u32,
+            },
+        ));
+
+        if approvals < threshold {
+            self.termination_requests.insert(&request_id, &request);
+            return PromiseOrValue::Value(0.into());
+        }
+
+        self.assert_not_paused(PauseFlag::Termination);
+        self.termination_requests.remove(&request_id);
+        let TerminationRequest {
+            lockup_index,
+            hashed_schedule,
+            termination_timestamp,
+            salt,
+            ..
+        } = request;
+        // the requested timestamp may have passed while collecting the approvals
+        let current_timestamp = current_timestamp_sec();
+        let termination_timestamp = termination_timestamp.map_or(current_timestamp, |timestamp| {
+            std::cmp::max(timestamp, current_timestamp)
+        });
+        self.internal_terminate(
+            lockup_index,
+            hashed_schedule,
+            termination_timestamp,
+            salt.map(|salt| salt.0),
+        )
+    }
+
+    /// Cancels the termination request, it can be called by the accounts that can terminate
+    /// the lockup. The expired requests and the requests of the lockups that can't be
+    /// terminated anymore can be removed by anyone.
+    #[payable]
+    pub fn cancel_termination_request(&mut self, request_id: TerminationRequestIndex) {
+        assert_one_yocto();
+        let account_id = env::predecessor_account_id();
+        let request = self
+            .termination_requests
+            .remove(&request_id)
+            .expect("Termination request not found");
+        if current_timestamp_sec() < request.expiration_timestamp {
+            let lockup = self
+                .lockups
+                .get(request.lockup_index as _)
+                .expect("Lockup not found");
+            // the lockup may have been terminated by another request
+            if lockup.termination_config.is_some() {
+                self.assert_can_terminate(&lockup, &account_id);
+            }
+        }
+
+        emit(EventKind::FtLockupCancelTerminationRequest(
+            FtLockupCancelTerminationRequest {
+                id: request_id,
+                account_id,
+            },
+        ));
+    }
+}
@@ -0,0 +1,136 @@
+use crate::*;
+
+/// The default delay between staging an upgrade and deploying it.
+pub const DEFAULT_UPGRADE_DELAY_SEC: TimestampSec = 7 * 24 * 60 * 60;
+
+const GAS_FOR_MIGRATE: Gas = 100_000_000_000_000;
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug, PartialEq, Deserialize))]
+pub struct StagedUpgrade {
+    /// The sha256 hash of the contract code to deploy.
+    pub code_hash: Base58CryptoHash,
+    /// The code can't be deployed before this timestamp.
+    pub deploy_timestamp: TimestampSec,
+}
+
+/// A shorter upgrade delay that takes effect once the current delay passes.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug, PartialEq, Deserialize))]
+pub struct StagedUpgradeDelay {
+    pub upgrade_delay_sec: TimestampSec,
+    pub effective_timestamp: TimestampSec,
+}
+
+impl Contract {
+    pub(crate) fn internal_upgrade_delay_sec(&self) -> TimestampSec {
+        match &self.staged_upgrade_delay {
+            Some(staged_upgrade_delay)
+                if current_timestamp_sec() >= staged_upgrade_delay.effective_timestamp =>
+            {
+                staged_upgrade_delay.upgrade_delay_sec
+            }
+            _ => self.upgrade_delay_sec,
+        }
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Stages the code with the given hash to be deployed after the upgrade delay.
+    /// Replaces the previously staged upgrade.
+    #[payable]
+    pub fn stage_upgrade(&mut self, code_hash: Base58CryptoHash) -> StagedUpgrade {
+        assert_one_yocto();
+        self.assert_admin(&env::predecessor_account_id());
+        let staged_upgrade = StagedUpgrade {
+            code_hash,
+            deploy_timestamp: current_timestamp_sec() + self.internal_upgrade_delay_sec(),
+        };
+        self.staged_upgrade = Some(staged_upgrade.clone());
+
+        emit(EventKind::FtLockupStageUpgrade(FtLockupStageUpgrade {
+            code_hash,
+            deploy_timestamp: staged_upgrade.deploy_timestamp,
+        }));
+
+        staged_upgrade
+    }
+
+    #[payable]
+    pub fn cancel_staged_upgrade(&mut self) {
+        assert_one_yocto();
+        self.assert_admin(&env::predecessor_account_id());
+        let staged_upgrade = self.staged_upgrade.take().expect("No staged upgrade");
+
+        emit(EventKind::FtLockupCancelUpgrade(FtLockupCancelUpgrade {
+            code_hash: staged_upgrade.code_hash,
+        }));
+    }
+
+    /// Deploys the staged code, passed as the raw input, and migrates the state.
+    /// The migration only rewrites the contract root, the stored records are rewritten
+    /// afterwards with `migrate_batch`.
+    #[payable]
+    pub fn deploy_staged_upgrade(&mut self) -> Promise {
+        assert_one_yocto();
+        self.assert_admin(&env::predecessor_account_id());
+        let staged_upgrade = self.staged_upgrade.take().expect("No staged upgrade");
+        assert!(
+            current_timestamp_sec() >= staged_upgrade.deploy_timestamp,
+            "The upgrade can't be deployed before {}",
+            staged_upgrade.deploy_timestamp
+        );
+        let code = env::input().expect("Expected the code as input");
+        let code_hash: CryptoHash = env::sha256(&code).try_into().unwrap();
+        assert_eq!(
+            Base58CryptoHash::from(code_hash),
+            staged_upgrade.code_hash,
+            "The code doesn't match the staged upgrade"
+        );
+
+        emit(EventKind::FtLockupDeployUpgrade(FtLockupDeployUpgrade {
+            code_hash: staged_upgrade.code_hash,
+        }));
+
+        Promise::new(env::current_account_id())
+            .deploy_contract(code)
+            .function_call(
+                b"migrate".to_vec(),
+                b"{}".to_vec(),
+                NO_DEPOSIT,
+                GAS_FOR_MIGRATE,
+            )
+    }
+
+    /// Sets the delay for the upgrades staged afterwards. A longer delay takes effect
+    /// immediately, a shorter one only once the current delay passes.
+    #[payable]
+    pub fn set_upgrade_delay(&mut self, upgrade_delay_sec: TimestampSec) {
+        assert_one_yocto();
+        self.assert_admin(&env::predecessor_account_id());
+        let current_upgrade_delay_sec = self.internal_upgrade_delay_sec();
+        let effective_timestamp = if upgrade_delay_sec >= current_upgrade_delay_sec {
+            self.upgrade_delay_sec = upgrade_delay_sec;
+            self.staged_upgrade_delay = None;
+            current_timestamp_sec()
+        } else {
+            let effective_timestamp = current_timestamp_sec() + current_upgrade_delay_sec;
+            self.upgrade_delay_sec = current_upgrade_delay_sec;
+            self.staged_upgrade_delay = Some(StagedUpgradeDelay {
+                upgrade_delay_sec,
+                effective_timestamp,
+            });
+            effective_timestamp
+        };
+
+        emit(EventKind::FtLockupSetUpgradeDelay(
+            FtLockupSetUpgradeDelay {
+                upgrade_delay_sec,
+                effective_timestamp,
+            },
+        ));
+    }
+}
@@ -8,6 +8,63 @@ pub(crate) fn current_timestamp_sec() -> TimestampSec {
     nano_to_sec(env::block_timestamp())
 }
 
+const SECONDS_PER_DAY: TimestampSec = 24 * 60 * 60;
+
+/// Returns the number of days since 1970-01-01 for the given proleptic Gregorian date.
+pub(crate) fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month_index = (month as i64 + 9) % 12;
+    let day_of_year = (153 * month_index + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+/// Returns the proleptic Gregorian `(year, month, day)` for the number of days since 1970-01-01.
+pub(crate) fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days - era * 146097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    } as u32;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+pub(crate) fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if (year % 4 == 0 && year % 100 != 0) || year % 400 == 0 => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Adds calendar months to the UTC timestamp keeping the time of day.
+/// The day of month is clamped to the last day of the resulting month.
+pub fn add_months(timestamp: TimestampSec, months: u32) -> TimestampSec {
+    let days = (timestamp / SECONDS_PER_DAY) as i64;
+    let time_of_day = timestamp % SECONDS_PER_DAY;
+    let (year, month, day) = civil_from_days(days);
+    let month_index = (month - 1) as i64 + months as i64;
+    let year = year + month_index / 12;
+    let month = (month_index % 12) as u32 + 1;
+    let day = std::cmp::min(day, days_in_month(year, month));
+    let timestamp = days_from_civil(year, month, day) * SECONDS_PER_DAY as i64 + time_of_day as i64;
+    timestamp
+        .try_into()
+        .expect("The timestamp is out of the supported range")
+}
+
 pub mod u128_dec_format {
     use near_sdk::serde::de;
     use near_sdk::serde::{Deserialize, Deserializer, Serializer};
@@ -28,3 +85,33 @@ pub mod u128_dec_format {
             .map_err(de::Error::custom)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_civil_days_round_trip() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(days_from_civil(2000, 3, 1), 11017);
+        assert_eq!(civil_from_days(11017), (2000, 3, 1));
+        for days in (0..100_000).step_by(37) {
+            let (year, month, day) = civil_from_days(days);
+            assert_eq!(days_from_civil(year, month, day), days);
+        }
+    }
+
+    #[test]
+    fn test_add_months() {
+        // 2023-01-31T00:00:00Z
+        let start = 1_675_123_200;
+        // 2023-02-28T00:00:00Z
+        assert_eq!(add_months(start, 1), 1_677_542_400);
+        // 2024-02-29T00:00:00Z
+        assert_eq!(add_months(start, 13), 1_709_164_800);
+        // 2023-12-31T00:00:00Z
+        assert_eq!(add_months(start, 11), 1_703_980_800);
+        // 2024-01-01T10:30:00Z -> 2024-02-01T10:30:00Z
+        assert_eq!(add_months(1_704_105_000, 1), 1_706_783_400);
+    }
+}
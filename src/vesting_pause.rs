@@ -0,0 +1,127 @@
+use crate::*;
+
+impl Lockup {
+    /// The timestamp the lockup is unlocked at, it doesn't advance while the vesting is paused.
+    pub fn unlock_timestamp(&self) -> TimestampSec {
+        let timestamp = current_timestamp_sec();
+        match self.vesting_paused_since {
+            Some(paused_since) => std::cmp::min(timestamp, paused_since),
+            None => timestamp,
+        }
+    }
+
+    /// Once the scheduled termination timestamp passes, the balance is capped at the balance
+    /// vested by then, even before the termination is executed.
+    pub fn unlocked_balance(&self) -> Balance {
+        let unlocked_balance = self.schedule.unlocked_balance(self.unlock_timestamp());
+        match &self.scheduled_termination {
+            Some(ScheduledTermination {
+                termination_timestamp,
+                hashed_schedule,
+                salt,
+            }) if current_timestamp_sec() >= *termination_timestamp => std::cmp::min(
+                unlocked_balance,
+                self.termination_vested_balance(
+                    hashed_schedule.as_ref(),
+                    *termination_timestamp,
+                    salt.as_ref().map(|salt| salt.0.as_slice()),
+                ),
+            ),
+            _ => unlocked_balance,
+        }
+    }
+
+    pub fn pause_vesting(&mut self) {
+        let termination_config = self
+            .termination_config
+            .as_ref()
+            .expect("No termination config");
+        if let VestingConditions::Hash(_) | VestingConditions::HashWithSalt(_) =
+            termination_config.vesting_schedule
+        {
+            env::panic(b"Cannot pause a lockup with a hidden vesting schedule");
+        }
+        assert!(
+            self.vesting_paused_since.is_none(),
+            "The vesting is already paused"
+        );
+        self.vesting_paused_since = Some(current_timestamp_sec());
+    }
+
+    /// Inserts the pause into the lockup and vesting schedules. Returns the pause duration.
+    pub fn resume_vesting(&mut self) -> TimestampSec {
+        let paused_since = self
+            .vesting_paused_since
+            .take()
+            .expect("The vesting is not paused");
+        let duration = current_timestamp_sec() - paused_since;
+        self.schedule.pause(paused_since, duration);
+        if let Some(TerminationConfig {
+            vesting_schedule: VestingConditions::Schedule(schedule),
+            ..
+        }) = self.termination_config.as_mut()
+        {
+            schedule.pause(paused_since, duration);
+            self.schedule.assert_valid_termination_schedule(schedule);
+        }
+        duration
+    }
+}
+
+impl Contract {
+    fn assert_termination_beneficiary(&self, lockup: &Lockup, account_id: &AccountId) {
+        let termination_config = lockup
+            .termination_config
+            .as_ref()
+            .expect("No termination config");
+        assert_eq!(
+            termination_config.beneficiary_id.as_ref(),
+            account_id,
+            "Not the termination beneficiary"
+        );
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Pauses the vesting of the lockup, e.g. for a leave of absence. Nothing is unlocked
+    /// until the vesting is resumed.
+    #[payable]
+    pub fn pause_vesting(&mut self, lockup_index: LockupIndex) {
+        assert_one_yocto();
+        let mut lockup = self
+            .lockups
+            .get(lockup_index as _)
+            .expect("Lockup not found");
+        self.assert_termination_beneficiary(&lockup, &env::predecessor_account_id());
+        lockup.pause_vesting();
+        self.lockups.replace(lockup_index as _, &lockup);
+
+        emit(EventKind::FtLockupPauseVesting(vec![
+            FtLockupPauseVesting {
+                id: lockup_index,
+                paused_since: current_timestamp_sec(),
+            },
+        ]));
+    }
+
+    /// Resumes the vesting, shifting the rest of the schedules by the pause duration.
+    #[payable]
+    pub fn resume_vesting(&mut self, lockup_index: LockupIndex) {
+        assert_one_yocto();
+        let mut lockup = self
+            .lockups
+            .get(lockup_index as _)
+            .expect("Lockup not found");
+        self.assert_termination_beneficiary(&lockup, &env::predecessor_account_id());
+        let pause_duration = lockup.resume_vesting();
+        self.lockups.replace(lockup_index as _, &lockup);
+
+        emit(EventKind::FtLockupResumeVesting(vec![
+            FtLockupResumeVesting {
+                id: lockup_index,
+                pause_duration,
+            },
+        ]));
+    }
+}
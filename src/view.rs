@@ -13,6 +13,12 @@ pub struct LockupView {
     pub claimed_balance: Balance,
     /// An optional configuration that allows vesting/lockup termination.
     pub termination_config: Option<TerminationConfig>,
+    pub transferable: bool,
+    pub token_account_id: TokenAccountId,
+    /// The pending termination at a future timestamp.
+    pub scheduled_termination: Option<ScheduledTermination>,
+    /// The timestamp since which the vesting is paused.
+    pub vesting_paused_since: Option<TimestampSec>,
 
     #[serde(with = "u128_dec_format")]
     pub total_balance: Balance,
@@ -26,19 +32,26 @@ impl From<Lockup> for LockupView {
     fn from(lockup: Lockup) -> Self {
         let total_balance = lockup.schedule.total_balance();
         let timestamp = current_timestamp_sec();
-        let unclaimed_balance =
-            lockup.schedule.unlocked_balance(timestamp) - lockup.claimed_balance;
+        let unclaimed_balance = lockup.unlocked_balance() - lockup.claimed_balance;
         let Lockup {
             account_id,
             schedule,
             claimed_balance,
             termination_config,
+            transferable,
+            token_account_id,
+            scheduled_termination,
+            vesting_paused_since,
         } = lockup;
         Self {
             account_id,
             schedule,
             claimed_balance,
             termination_config,
+            transferable,
+            token_account_id,
+            scheduled_termination,
+            vesting_paused_since,
             total_balance,
             unclaimed_balance,
             timestamp,
@@ -53,6 +66,7 @@ pub struct LockupCreateView {
     pub account_id: ValidAccountId,
     pub schedule: Schedule,
     pub vesting_schedule: Option<VestingConditions>,
+    pub transferable: bool,
 
     #[serde(with = "u128_dec_format")]
     pub claimed_balance: Balance,
@@ -73,11 +87,14 @@ impl From<LockupCreate> for LockupCreateView {
             account_id,
             schedule,
             vesting_schedule,
+            transferable,
+            ..
         } = lockup_create;
         Self {
             account_id,
             schedule,
             vesting_schedule,
+            transferable,
             claimed_balance: 0,
             total_balance,
             unclaimed_balance,
@@ -96,6 +113,8 @@ pub struct DraftGroupView {
     pub draft_indices: Vec<DraftIndex>,
     pub discarded: bool,
     pub funded: bool,
+    pub token_account_id: Option<TokenAccountId>,
+    pub clawback_receiver: Option<ClawbackReceiver>,
 }
 
 impl From<DraftGroup> for DraftGroupView {
@@ -106,6 +125,8 @@ impl From<DraftGroup> for DraftGroupView {
             draft_indices: draft_group.draft_indices.into_iter().collect(),
             discarded: draft_group.discarded,
             funded: draft_group.payer_id.is_some(),
+            token_account_id: draft_group.token_account_id,
+            clawback_receiver: draft_group.clawback_receiver,
         }
     }
 }
@@ -174,14 +195,114 @@ impl Contract {
         self.deposit_whitelist.to_vec()
     }
 
+    pub fn get_token_whitelist(&self) -> Vec<TokenAccountId> {
+        self.token_whitelist.to_vec()
+    }
+
     pub fn get_draft_operators_whitelist(&self) -> Vec<AccountId> {
         self.draft_operators_whitelist.to_vec()
     }
 
+    pub fn get_guardians_whitelist(&self) -> Vec<AccountId> {
+        self.guardians_whitelist.to_vec()
+    }
+
+    pub fn get_role_members(&self, role: Role) -> Vec<AccountId> {
+        let mut account_ids = self.role_members(role).to_vec();
+        account_ids.sort();
+        account_ids
+    }
+
+    pub fn get_admin_change_delay(&self) -> TimestampSec {
+        self.admin_change_delay_sec
+    }
+
+    pub fn get_admin_proposals(&self) -> Vec<(AdminProposalIndex, AdminProposal)> {
+        self.admin_proposals.to_vec()
+    }
+
+    pub fn get_termination_approval_config(&self) -> Option<TerminationApprovalConfig> {
+        self.termination_approval_config.clone()
+    }
+
+    pub fn get_termination_requests(&self) -> Vec<(TerminationRequestIndex, TerminationRequest)> {
+        self.termination_requests.to_vec()
+    }
+
+    pub fn get_paused(&self) -> Vec<PauseFlag> {
+        let mut flags: Vec<PauseFlag> = self.paused.iter().cloned().collect();
+        flags.sort();
+        flags
+    }
+
+    pub fn get_termination_delegates(&self, beneficiary_id: ValidAccountId) -> Vec<AccountId> {
+        let mut delegates: Vec<AccountId> = self
+            .termination_delegates
+            .get(beneficiary_id.as_ref())
+            .unwrap_or_default()
+            .into_iter()
+            .collect();
+        delegates.sort();
+        delegates
+    }
+
+    /// Returns claim operators approved by the account with their remaining allowances
+    /// per token. `None` allowances mean the operator can claim any token without a limit.
+    pub fn get_claim_operators(
+        &self,
+        account_id: ValidAccountId,
+    ) -> Vec<(AccountId, Option<HashMap<TokenAccountId, WrappedBalance>>)> {
+        let mut operators: Vec<(AccountId, Option<HashMap<TokenAccountId, WrappedBalance>>)> = self
+            .claim_operators
+            .get(account_id.as_ref())
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(operator_id, allowances)| {
+                let allowances = allowances.map(|allowances| {
+                    allowances
+                        .into_iter()
+                        .map(|(token_account_id, allowance)| (token_account_id, allowance.into()))
+                        .collect()
+                });
+                (operator_id, allowances)
+            })
+            .collect();
+        operators.sort_by(|a, b| a.0.cmp(&b.0));
+        operators
+    }
+
+    /// Returns accounts that can terminate the given lockup: the termination beneficiary
+    /// followed by its delegates and the terminators. Empty if the lockup is not terminatable.
+    pub fn get_lockup_terminators(&self, index: LockupIndex) -> Vec<AccountId> {
+        let lockup = self.lockups.get(index as _).expect("Lockup not found");
+        match lockup.termination_config {
+            None => vec![],
+            Some(termination_config) => {
+                let beneficiary_id = termination_config.beneficiary_id;
+                let mut terminators = vec![beneficiary_id.to_string()];
+                terminators.extend(self.get_termination_delegates(beneficiary_id));
+                for account_id in self.get_role_members(Role::Terminator) {
+                    if !terminators.contains(&account_id) {
+                        terminators.push(account_id);
+                    }
+                }
+                terminators
+            }
+        }
+    }
+
     pub fn hash_schedule(&self, schedule: Schedule) -> Base58CryptoHash {
         schedule.hash().into()
     }
 
+    pub fn hash_schedule_with_salt(
+        &self,
+        schedule: Schedule,
+        salt: Base64VecU8,
+    ) -> Base58CryptoHash {
+        schedule.hash_with_salt(&salt.0).into()
+    }
+
     pub fn validate_schedule(
         &self,
         schedule: Schedule,
@@ -245,4 +366,28 @@ impl Contract {
     pub fn get_version(&self) -> String {
         VERSION.into()
     }
+
+    pub fn get_state_version(&self) -> StateVersion {
+        read_state_version()
+    }
+
+    pub fn get_migration(&self) -> Option<Migration> {
+        self.migration.clone()
+    }
+
+    pub fn get_staged_upgrade(&self) -> Option<StagedUpgrade> {
+        self.staged_upgrade.clone()
+    }
+
+    pub fn get_upgrade_delay(&self) -> TimestampSec {
+        self.internal_upgrade_delay_sec()
+    }
+
+    pub fn get_staged_upgrade_delay(&self) -> Option<StagedUpgradeDelay> {
+        self.staged_upgrade_delay
+            .clone()
+            .filter(|staged_upgrade_delay| {
+                current_timestamp_sec() < staged_upgrade_delay.effective_timestamp
+            })
+    }
 }
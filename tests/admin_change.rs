@@ -0,0 +1,187 @@
+mod setup;
+
+use crate::setup::*;
+
+#[test]
+fn test_delayed_admin_change() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+
+    // without a delay the changes are applied right away
+    assert_eq!(e.get_admin_change_delay(), 0);
+    let res = e.propose_admin_change(
+        &e.owner,
+        &AdminChange::SetDelay {
+            admin_change_delay_sec: ONE_DAY_SEC,
+        },
+    );
+    assert!(res.is_ok());
+    assert_eq!(e.get_admin_change_delay(), ONE_DAY_SEC);
+    assert!(e.get_admin_proposals().is_empty());
+
+    // the whitelist change is pending
+    let proposal_id: AdminProposalIndex = e
+        .add_to_deposit_whitelist(&e.owner, &users.eve.valid_account_id())
+        .unwrap_json();
+    assert_eq!(e.get_deposit_whitelist(), vec![e.owner.account_id()]);
+    assert_eq!(
+        e.get_admin_proposals(),
+        vec![(
+            proposal_id,
+            AdminProposal {
+                proposer_id: e.owner.account_id(),
+                change: AdminChange::GrantRoles {
+                    roles: vec![Role::Admin, Role::Depositor],
+                    account_ids: vec![users.eve.valid_account_id()],
+                },
+                accept_timestamp: GENESIS_TIMESTAMP_SEC + ONE_DAY_SEC,
+            }
+        )]
+    );
+
+    let res = e.accept_admin_change(&e.owner, proposal_id);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("The proposal can't be accepted before"));
+
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC + ONE_DAY_SEC);
+    let res = e.accept_admin_change(&users.eve, proposal_id);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Not an admin"));
+
+    let res = e.accept_admin_change(&e.owner, proposal_id);
+    assert!(res.is_ok());
+    assert!(e.get_admin_proposals().is_empty());
+    assert_eq!(
+        e.get_role_members(Role::Admin),
+        vec![users.eve.account_id(), e.owner.account_id()]
+    );
+    assert_eq!(
+        e.get_deposit_whitelist(),
+        vec![e.owner.account_id(), users.eve.account_id()]
+    );
+
+    let res = e.accept_admin_change(&e.owner, proposal_id);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Proposal not found"));
+}
+
+#[test]
+fn test_default_admin_change_delay() {
+    let e = Env::deploy(None, &FT_LOCKUP_WASM_BYTES);
+    let users = Users::init(&e);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+
+    // the admin changes are delayed by default
+    assert_eq!(e.get_admin_change_delay(), DEFAULT_ADMIN_CHANGE_DELAY_SEC);
+    let proposal_id: AdminProposalIndex = e
+        .add_to_deposit_whitelist(&e.owner, &users.eve.valid_account_id())
+        .unwrap_json();
+    assert_eq!(e.get_deposit_whitelist(), vec![e.owner.account_id()]);
+    let proposal_id_2: Option<AdminProposalIndex> = e
+        .grant_role(
+            &e.owner,
+            Role::Depositor,
+            vec![users.bob.valid_account_id()],
+        )
+        .unwrap_json();
+    let proposal_id_3: Option<AdminProposalIndex> = e
+        .grant_role(
+            &e.owner,
+            Role::Terminator,
+            vec![users.bob.valid_account_id()],
+        )
+        .unwrap_json();
+    assert_eq!(e.get_admin_proposals().len(), 3);
+    assert!(e.get_role_members(Role::Terminator).is_empty());
+
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC + DEFAULT_ADMIN_CHANGE_DELAY_SEC);
+    let res = e.accept_admin_change(&e.owner, proposal_id);
+    assert!(res.is_ok());
+    let res = e.accept_admin_change(&e.owner, proposal_id_2.unwrap());
+    assert!(res.is_ok());
+    let res = e.accept_admin_change(&e.owner, proposal_id_3.unwrap());
+    assert!(res.is_ok());
+    assert_eq!(
+        e.get_role_members(Role::Terminator),
+        vec![users.bob.account_id()]
+    );
+    assert_eq!(
+        e.get_deposit_whitelist(),
+        vec![
+            e.owner.account_id(),
+            users.eve.account_id(),
+            users.bob.account_id()
+        ]
+    );
+}
+
+#[test]
+fn test_admin_revoke_is_not_accepted_automatically() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+
+    assert_eq!(e.get_admin_change_delay(), 0);
+    let res = e.add_to_deposit_whitelist(&e.owner, &users.eve.valid_account_id());
+    assert!(res.is_ok());
+
+    // even without a delay, the other admin can cancel the revoke
+    let proposal_id: AdminProposalIndex = e
+        .propose_admin_change(
+            &users.eve,
+            &AdminChange::RevokeRoles {
+                roles: vec![Role::Admin],
+                account_ids: vec![e.owner.valid_account_id()],
+            },
+        )
+        .unwrap_json();
+    assert_eq!(
+        e.get_role_members(Role::Admin),
+        vec![users.eve.account_id(), e.owner.account_id()]
+    );
+    let res = e.cancel_admin_change(&e.owner, proposal_id);
+    assert!(res.is_ok());
+    assert!(e.get_admin_proposals().is_empty());
+    assert_eq!(e.get_role_members(Role::Admin).len(), 2);
+}
+
+#[test]
+fn test_cancel_admin_change() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+
+    let res = e.add_to_deposit_whitelist(&e.owner, &users.eve.valid_account_id());
+    assert!(res.is_ok());
+    let res = e.propose_admin_change(
+        &e.owner,
+        &AdminChange::SetDelay {
+            admin_change_delay_sec: ONE_DAY_SEC,
+        },
+    );
+    assert!(res.is_ok());
+
+    // the other admin can cancel a removal during the delay
+    let proposal_id: AdminProposalIndex = e
+        .remove_from_deposit_whitelist(&users.eve, &e.owner.valid_account_id())
+        .unwrap_json();
+    assert_eq!(e.get_admin_proposals().len(), 1);
+
+    let res = e.cancel_admin_change(&users.alice, proposal_id);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Not an admin"));
+
+    let res = e.cancel_admin_change(&e.owner, proposal_id);
+    assert!(res.is_ok());
+    assert!(e.get_admin_proposals().is_empty());
+
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC + ONE_DAY_SEC);
+    let res = e.accept_admin_change(&users.eve, proposal_id);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Proposal not found"));
+    assert_eq!(
+        e.get_role_members(Role::Admin),
+        vec![users.eve.account_id(), e.owner.account_id()]
+    );
+}
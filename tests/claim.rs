@@ -16,13 +16,17 @@ fn test_lockup_claim_logic() {
             Checkpoint {
                 timestamp: GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC - 1,
                 balance: 0,
+                segment: SegmentKind::Linear,
             },
             Checkpoint {
                 timestamp: GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC,
                 balance: amount,
+                segment: SegmentKind::Linear,
             },
         ]),
         vesting_schedule: None,
+        transferable: false,
+        clawback_receiver: None,
     };
     let balance: WrappedBalance = e.add_lockup(&e.owner, amount, &lockup_create).unwrap_json();
     assert_eq!(balance.0, amount);
@@ -92,13 +96,17 @@ fn test_lockup_linear() {
             Checkpoint {
                 timestamp: GENESIS_TIMESTAMP_SEC,
                 balance: 0,
+                segment: SegmentKind::Linear,
             },
             Checkpoint {
                 timestamp: GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC,
                 balance: amount,
+                segment: SegmentKind::Linear,
             },
         ]),
         vesting_schedule: None,
+        transferable: false,
+        clawback_receiver: None,
     };
     let balance: WrappedBalance = e.add_lockup(&e.owner, amount, &lockup_create).unwrap_json();
     assert_eq!(balance.0, amount);
@@ -220,25 +228,32 @@ fn test_lockup_cliff_amazon() {
             Checkpoint {
                 timestamp: GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC - 1,
                 balance: 0,
+                segment: SegmentKind::Linear,
             },
             Checkpoint {
                 timestamp: GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC,
                 balance: amount / 10,
+                segment: SegmentKind::Linear,
             },
             Checkpoint {
                 timestamp: GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC * 2,
                 balance: 3 * amount / 10,
+                segment: SegmentKind::Linear,
             },
             Checkpoint {
                 timestamp: GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC * 3,
                 balance: 6 * amount / 10,
+                segment: SegmentKind::Linear,
             },
             Checkpoint {
                 timestamp: GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC * 4,
                 balance: amount,
+                segment: SegmentKind::Linear,
             },
         ]),
         vesting_schedule: None,
+        transferable: false,
+        clawback_receiver: None,
     };
     let balance: WrappedBalance = e.add_lockup(&e.owner, amount, &lockup_create).unwrap_json();
     assert_eq!(balance.0, amount);
@@ -340,13 +355,17 @@ fn test_claim_specific_lockups_with_specific_amounts_success() {
             Checkpoint {
                 timestamp: GENESIS_TIMESTAMP_SEC,
                 balance: 0,
+                segment: SegmentKind::Linear,
             },
             Checkpoint {
                 timestamp: GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC,
                 balance: amount,
+                segment: SegmentKind::Linear,
             },
         ]),
         vesting_schedule: None,
+        transferable: false,
+        clawback_receiver: None,
     };
 
     let balance: WrappedBalance = e.add_lockup(&e.owner, amount, &lockup_create).unwrap_json();
@@ -406,13 +425,17 @@ fn test_claim_specific_lockups_with_specific_amounts_fail() {
             Checkpoint {
                 timestamp: GENESIS_TIMESTAMP_SEC,
                 balance: 0,
+                segment: SegmentKind::Linear,
             },
             Checkpoint {
                 timestamp: GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC,
                 balance: amount,
+                segment: SegmentKind::Linear,
             },
         ]),
         vesting_schedule: None,
+        transferable: false,
+        clawback_receiver: None,
     };
 
     let balance: WrappedBalance = e.add_lockup(&e.owner, amount, &lockup_create).unwrap_json();
@@ -473,13 +496,17 @@ fn test_claim_specific_lockups_overflow() {
             Checkpoint {
                 timestamp: GENESIS_TIMESTAMP_SEC,
                 balance: 0,
+                segment: SegmentKind::Linear,
             },
             Checkpoint {
                 timestamp: GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC,
                 balance: amount,
+                segment: SegmentKind::Linear,
             },
         ]),
         vesting_schedule: None,
+        transferable: false,
+        clawback_receiver: None,
     };
 
     let balance: WrappedBalance = e.add_lockup(&e.owner, amount, &lockup_create).unwrap_json();
@@ -501,3 +528,329 @@ fn test_claim_specific_lockups_overflow() {
     assert!(!res.is_ok());
     assert!(format!("{:?}", res.status()).contains("attempt to add with overflow"));
 }
+
+#[test]
+fn test_claim_to_receiver() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+    let amount = d(10000, TOKEN_DECIMALS);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+
+    let balance: WrappedBalance = e
+        .add_lockup(
+            &e.owner,
+            amount,
+            &LockupCreate::new_unlocked(users.alice.valid_account_id(), amount),
+        )
+        .unwrap_json();
+    assert_eq!(balance.0, amount);
+
+    ft_storage_deposit(&users.bob, TOKEN_ID, &users.bob.account_id);
+
+    // Claim tokens to Bob.
+    let res: WrappedBalance = e
+        .claim_to(&users.alice, &users.bob.valid_account_id(), None)
+        .unwrap_json();
+    assert_eq!(res.0, amount);
+    let lockups = e.get_account_lockups(&users.alice);
+    assert!(lockups.is_empty());
+
+    assert_eq!(e.ft_balance_of(&users.alice), 0);
+    assert_eq!(e.ft_balance_of(&users.bob), amount);
+}
+
+#[test]
+fn test_claim_with_ft_transfer_call() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+    let amount = d(10000, TOKEN_DECIMALS);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+    let receiver = e.deploy_lockup_receiver("receiver.near");
+
+    let balance: WrappedBalance = e
+        .add_lockup(
+            &e.owner,
+            amount,
+            &LockupCreate::new_unlocked(users.alice.valid_account_id(), amount),
+        )
+        .unwrap_json();
+    assert_eq!(balance.0, amount);
+
+    // The receiver rejects the message, so the claim is refunded.
+    let res: WrappedBalance = e
+        .claim_to(
+            &users.alice,
+            &receiver.valid_account_id(),
+            Some("invalid".to_string()),
+        )
+        .unwrap_json();
+    assert_eq!(res.0, 0);
+    let lockups = e.get_account_lockups(&users.alice);
+    assert_eq!(lockups.len(), 1);
+    assert_eq!(lockups[0].1.claimed_balance, 0);
+    assert_eq!(lockups[0].1.unclaimed_balance, amount);
+    assert_eq!(e.ft_balance_of(&receiver), 0);
+
+    // Claim straight into a new lockup on the receiver.
+    let msg = serde_json::to_string(&LockupCreate::new_unlocked(
+        users.alice.valid_account_id(),
+        amount,
+    ))
+    .unwrap();
+    let res: WrappedBalance = e
+        .claim_to(&users.alice, &receiver.valid_account_id(), Some(msg))
+        .unwrap_json();
+    assert_eq!(res.0, amount);
+    let lockups = e.get_account_lockups(&users.alice);
+    assert!(lockups.is_empty());
+    let lockup = e.get_lockup(0);
+    assert_eq!(lockup.claimed_balance, amount);
+
+    assert_eq!(e.ft_balance_of(&receiver), amount);
+    let lockups = e.get_receiver_account_lockups(&receiver, &users.alice);
+    assert_eq!(lockups.len(), 1);
+    assert_eq!(lockups[0].1.total_balance, amount);
+}
+
+#[test]
+fn test_claim_by_operator() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+    let amount = d(10000, TOKEN_DECIMALS);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+
+    let lockup_create = LockupCreate {
+        account_id: users.alice.valid_account_id(),
+        schedule: Schedule(vec![
+            Checkpoint {
+                timestamp: GENESIS_TIMESTAMP_SEC,
+                balance: 0,
+                segment: SegmentKind::Linear,
+            },
+            Checkpoint {
+                timestamp: GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC,
+                balance: amount,
+                segment: SegmentKind::Linear,
+            },
+        ]),
+        vesting_schedule: None,
+        transferable: false,
+        clawback_receiver: None,
+    };
+    let balance: WrappedBalance = e.add_lockup(&e.owner, amount, &lockup_create).unwrap_json();
+    assert_eq!(balance.0, amount);
+    ft_storage_deposit(&users.alice, TOKEN_ID, &users.alice.account_id);
+    ft_storage_deposit(&users.bob, TOKEN_ID, &users.bob.account_id);
+
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC / 4);
+
+    // Bob is not an operator yet.
+    let res = e.claim_for(&users.bob, &users.alice);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Not an approved claim operator"));
+
+    // The allowance of another token doesn't let Bob claim.
+    let res = e.approve_claim_operator(
+        &users.alice,
+        &users.bob,
+        Some(claim_allowances(&[("token2.near", amount)])),
+    );
+    assert!(res.is_ok());
+    let res = e.claim_for(&users.bob, &users.alice);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("The claim exceeds the operator allowance"));
+
+    let res = e.approve_claim_operator(
+        &users.alice,
+        &users.bob,
+        Some(claim_allowances(&[(TOKEN_ID, amount / 2)])),
+    );
+    assert!(res.is_ok());
+    assert_eq!(
+        e.get_claim_operators(&users.alice),
+        vec![(
+            users.bob.account_id.clone(),
+            Some(claim_allowances(&[(TOKEN_ID, amount / 2)]))
+        )]
+    );
+
+    // The tokens go to Alice.
+    let res: WrappedBalance = e.claim_for(&users.bob, &users.alice).unwrap_json();
+    assert_eq!(res.0, amount / 4);
+    assert_eq!(e.ft_balance_of(&users.alice), amount / 4);
+    assert_eq!(e.ft_balance_of(&users.bob), 0);
+    assert_eq!(
+        e.get_claim_operators(&users.alice),
+        vec![(
+            users.bob.account_id.clone(),
+            Some(claim_allowances(&[(TOKEN_ID, amount / 4)]))
+        )]
+    );
+
+    // The claim exceeds the remaining allowance.
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC * 3 / 4);
+    let res = e.claim_for(&users.bob, &users.alice);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("The claim exceeds the operator allowance"));
+
+    // Unlimited allowance.
+    let res = e.approve_claim_operator(&users.alice, &users.bob, None);
+    assert!(res.is_ok());
+    let res: WrappedBalance = e.claim_for(&users.bob, &users.alice).unwrap_json();
+    assert_eq!(res.0, amount / 2);
+    assert_eq!(e.ft_balance_of(&users.alice), amount * 3 / 4);
+
+    let res = e.revoke_claim_operator(&users.alice, &users.bob);
+    assert!(res.is_ok());
+    assert!(e.get_claim_operators(&users.alice).is_empty());
+
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC);
+    let res = e.claim_for(&users.bob, &users.alice);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Not an approved claim operator"));
+}
+
+#[test]
+fn test_claim_by_operator_refunds_allowance() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+    let amount = d(10000, TOKEN_DECIMALS);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+
+    let balance: WrappedBalance = e
+        .add_lockup(
+            &e.owner,
+            amount,
+            &LockupCreate::new_unlocked(users.alice.valid_account_id(), amount),
+        )
+        .unwrap_json();
+    assert_eq!(balance.0, amount);
+
+    let res = e.approve_claim_operator(
+        &users.alice,
+        &users.bob,
+        Some(claim_allowances(&[(TOKEN_ID, amount)])),
+    );
+    assert!(res.is_ok());
+
+    // No storage deposit for Alice, so the transfer fails.
+    let res: WrappedBalance = e.claim_for(&users.bob, &users.alice).unwrap_json();
+    assert_eq!(res.0, 0);
+    let lockups = e.get_account_lockups(&users.alice);
+    assert_eq!(lockups[0].1.claimed_balance, 0);
+    assert_eq!(
+        e.get_claim_operators(&users.alice),
+        vec![(
+            users.bob.account_id.clone(),
+            Some(claim_allowances(&[(TOKEN_ID, amount)]))
+        )]
+    );
+}
+
+#[test]
+fn test_lockup_with_schedule_spec() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+    let amount = d(40000, TOKEN_DECIMALS);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+
+    let msg = json!({
+        "account_id": users.alice.valid_account_id(),
+        "schedule": {
+            "cliff_linear": {
+                "start": GENESIS_TIMESTAMP_SEC,
+                "cliff": GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC,
+                "end": GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC * 4,
+                "total_balance": WrappedBalance::from(amount),
+            }
+        },
+    })
+    .to_string();
+    let balance: WrappedBalance = e.ft_transfer_call(&e.owner, amount, &msg).unwrap_json();
+    assert_eq!(balance.0, amount);
+
+    let lockups = e.get_account_lockups(&users.alice);
+    assert_eq!(lockups.len(), 1);
+    assert_eq!(
+        lockups[0].1.schedule,
+        Schedule::cliff_linear(
+            GENESIS_TIMESTAMP_SEC,
+            GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC,
+            GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC * 4,
+            amount,
+        )
+    );
+
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC - 1);
+    let lockups = e.get_account_lockups(&users.alice);
+    assert_eq!(lockups[0].1.unclaimed_balance, 0);
+
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC);
+    let lockups = e.get_account_lockups(&users.alice);
+    assert_eq!(lockups[0].1.unclaimed_balance, amount / 4);
+
+    // A mismatching total balance is rejected.
+    let msg = json!({
+        "account_id": users.alice.valid_account_id(),
+        "schedule": {
+            "periodic": {
+                "start": GENESIS_TIMESTAMP_SEC,
+                "period": ONE_YEAR_SEC,
+                "count": 4,
+                "total_balance": WrappedBalance::from(amount / 2),
+            }
+        },
+    })
+    .to_string();
+    let balance: WrappedBalance = e.ft_transfer_call(&e.owner, amount, &msg).unwrap_json();
+    assert_eq!(balance.0, 0);
+}
+
+#[test]
+fn test_lockup_with_step_segments() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+    let amount = d(40000, TOKEN_DECIMALS);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+
+    let lockup_create = LockupCreate {
+        account_id: users.alice.valid_account_id(),
+        schedule: Schedule(vec![
+            Checkpoint {
+                timestamp: GENESIS_TIMESTAMP_SEC,
+                balance: 0,
+                segment: SegmentKind::Step,
+            },
+            Checkpoint {
+                timestamp: GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC,
+                balance: amount / 4,
+                segment: SegmentKind::Linear,
+            },
+            Checkpoint {
+                timestamp: GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC * 4,
+                balance: amount,
+                segment: SegmentKind::Linear,
+            },
+        ]),
+        vesting_schedule: None,
+        transferable: false,
+        clawback_receiver: None,
+    };
+    let balance: WrappedBalance = e.add_lockup(&e.owner, amount, &lockup_create).unwrap_json();
+    assert_eq!(balance.0, amount);
+
+    let lockups = e.get_account_lockups(&users.alice);
+    assert_eq!(lockups[0].1.schedule, lockup_create.schedule);
+
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC - 1);
+    let lockups = e.get_account_lockups(&users.alice);
+    assert_eq!(lockups[0].1.unclaimed_balance, 0);
+
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC);
+    let lockups = e.get_account_lockups(&users.alice);
+    assert_eq!(lockups[0].1.unclaimed_balance, amount / 4);
+
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC * 5 / 2);
+    let lockups = e.get_account_lockups(&users.alice);
+    assert_eq!(lockups[0].1.unclaimed_balance, amount * 5 / 8);
+}
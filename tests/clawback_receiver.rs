@@ -0,0 +1,152 @@
+mod setup;
+
+use crate::setup::*;
+
+fn create_terminatable_lockup(
+    e: &Env,
+    users: &Users,
+    amount: Balance,
+    clawback_receiver: Option<ClawbackReceiver>,
+) -> LockupIndex {
+    let (lockup_schedule, vesting_schedule) = lockup_vesting_schedule(amount);
+    let lockup_create = LockupCreate {
+        account_id: users.alice.valid_account_id(),
+        schedule: lockup_schedule,
+        vesting_schedule: Some(VestingConditions::Schedule(vesting_schedule)),
+        transferable: false,
+        clawback_receiver,
+    };
+    let balance: WrappedBalance = e.add_lockup(&e.owner, amount, &lockup_create).unwrap_json();
+    assert_eq!(balance.0, amount);
+    e.get_account_lockups(&users.alice)
+        .into_iter()
+        .map(|(lockup_index, _)| lockup_index)
+        .max()
+        .unwrap()
+}
+
+#[test]
+fn test_clawback_receiver() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+    let amount = d(60000, TOKEN_DECIMALS);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+    let lockup_index = create_terminatable_lockup(
+        &e,
+        &users,
+        amount,
+        Some(ClawbackReceiver {
+            account_id: users.dude.valid_account_id(),
+            msg: None,
+        }),
+    );
+
+    // the payer keeps the termination authority, the treasury gets the tokens
+    let res = e.terminate(&users.dude, lockup_index);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Not authorized to terminate the lockup"));
+    let balance_before = e.ft_balance_of(&e.owner);
+    let res: WrappedBalance = e.terminate(&e.owner, lockup_index).unwrap_json();
+    assert_eq!(res.0, amount);
+    assert_eq!(e.ft_balance_of(&users.dude), amount);
+    assert_eq!(e.ft_balance_of(&e.owner), balance_before);
+}
+
+#[test]
+fn test_clawback_receiver_transfer_call_refund() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+    let amount = d(60000, TOKEN_DECIMALS);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+    // the receiver isn't a contract, so `ft_transfer_call` refunds everything
+    let lockup_index = create_terminatable_lockup(
+        &e,
+        &users,
+        amount,
+        Some(ClawbackReceiver {
+            account_id: users.dude.valid_account_id(),
+            msg: Some("".to_string()),
+        }),
+    );
+
+    let (_, available) = e.get_storage_balance(&e.owner).unwrap();
+    let res: WrappedBalance = e.terminate(&e.owner, lockup_index).unwrap_json();
+    assert_eq!(res.0, 0);
+    assert_eq!(e.ft_balance_of(&users.dude), 0);
+
+    // the refunded balance is unlocked for the receiver,
+    // the payer of the terminated lockup pays for its storage
+    let lockups = e.get_account_lockups(&users.dude);
+    assert_eq!(lockups.len(), 1);
+    assert_eq!(lockups[0].1.total_balance, amount);
+    assert_eq!(lockups[0].1.unclaimed_balance, amount);
+    assert!(e.get_storage_balance(&e.owner).unwrap().1 < available);
+}
+
+#[test]
+fn test_draft_group_clawback_receiver() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+    let amount = d(60000, TOKEN_DECIMALS);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+    let clawback_receiver = ClawbackReceiver {
+        account_id: users.dude.valid_account_id(),
+        msg: None,
+    };
+
+    let (lockup_schedule, vesting_schedule) = lockup_vesting_schedule(amount);
+    let lockup_create = LockupCreate {
+        account_id: users.alice.valid_account_id(),
+        schedule: lockup_schedule,
+        vesting_schedule: Some(VestingConditions::Schedule(vesting_schedule)),
+        transferable: false,
+        clawback_receiver: None,
+    };
+    e.create_draft_group(&e.owner);
+
+    // the draft operators can't redirect the clawback
+    let res = e.create_draft(
+        &e.owner,
+        &Draft {
+            draft_group_id: 0,
+            lockup_create: LockupCreate {
+                clawback_receiver: Some(clawback_receiver.clone()),
+                ..lockup_create.clone()
+            },
+        },
+    );
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status())
+        .contains("The clawback receiver of the drafts is set by the draft group funding"));
+
+    let res = e.create_draft(
+        &e.owner,
+        &Draft {
+            draft_group_id: 0,
+            lockup_create,
+        },
+    );
+    assert!(res.is_ok());
+    let res =
+        e.fund_draft_group_with_clawback_receiver(&e.owner, amount, 0, clawback_receiver.clone());
+    assert!(res.is_ok());
+    assert_eq!(
+        e.get_draft_group(0).unwrap().clawback_receiver,
+        Some(clawback_receiver.clone())
+    );
+
+    let lockup_index: LockupIndex = e.convert_draft(&users.bob, 0).unwrap_json();
+    let termination_config = e.get_lockup(lockup_index).termination_config.unwrap();
+    assert_eq!(
+        termination_config.beneficiary_id,
+        e.owner.valid_account_id()
+    );
+    assert_eq!(
+        termination_config.clawback_receiver,
+        Some(clawback_receiver)
+    );
+
+    let res: WrappedBalance = e.terminate(&e.owner, lockup_index).unwrap_json();
+    assert_eq!(res.0, amount);
+    assert_eq!(e.ft_balance_of(&users.dude), amount);
+}
@@ -24,7 +24,12 @@ fn test_deposit_whitelist_get_single() {
     );
 
     // user from whiltelist can remove other users
-    let res = e.remove_from_deposit_whitelist_single(&users.eve, &e.owner.valid_account_id());
+    let proposal_id: AdminProposalIndex = e
+        .remove_from_deposit_whitelist_single(&users.eve, &e.owner.valid_account_id())
+        .unwrap_json();
+    // revoking an admin is never accepted automatically
+    assert_eq!(e.get_deposit_whitelist().len(), 2);
+    let res = e.accept_admin_change(&users.eve, proposal_id);
     assert!(res.is_ok());
 
     let deposit_whitelist = e.get_deposit_whitelist();
@@ -51,13 +56,17 @@ fn test_deposit_whitelist_get() {
             Checkpoint {
                 timestamp: 0,
                 balance: 0,
+                segment: SegmentKind::Linear,
             },
             Checkpoint {
                 timestamp: 1,
                 balance: amount,
+                segment: SegmentKind::Linear,
             },
         ]),
         vesting_schedule: None,
+        transferable: false,
+        clawback_receiver: None,
     };
     let balance: WrappedBalance = e.add_lockup(&e.owner, amount, &lockup_create).unwrap_json();
     assert_eq!(balance.0, amount);
@@ -75,7 +84,10 @@ fn test_deposit_whitelist_get() {
     );
 
     // user from whiltelist can remove other users
-    let res = e.remove_from_deposit_whitelist(&users.eve, &e.owner.valid_account_id());
+    let proposal_id: AdminProposalIndex = e
+        .remove_from_deposit_whitelist(&users.eve, &e.owner.valid_account_id())
+        .unwrap_json();
+    let res = e.accept_admin_change(&users.eve, proposal_id);
     assert!(res.is_ok());
 
     let deposit_whitelist = e.get_deposit_whitelist();
@@ -101,7 +113,10 @@ fn test_deposit_whitelist_get() {
     assert_eq!(lockups.len(), 1);
 
     // try remove last user from the list, should fail
-    let res = e.remove_from_deposit_whitelist(&users.eve, &users.eve.valid_account_id());
+    let proposal_id: AdminProposalIndex = e
+        .remove_from_deposit_whitelist(&users.eve, &users.eve.valid_account_id())
+        .unwrap_json();
+    let res = e.accept_admin_change(&users.eve, proposal_id);
     assert!(!res.is_ok());
     assert!(
         format!("{:?}", res.status()).contains("cannot remove all accounts from deposit whitelist")
@@ -545,10 +545,12 @@ fn test_draft_payer_update() {
         Checkpoint {
             timestamp: GENESIS_TIMESTAMP_SEC,
             balance: 0,
+            segment: SegmentKind::Linear,
         },
         Checkpoint {
             timestamp: GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC,
             balance: amount,
+            segment: SegmentKind::Linear,
         },
     ]);
 
@@ -556,6 +558,8 @@ fn test_draft_payer_update() {
         account_id: users.alice.valid_account_id(),
         schedule: schedule.clone(),
         vesting_schedule: Some(VestingConditions::Schedule(schedule.clone())),
+        transferable: false,
+        clawback_receiver: None,
     };
 
     let draft = Draft {
@@ -585,8 +589,13 @@ fn test_draft_payer_update() {
         "expected beneficiary_id from draft group payer_id",
     );
 
-    // terminating as owner, unvested balance returns to the payer
-    let res: WrappedBalance = e.terminate(&e.owner, lockup_index).unwrap_json();
+    // terminating as owner is not allowed, only the payer can terminate
+    let res = e.terminate(&e.owner, lockup_index);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Not authorized to terminate the lockup"));
+
+    // terminating as payer, unvested balance returns to the payer
+    let res: WrappedBalance = e.terminate(&users.dude, lockup_index).unwrap_json();
     assert_eq!(res.0, amount);
     let balance = e.ft_balance_of(&users.alice);
     assert_eq!(balance, 0);
@@ -757,10 +766,12 @@ fn test_draft_operator_lockup_permissions() {
         Checkpoint {
             timestamp: GENESIS_TIMESTAMP_SEC,
             balance: 0,
+            segment: SegmentKind::Linear,
         },
         Checkpoint {
             timestamp: GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC,
             balance: amount,
+            segment: SegmentKind::Linear,
         },
     ]);
 
@@ -768,6 +779,8 @@ fn test_draft_operator_lockup_permissions() {
         account_id: users.alice.valid_account_id(),
         schedule: schedule.clone(),
         vesting_schedule: Some(VestingConditions::Schedule(schedule.clone())),
+        transferable: false,
+        clawback_receiver: None,
     };
 
     ft_storage_deposit(&e.owner, TOKEN_ID, &e.draft_operator.account_id);
@@ -792,7 +805,7 @@ fn test_draft_operator_lockup_permissions() {
     e.set_time_sec(GENESIS_TIMESTAMP_SEC);
     let res = e.terminate(&e.draft_operator, lockup_index);
     assert!(!res.is_ok());
-    assert!(format!("{:?}", res.status()).contains("Not in deposit whitelist"));
+    assert!(format!("{:?}", res.status()).contains("Not authorized to terminate the lockup"));
 
     let draft_group_id = 0;
     let draft = Draft {
@@ -894,7 +907,10 @@ fn test_draft_operator_permission_updates() {
     let res = e.add_to_deposit_whitelist(&e.owner, &users.charlie.valid_account_id());
     assert!(res.is_ok());
     // removing deposit role, draft operator role must be retained
-    let res = e.remove_from_deposit_whitelist(&e.owner, &e.owner.valid_account_id());
+    let proposal_id: AdminProposalIndex = e
+        .remove_from_deposit_whitelist(&e.owner, &e.owner.valid_account_id())
+        .unwrap_json();
+    let res = e.accept_admin_change(&users.charlie, proposal_id);
     assert!(res.is_ok());
     // deposit role is removed
     let res: Vec<AccountId> = e.get_deposit_whitelist();
@@ -913,3 +929,68 @@ fn test_draft_operator_permission_updates() {
     let res = e.create_draft_group(&e.owner);
     assert!(res.is_ok());
 }
+
+#[test]
+fn test_update_and_remove_draft() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+
+    let amount = d(60000, TOKEN_DECIMALS);
+    let draft_group_id: DraftGroupIndex = e.create_draft_group(&e.draft_operator).unwrap_json();
+    let draft = Draft {
+        draft_group_id,
+        lockup_create: LockupCreate::new_unlocked(users.alice.valid_account_id(), amount),
+    };
+    let res = e.create_draft(&e.draft_operator, &draft);
+    assert!(res.is_ok());
+    let res = e.create_draft(&e.draft_operator, &draft);
+    assert!(res.is_ok());
+    assert_eq!(
+        e.get_draft_group(draft_group_id).unwrap().total_amount,
+        amount * 2
+    );
+
+    // fix the account and the amount of the draft 0
+    let updated_draft = Draft {
+        draft_group_id,
+        lockup_create: LockupCreate::new_unlocked(users.bob.valid_account_id(), amount * 2),
+    };
+    let res = e.update_draft(&users.alice, 0, &updated_draft);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Not in draft operators whitelist"));
+    let res = e.update_draft(&e.draft_operator, 0, &updated_draft);
+    assert!(res.is_ok());
+    assert_eq!(
+        e.get_draft(0).unwrap().lockup_create.account_id,
+        users.bob.valid_account_id()
+    );
+    assert_eq!(
+        e.get_draft_group(draft_group_id).unwrap().total_amount,
+        amount * 3
+    );
+
+    let res = e.remove_draft(&e.draft_operator, 1);
+    assert!(res.is_ok());
+    assert!(e.get_draft(1).is_none());
+    let draft_group = e.get_draft_group(draft_group_id).unwrap();
+    assert_eq!(draft_group.total_amount, amount * 2);
+    assert_eq!(draft_group.draft_indices, vec![0]);
+
+    // the drafts can't be edited after the group is funded
+    let res = e.fund_draft_group(&e.owner, amount * 2, draft_group_id);
+    let balance: WrappedBalance = res.unwrap_json();
+    assert_eq!(balance.0, amount * 2);
+    let res = e.update_draft(&e.draft_operator, 0, &draft);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("cannot edit draft, group already funded"));
+    let res = e.remove_draft(&e.draft_operator, 0);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("cannot edit draft, group already funded"));
+
+    let res = e.convert_draft(&users.bob, 0);
+    assert!(res.is_ok());
+    let lockups = e.get_account_lockups(&users.bob);
+    assert_eq!(lockups.len(), 1);
+    assert_eq!(lockups[0].1.total_balance, amount * 2);
+}
@@ -0,0 +1,77 @@
+mod setup;
+
+use crate::setup::*;
+
+#[test]
+fn test_migrate_from_v1_0_0() {
+    let e = Env::deploy(None, &FT_LOCKUP_V1_0_0_WASM_BYTES);
+    let alice = e
+        .near
+        .create_user("alice.near".to_string(), to_yocto("10000"));
+    let amount = d(10000, TOKEN_DECIMALS);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+
+    // the state of the old layout
+    let lockup_create = LockupCreate::new_unlocked(alice.valid_account_id(), amount);
+    let res: WrappedBalance = e.add_lockup(&e.owner, amount, &lockup_create).unwrap_json();
+    assert_eq!(res.0, amount);
+    let res = e.create_draft_group(&e.draft_operator);
+    assert!(res.is_ok());
+    let draft = Draft {
+        draft_group_id: 0,
+        lockup_create: lockup_create.clone(),
+    };
+    let res = e.create_draft(&e.draft_operator, &draft);
+    assert!(res.is_ok());
+
+    let res = e.upgrade();
+    assert!(res.is_ok());
+
+    // only the owner can migrate
+    let res = e.migrate(&alice);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Not an admin"));
+
+    let res = e.migrate(&e.owner);
+    assert!(res.is_ok());
+    assert_eq!(e.get_state_version(), 2);
+    assert_eq!(e.get_token_whitelist(), vec![TOKEN_ID.to_string()]);
+
+    // the lockup, the draft and the draft group are rewritten in batches by anyone
+    let migration = e.get_migration().unwrap();
+    assert_eq!(migration.num_lockups, 1);
+    assert_eq!(migration.num_drafts, 1);
+    assert_eq!(migration.num_draft_groups, 1);
+    let migration: Option<Migration> = e.migrate_batch(&alice, 2).unwrap_json();
+    assert_eq!(migration.unwrap().next_draft_group_id, 0);
+    let migration: Option<Migration> = e.migrate_batch(&alice, 2).unwrap_json();
+    assert!(migration.is_none());
+    assert!(e.get_migration().is_none());
+    let res = e.migrate_batch(&alice, 2);
+    assert!(format!("{:?}", res.status()).contains("No migration in progress"));
+
+    let lockups = e.get_account_lockups(&alice);
+    assert_eq!(lockups.len(), 1);
+    assert_eq!(lockups[0].1.token_account_id, TOKEN_ID.to_string());
+    assert_eq!(lockups[0].1.total_balance, amount);
+    assert!(!lockups[0].1.transferable);
+
+    let draft_group = e.get_draft_group(0).unwrap();
+    assert_eq!(draft_group.total_amount, amount);
+    assert!(draft_group.token_account_id.is_none());
+
+    // the migrated drafts and lockups work as before
+    let res = e.fund_draft_group(&e.owner, amount, 0);
+    assert!(res.is_ok());
+    let res = e.convert_draft(&alice, 0);
+    assert!(res.is_ok());
+    ft_storage_deposit(&e.owner, TOKEN_ID, &alice.account_id);
+    let res: WrappedBalance = e.claim(&alice).unwrap_json();
+    assert_eq!(res.0, amount * 2);
+    assert_eq!(e.ft_balance_of(&alice), amount * 2);
+
+    // migrating the current state does nothing
+    let res = e.migrate(&e.owner);
+    assert!(res.is_ok());
+    assert_eq!(e.get_state_version(), 2);
+}
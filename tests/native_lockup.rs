@@ -0,0 +1,105 @@
+mod setup;
+
+use crate::setup::*;
+
+fn near_balance(user: &UserAccount) -> Balance {
+    user.account().unwrap().amount
+}
+
+#[test]
+fn test_native_lockup_claim() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+    let amount = to_yocto("100");
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+
+    let (lockup_schedule, _) = lockup_vesting_schedule(amount);
+    let lockup_create = LockupCreate {
+        account_id: users.alice.valid_account_id(),
+        schedule: lockup_schedule,
+        vesting_schedule: None,
+        transferable: false,
+        clawback_receiver: None,
+    };
+
+    // only deposit whitelist can create native lockups
+    let res = e.create_native_lockup(&users.bob, amount, &lockup_create);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Not in deposit whitelist"));
+
+    // the attached deposit must match the schedule
+    let res = e.create_native_lockup(&e.owner, amount / 2, &lockup_create);
+    assert!(!res.is_ok());
+
+    let res = e.create_native_lockup(&e.owner, amount, &lockup_create);
+    let lockup_index: LockupIndex = res.unwrap_json();
+
+    let lockups = e.get_account_lockups(&users.alice);
+    assert_eq!(lockups.len(), 1);
+    assert_eq!(lockups[0].0, lockup_index);
+    assert_eq!(lockups[0].1.token_account_id, NATIVE_TOKEN_ID.to_string());
+    assert_eq!(lockups[0].1.total_balance, amount);
+
+    // 3/8 unlocked after 3 years
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC * 3);
+    let balance_before = near_balance(&users.alice);
+    let res: WrappedBalance = e.claim(&users.alice).unwrap_json();
+    assert_eq!(res.0, amount * 3 / 8);
+    let balance_after = near_balance(&users.alice);
+    // some of the NEAR is spent on gas
+    assert!(balance_after > balance_before + amount * 3 / 8 - to_yocto("0.1"));
+    assert!(balance_after < balance_before + amount * 3 / 8);
+
+    // native lockups can't be claimed with ft_transfer_call
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC * 5);
+    let res = e.claim_to(
+        &users.alice,
+        &users.bob.valid_account_id(),
+        Some("".to_string()),
+    );
+    assert!(!res.is_ok());
+    assert!(
+        format!("{:?}", res.status()).contains("Native lockups can't be claimed with a message")
+    );
+
+    let balance_before = near_balance(&users.bob);
+    let res: WrappedBalance = e
+        .claim_to(&users.alice, &users.bob.valid_account_id(), None)
+        .unwrap_json();
+    assert_eq!(res.0, amount * 5 / 8);
+    assert_eq!(near_balance(&users.bob), balance_before + amount * 5 / 8);
+    assert!(e.get_account_lockups(&users.alice).is_empty());
+}
+
+#[test]
+fn test_native_lockup_terminate() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+    let amount = to_yocto("100");
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+
+    let (lockup_schedule, vesting_schedule) = lockup_vesting_schedule(amount);
+    let lockup_create = LockupCreate {
+        account_id: users.alice.valid_account_id(),
+        schedule: lockup_schedule,
+        vesting_schedule: Some(VestingConditions::Schedule(vesting_schedule)),
+        transferable: false,
+        clawback_receiver: None,
+    };
+    let lockup_index: LockupIndex = e
+        .create_native_lockup(&e.owner, amount, &lockup_create)
+        .unwrap_json();
+
+    // terminating after 2 years, half is vested
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC * 2);
+    let balance_before = near_balance(&e.owner);
+    let res: WrappedBalance = e.terminate(&e.owner, lockup_index).unwrap_json();
+    assert_eq!(res.0, amount / 2);
+    let balance_after = near_balance(&e.owner);
+    assert!(balance_after > balance_before + amount / 2 - to_yocto("0.1"));
+
+    let lockups = e.get_account_lockups(&users.alice);
+    assert_eq!(lockups.len(), 1);
+    assert_eq!(lockups[0].1.total_balance, amount / 2);
+    assert_eq!(lockups[0].1.token_account_id, NATIVE_TOKEN_ID.to_string());
+}
@@ -0,0 +1,114 @@
+mod setup;
+
+use crate::setup::*;
+
+#[test]
+fn test_guardians_whitelist() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+
+    let res = e.add_to_guardians_whitelist(&users.alice, &users.eve.valid_account_id());
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Not in deposit whitelist"));
+
+    let res = e.pause(&users.eve, vec![PauseFlag::Claiming]);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Not in guardians whitelist"));
+
+    let res = e.add_to_guardians_whitelist(&e.owner, &users.eve.valid_account_id());
+    assert!(res.is_ok());
+    assert_eq!(
+        e.get_guardians_whitelist(),
+        vec![users.eve.account_id.clone()]
+    );
+
+    let res = e.pause(&users.eve, vec![PauseFlag::Claiming]);
+    assert!(res.is_ok());
+    assert_eq!(e.get_paused(), vec![PauseFlag::Claiming]);
+
+    // deposit whitelist can unpause too
+    let res = e.unpause(&e.owner, vec![PauseFlag::Claiming]);
+    assert!(res.is_ok());
+    assert!(e.get_paused().is_empty());
+
+    let res = e.remove_from_guardians_whitelist(&e.owner, &users.eve.valid_account_id());
+    assert!(res.is_ok());
+    assert!(e.get_guardians_whitelist().is_empty());
+    let res = e.pause(&users.eve, vec![PauseFlag::Claiming]);
+    assert!(!res.is_ok());
+}
+
+#[test]
+fn test_pause_flags() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+    let amount = d(60000, TOKEN_DECIMALS);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+    ft_storage_deposit(&e.owner, TOKEN_ID, &users.alice.account_id);
+
+    let (lockup_schedule, vesting_schedule) = lockup_vesting_schedule(amount);
+    let lockup_create = LockupCreate {
+        account_id: users.alice.valid_account_id(),
+        schedule: lockup_schedule,
+        vesting_schedule: Some(VestingConditions::Schedule(vesting_schedule)),
+        transferable: false,
+        clawback_receiver: None,
+    };
+
+    // creation
+    let res = e.pause(
+        &e.owner,
+        vec![
+            PauseFlag::Creation,
+            PauseFlag::Claiming,
+            PauseFlag::Termination,
+            PauseFlag::DraftConversion,
+        ],
+    );
+    assert!(res.is_ok());
+    let res: WrappedBalance = e.add_lockup(&e.owner, amount, &lockup_create).unwrap_json();
+    assert_eq!(res.0, 0);
+    assert!(e.get_account_lockups(&users.alice).is_empty());
+    let res = e.unpause(&e.owner, vec![PauseFlag::Creation]);
+    assert!(res.is_ok());
+    let res: WrappedBalance = e.add_lockup(&e.owner, amount, &lockup_create).unwrap_json();
+    assert_eq!(res.0, amount);
+
+    // claiming
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC * 3);
+    let res = e.claim(&users.alice);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Claiming is paused"));
+    let res = e.unpause(&e.owner, vec![PauseFlag::Claiming]);
+    assert!(res.is_ok());
+    let res: WrappedBalance = e.claim(&users.alice).unwrap_json();
+    assert_eq!(res.0, amount * 3 / 8);
+
+    // termination
+    let res = e.terminate(&e.owner, 0);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Termination is paused"));
+    let res = e.unpause(&e.owner, vec![PauseFlag::Termination]);
+    assert!(res.is_ok());
+    let res: WrappedBalance = e.terminate(&e.owner, 0).unwrap_json();
+    assert_eq!(res.0, amount / 4);
+
+    // draft conversion
+    e.create_draft_group(&e.draft_operator);
+    let draft = Draft {
+        draft_group_id: 0,
+        lockup_create: LockupCreate::new_unlocked(users.bob.valid_account_id(), amount),
+    };
+    let res = e.create_draft(&e.draft_operator, &draft);
+    assert!(res.is_ok());
+    let res = e.fund_draft_group(&e.owner, amount, 0);
+    assert!(res.is_ok());
+    let res = e.convert_draft(&users.bob, 0);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Draft conversion is paused"));
+    let res = e.unpause(&e.owner, vec![PauseFlag::DraftConversion]);
+    assert!(res.is_ok());
+    let res = e.convert_draft(&users.bob, 0);
+    assert!(res.is_ok());
+    assert!(e.get_paused().is_empty());
+}
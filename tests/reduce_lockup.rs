@@ -0,0 +1,64 @@
+mod setup;
+
+use crate::setup::*;
+
+#[test]
+fn test_reduce_lockup() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+    let amount = d(60000, TOKEN_DECIMALS);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+
+    let (lockup_schedule, vesting_schedule) = lockup_vesting_schedule(amount);
+    let lockup_create = LockupCreate {
+        account_id: users.alice.valid_account_id(),
+        schedule: lockup_schedule,
+        vesting_schedule: Some(VestingConditions::Schedule(vesting_schedule)),
+        transferable: false,
+        clawback_receiver: None,
+    };
+    let balance: WrappedBalance = e.add_lockup(&e.owner, amount, &lockup_create).unwrap_json();
+    assert_eq!(balance.0, amount);
+    let lockup_index = e.get_account_lockups(&users.alice)[0].0;
+
+    // 1/4 is vested after the cliff
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC);
+    let res = e.reduce_lockup(&users.bob, lockup_index, amount / 2);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Not authorized to terminate the lockup"));
+    let res = e.reduce_lockup(&e.owner, lockup_index, amount * 3 / 4 + 1);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("The amount exceeds the unvested balance"));
+
+    let balance_before = e.ft_balance_of(&e.owner);
+    let res: WrappedBalance = e
+        .reduce_lockup(&e.owner, lockup_index, amount / 2)
+        .unwrap_json();
+    assert_eq!(res.0, amount / 2);
+    assert_eq!(e.ft_balance_of(&e.owner), balance_before + amount / 2);
+    let lockup = e.get_lockup(lockup_index);
+    assert_eq!(lockup.total_balance, amount / 2);
+    assert!(lockup.termination_config.is_some());
+
+    // the vested part is kept, the rest of the grant vests proportionally
+    match lockup.termination_config.unwrap().vesting_schedule {
+        VestingConditions::Schedule(vesting_schedule) => {
+            assert_eq!(
+                vesting_schedule.unlocked_balance(GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC),
+                amount / 4
+            );
+            assert_eq!(
+                vesting_schedule.unlocked_balance(GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC * 4),
+                amount / 2
+            );
+        }
+        _ => panic!("expected the vesting schedule"),
+    }
+
+    // the remaining unvested balance can still be terminated
+    let balance_before = e.ft_balance_of(&e.owner);
+    let res: WrappedBalance = e.terminate(&e.owner, lockup_index).unwrap_json();
+    assert_eq!(res.0, amount / 4);
+    assert_eq!(e.ft_balance_of(&e.owner), balance_before + amount / 4);
+    assert_eq!(e.get_lockup(lockup_index).total_balance, amount / 4);
+}
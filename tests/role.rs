@@ -0,0 +1,177 @@
+mod setup;
+
+use crate::setup::*;
+
+#[test]
+fn test_roles() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+    let amount = d(60000, TOKEN_DECIMALS);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+
+    assert_eq!(e.get_role_members(Role::Admin), vec![e.owner.account_id()]);
+    assert_eq!(
+        e.get_role_members(Role::Depositor),
+        vec![e.owner.account_id()]
+    );
+    assert_eq!(
+        e.get_role_members(Role::DraftOperator),
+        vec![e.draft_operator.account_id()]
+    );
+    assert!(e.get_role_members(Role::Terminator).is_empty());
+    assert!(e.get_role_members(Role::Pauser).is_empty());
+
+    // only admins can grant roles
+    let res = e.grant_role(
+        &users.eve,
+        Role::Depositor,
+        vec![users.eve.valid_account_id()],
+    );
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Not an admin"));
+
+    // depositor is granted with a proposal, it can create lockups, but can't manage the roles
+    let proposal_id: Option<AdminProposalIndex> = e
+        .grant_role(
+            &e.owner,
+            Role::Depositor,
+            vec![users.eve.valid_account_id()],
+        )
+        .unwrap_json();
+    assert!(proposal_id.is_some());
+    assert_eq!(
+        e.get_role_members(Role::Depositor),
+        vec![users.eve.account_id(), e.owner.account_id()]
+    );
+    assert_eq!(e.get_deposit_whitelist().len(), 2);
+    ft_storage_deposit(&e.owner, TOKEN_ID, &users.eve.account_id);
+    e.ft_transfer(&e.owner, amount, &users.eve);
+    let lockup_create = LockupCreate::new_unlocked(users.alice.valid_account_id(), amount);
+    let balance: WrappedBalance = e
+        .add_lockup(&users.eve, amount, &lockup_create)
+        .unwrap_json();
+    assert_eq!(balance.0, amount);
+    let res = e.grant_role(&users.eve, Role::Admin, vec![users.eve.valid_account_id()]);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Not an admin"));
+    let res = e.add_to_draft_operators_whitelist(&users.eve, &users.dude.valid_account_id());
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Not in deposit whitelist"));
+
+    // the admin role is changed with proposals
+    let res = e.grant_role(&e.owner, Role::Admin, vec![users.bob.valid_account_id()]);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status())
+        .contains("The admin role can only be changed with a proposal"));
+
+    // the last admin can't be revoked
+    let proposal_id: AdminProposalIndex = e
+        .propose_admin_change(
+            &e.owner,
+            &AdminChange::RevokeRoles {
+                roles: vec![Role::Admin],
+                account_ids: vec![e.owner.valid_account_id()],
+            },
+        )
+        .unwrap_json();
+    let res = e.accept_admin_change(&e.owner, proposal_id);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Cannot remove all admins"));
+    let res = e.cancel_admin_change(&e.owner, proposal_id);
+    assert!(res.is_ok());
+
+    // a new admin can revoke the other one, the other roles are kept
+    let res = e.propose_admin_change(
+        &e.owner,
+        &AdminChange::GrantRoles {
+            roles: vec![Role::Admin],
+            account_ids: vec![users.bob.valid_account_id()],
+        },
+    );
+    assert!(res.is_ok());
+    let proposal_id: AdminProposalIndex = e
+        .propose_admin_change(
+            &users.bob,
+            &AdminChange::RevokeRoles {
+                roles: vec![Role::Admin],
+                account_ids: vec![e.owner.valid_account_id()],
+            },
+        )
+        .unwrap_json();
+    // revoking an admin is never accepted automatically
+    assert_eq!(e.get_role_members(Role::Admin).len(), 2);
+    let res = e.accept_admin_change(&users.bob, proposal_id);
+    assert!(res.is_ok());
+    assert_eq!(
+        e.get_role_members(Role::Admin),
+        vec![users.bob.account_id()]
+    );
+    assert_eq!(
+        e.get_role_members(Role::Depositor),
+        vec![users.eve.account_id(), e.owner.account_id()]
+    );
+    let res = e.grant_role(&e.owner, Role::Pauser, vec![users.dude.valid_account_id()]);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Not an admin"));
+
+    // the whitelist wrappers manage the same roles
+    let res = e.add_to_guardians_whitelist(&users.bob, &users.dude.valid_account_id());
+    assert!(res.is_ok());
+    assert_eq!(
+        e.get_role_members(Role::Pauser),
+        vec![users.dude.account_id()]
+    );
+    let res = e.revoke_role(
+        &users.bob,
+        Role::Pauser,
+        vec![users.dude.valid_account_id()],
+    );
+    assert!(res.is_ok());
+    assert!(e.get_guardians_whitelist().is_empty());
+}
+
+#[test]
+fn test_terminator_role() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+    let amount = d(60000, TOKEN_DECIMALS);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+
+    let (lockup_schedule, vesting_schedule) = lockup_vesting_schedule(amount);
+    let lockup_create = LockupCreate {
+        account_id: users.alice.valid_account_id(),
+        schedule: lockup_schedule,
+        vesting_schedule: Some(VestingConditions::Schedule(vesting_schedule)),
+        transferable: false,
+        clawback_receiver: None,
+    };
+    let balance: WrappedBalance = e.add_lockup(&e.owner, amount, &lockup_create).unwrap_json();
+    assert_eq!(balance.0, amount);
+    let lockup_index = e.get_account_lockups(&users.alice)[0].0;
+
+    let res = e.terminate(&users.charlie, lockup_index);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Not authorized to terminate the lockup"));
+
+    // terminator is granted with a proposal
+    let proposal_id: Option<AdminProposalIndex> = e
+        .grant_role(
+            &e.owner,
+            Role::Terminator,
+            vec![users.charlie.valid_account_id()],
+        )
+        .unwrap_json();
+    assert!(proposal_id.is_some());
+    assert_eq!(
+        e.get_lockup_terminators(lockup_index),
+        vec![e.owner.account_id(), users.charlie.account_id()]
+    );
+
+    // the unvested balance still goes to the termination beneficiary
+    let balance_before = e.ft_balance_of(&e.owner);
+    let res: WrappedBalance = e.terminate(&users.charlie, lockup_index).unwrap_json();
+    assert_eq!(res.0, amount);
+    assert_eq!(e.ft_balance_of(&e.owner), balance_before + amount);
+    assert_eq!(e.ft_balance_of(&users.charlie), 0);
+    assert!(e.get_lockup_terminators(lockup_index).is_empty());
+}
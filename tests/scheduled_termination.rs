@@ -0,0 +1,116 @@
+mod setup;
+
+use crate::setup::*;
+
+#[test]
+fn test_scheduled_termination() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+    let amount = d(60000, TOKEN_DECIMALS);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+
+    let (lockup_schedule, vesting_schedule) = lockup_vesting_schedule(amount);
+    let lockup_create = LockupCreate {
+        account_id: users.alice.valid_account_id(),
+        schedule: lockup_schedule,
+        vesting_schedule: Some(VestingConditions::Schedule(vesting_schedule)),
+        transferable: false,
+        clawback_receiver: None,
+    };
+    let balance: WrappedBalance = e.add_lockup(&e.owner, amount, &lockup_create).unwrap_json();
+    assert_eq!(balance.0, amount);
+    let lockup_index = e.get_account_lockups(&users.alice)[0].0;
+    let termination_timestamp = GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC;
+
+    let res = e.schedule_termination(&users.bob, lockup_index, termination_timestamp);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Not authorized to terminate the lockup"));
+    let res = e.schedule_termination(&e.owner, lockup_index, GENESIS_TIMESTAMP_SEC);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("expected termination_timestamp > now"));
+
+    // the scheduled termination can be cancelled before its timestamp
+    let res = e.schedule_termination(&e.owner, lockup_index, termination_timestamp);
+    assert!(res.is_ok());
+    assert_eq!(
+        e.get_lockup(lockup_index).scheduled_termination,
+        Some(ScheduledTermination {
+            termination_timestamp,
+            hashed_schedule: None,
+            salt: None,
+        })
+    );
+    let res = e.execute_scheduled_termination(&users.bob, lockup_index);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("The termination is scheduled at"));
+    let res = e.cancel_scheduled_termination(&e.owner, lockup_index);
+    assert!(res.is_ok());
+    assert!(e.get_lockup(lockup_index).scheduled_termination.is_none());
+    let res = e.execute_scheduled_termination(&users.bob, lockup_index);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("No scheduled termination"));
+
+    // afterwards anyone can execute it at the scheduled timestamp
+    let res = e.schedule_termination(&e.owner, lockup_index, termination_timestamp);
+    assert!(res.is_ok());
+    e.set_time_sec(termination_timestamp + ONE_DAY_SEC);
+    let res = e.cancel_scheduled_termination(&e.owner, lockup_index);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status())
+        .contains("The scheduled termination can't be cancelled after"));
+
+    let balance_before = e.ft_balance_of(&e.owner);
+    let res: WrappedBalance = e
+        .execute_scheduled_termination(&users.bob, lockup_index)
+        .unwrap_json();
+    assert_eq!(res.0, amount * 3 / 4);
+    assert_eq!(e.ft_balance_of(&e.owner), balance_before + amount * 3 / 4);
+    let lockup = e.get_lockup(lockup_index);
+    assert_eq!(lockup.total_balance, amount / 4);
+    assert!(lockup.termination_config.is_none());
+    assert!(lockup.scheduled_termination.is_none());
+}
+
+#[test]
+fn test_claim_after_scheduled_termination_timestamp() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+    let amount = d(60000, TOKEN_DECIMALS);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+
+    let (lockup_schedule, vesting_schedule) = lockup_vesting_schedule(amount);
+    let lockup_create = LockupCreate {
+        account_id: users.alice.valid_account_id(),
+        schedule: lockup_schedule,
+        vesting_schedule: Some(VestingConditions::Schedule(vesting_schedule)),
+        transferable: false,
+        clawback_receiver: None,
+    };
+    let balance: WrappedBalance = e.add_lockup(&e.owner, amount, &lockup_create).unwrap_json();
+    assert_eq!(balance.0, amount);
+    let lockup_index = e.get_account_lockups(&users.alice)[0].0;
+    let termination_timestamp = GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC;
+    let res = e.schedule_termination(&e.owner, lockup_index, termination_timestamp);
+    assert!(res.is_ok());
+
+    // the lockup is fully unlocked, but only the balance vested at the termination is claimable
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC * 5);
+    assert_eq!(e.get_lockup(lockup_index).unclaimed_balance, amount / 4);
+    ft_storage_deposit(&users.alice, TOKEN_ID, &users.alice.account_id);
+    let res: WrappedBalance = e.claim(&users.alice).unwrap_json();
+    assert_eq!(res.0, amount / 4);
+    assert_eq!(e.ft_balance_of(&users.alice), amount / 4);
+    let res: WrappedBalance = e.claim(&users.alice).unwrap_json();
+    assert_eq!(res.0, 0);
+
+    let balance_before = e.ft_balance_of(&e.owner);
+    let res: WrappedBalance = e
+        .execute_scheduled_termination(&users.bob, lockup_index)
+        .unwrap_json();
+    assert_eq!(res.0, amount * 3 / 4);
+    assert_eq!(e.ft_balance_of(&e.owner), balance_before + amount * 3 / 4);
+    let lockup = e.get_lockup(lockup_index);
+    assert_eq!(lockup.total_balance, amount / 4);
+    assert_eq!(lockup.claimed_balance, amount / 4);
+    assert_eq!(lockup.unclaimed_balance, 0);
+}
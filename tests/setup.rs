@@ -1,26 +1,45 @@
 #![allow(dead_code)]
 
+pub use std::collections::HashMap;
+pub use std::convert::TryInto;
 pub use std::iter;
 
 use near_contract_standards::fungible_token::metadata::{FungibleTokenMetadata, FT_METADATA_SPEC};
 pub use near_sdk::json_types::{Base58CryptoHash, ValidAccountId, WrappedBalance};
-use near_sdk::serde_json::json;
-pub use near_sdk::{env, serde_json, AccountId, Balance, Gas, Timestamp};
+pub use near_sdk::serde_json::json;
+pub use near_sdk::{env, serde_json, AccountId, Balance, Gas, PendingContractTx, Timestamp};
+use near_sdk_sim::hash::CryptoHash;
 use near_sdk_sim::runtime::GenesisConfig;
+use near_sdk_sim::transaction::Transaction;
 pub use near_sdk_sim::{
     deploy, init_simulator, to_yocto, ContractAccount, ExecutionResult, UserAccount, ViewResult,
 };
 
+pub use ft_lockup::admin_change::{
+    AdminChange, AdminProposal, AdminProposalIndex, DEFAULT_ADMIN_CHANGE_DELAY_SEC,
+};
 pub use ft_lockup::draft::{Draft, DraftGroupIndex, DraftIndex};
 use ft_lockup::ft_token_receiver::DraftGroupFunding;
 pub use ft_lockup::lockup::{Lockup, LockupCreate, LockupIndex};
-pub use ft_lockup::schedule::{Checkpoint, Schedule};
-pub use ft_lockup::termination::{TerminationConfig, VestingConditions};
+pub use ft_lockup::migration::Migration;
+pub use ft_lockup::pause::PauseFlag;
+pub use ft_lockup::role::Role;
+pub use ft_lockup::schedule::{Checkpoint, Schedule, SegmentKind};
+pub use ft_lockup::termination::{
+    ClawbackReceiver, ScheduledTermination, TerminationConfig, VestingConditions,
+};
+pub use ft_lockup::termination_approval::{
+    TerminationApprovalConfig, TerminationRequest, TerminationRequestIndex,
+};
+pub use ft_lockup::upgrade::{StagedUpgrade, StagedUpgradeDelay, DEFAULT_UPGRADE_DELAY_SEC};
 pub use ft_lockup::view::{DraftGroupView, DraftView, LockupView};
-pub use ft_lockup::{ContractContract as FtLockupContract, TimestampSec};
+pub use ft_lockup::{
+    ContractContract as FtLockupContract, TimestampSec, TokenAccountId, NATIVE_TOKEN_ID,
+};
 
 near_sdk_sim::lazy_static_include::lazy_static_include_bytes! {
     FT_LOCKUP_WASM_BYTES => "res/ft_lockup.wasm",
+    FT_LOCKUP_V1_0_0_WASM_BYTES => "res/ft_lockup_v1_0_0.wasm",
     FUNGIBLE_TOKEN_WASM_BYTES => "res/fungible_token.wasm",
 }
 
@@ -67,36 +86,44 @@ pub fn lockup_vesting_schedule(amount: u128) -> (Schedule, Schedule) {
         Checkpoint {
             timestamp: GENESIS_TIMESTAMP_SEC,
             balance: 0,
+            segment: SegmentKind::Linear,
         },
         Checkpoint {
             timestamp: GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC * 2,
             balance: 0,
+            segment: SegmentKind::Linear,
         },
         Checkpoint {
             timestamp: GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC * 4,
             balance: amount * 3 / 4,
+            segment: SegmentKind::Linear,
         },
         Checkpoint {
             timestamp: GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC * 4 + 1,
             balance: amount,
+            segment: SegmentKind::Linear,
         },
     ]);
     let vesting_schedule = Schedule(vec![
         Checkpoint {
             timestamp: GENESIS_TIMESTAMP_SEC,
             balance: 0,
+            segment: SegmentKind::Linear,
         },
         Checkpoint {
             timestamp: GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC - 1,
             balance: 0,
+            segment: SegmentKind::Linear,
         },
         Checkpoint {
             timestamp: GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC,
             balance: amount / 4,
+            segment: SegmentKind::Linear,
         },
         Checkpoint {
             timestamp: GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC * 4,
             balance: amount,
+            segment: SegmentKind::Linear,
         },
     ]);
     (lockup_schedule, vesting_schedule)
@@ -107,33 +134,65 @@ pub fn lockup_vesting_schedule_2(amount: u128) -> (Schedule, Schedule) {
         Checkpoint {
             timestamp: GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC * 2,
             balance: 0,
+            segment: SegmentKind::Linear,
         },
         Checkpoint {
             timestamp: GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC * 4,
             balance: amount * 3 / 4,
+            segment: SegmentKind::Linear,
         },
         Checkpoint {
             timestamp: GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC * 4 + 1,
             balance: amount,
+            segment: SegmentKind::Linear,
         },
     ]);
     let vesting_schedule = Schedule(vec![
         Checkpoint {
             timestamp: GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC - 1,
             balance: 0,
+            segment: SegmentKind::Linear,
         },
         Checkpoint {
             timestamp: GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC,
             balance: amount / 4,
+            segment: SegmentKind::Linear,
         },
         Checkpoint {
             timestamp: GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC * 4,
             balance: amount,
+            segment: SegmentKind::Linear,
         },
     ]);
     (lockup_schedule, vesting_schedule)
 }
 
+/// Deploys a fungible token with the total supply owned by the owner.
+pub fn deploy_token(near: &UserAccount, owner: &UserAccount, token_id: &str) -> UserAccount {
+    near.deploy_and_init(
+        &FUNGIBLE_TOKEN_WASM_BYTES,
+        token_id.to_string(),
+        "new",
+        &json!({
+            "owner_id": owner.valid_account_id(),
+            "total_supply": WrappedBalance::from(TOKEN_TOTAL_SUPPLY),
+            "metadata": FungibleTokenMetadata {
+                spec: FT_METADATA_SPEC.to_string(),
+                name: "Token".to_string(),
+                symbol: "TOKEN".to_string(),
+                icon: None,
+                reference: None,
+                reference_hash: None,
+                decimals: TOKEN_DECIMALS,
+            }
+        })
+        .to_string()
+        .into_bytes(),
+        to_yocto("10"),
+        DEFAULT_GAS,
+    )
+}
+
 pub fn storage_deposit(
     user: &UserAccount,
     contract_id: &str,
@@ -170,12 +229,49 @@ pub fn ft_storage_deposit(user: &UserAccount, token_account_id: &str, account_id
     );
 }
 
+/// Prepays the lockup contract storage for the lockups and drafts created by the account.
+pub fn lockup_storage_deposit(user: &UserAccount, contract_id: &str, account_id: &str) {
+    storage_deposit(user, contract_id, account_id, to_yocto("1"));
+}
+
+pub fn code_hash(code: &[u8]) -> Base58CryptoHash {
+    let hash: [u8; 32] = near_sdk_sim::hash::hash(code).as_ref().try_into().unwrap();
+    hash.into()
+}
+
 pub fn to_nano(timestamp: u32) -> Timestamp {
     Timestamp::from(timestamp) * 10u64.pow(9)
 }
 
 impl Env {
     pub fn init(deposit_whitelist: Option<Vec<ValidAccountId>>) -> Self {
+        let e = Self::deploy(deposit_whitelist, &FT_LOCKUP_WASM_BYTES);
+        lockup_storage_deposit(&e.owner, FT_LOCKUP_ID, OWNER_ID);
+        lockup_storage_deposit(&e.owner, FT_LOCKUP_ID, DRAFT_OPERATOR_ID);
+        e.remove_admin_change_delay();
+        e
+    }
+
+    /// The tests apply the admin changes right away, unless they set a delay.
+    fn remove_admin_change_delay(&self) {
+        let timestamp = self.near.borrow_runtime().cur_block.block_timestamp;
+        let proposal_id: AdminProposalIndex = self
+            .propose_admin_change(
+                &self.owner,
+                &AdminChange::SetDelay {
+                    admin_change_delay_sec: 0,
+                },
+            )
+            .unwrap_json();
+        self.near.borrow_runtime_mut().cur_block.block_timestamp =
+            timestamp + to_nano(DEFAULT_ADMIN_CHANGE_DELAY_SEC);
+        self.accept_admin_change(&self.owner, proposal_id)
+            .assert_success();
+        self.near.borrow_runtime_mut().cur_block.block_timestamp = timestamp;
+    }
+
+    /// Deploys the given lockup contract code, e.g. of a previous version.
+    pub fn deploy(deposit_whitelist: Option<Vec<ValidAccountId>>, wasm_bytes: &[u8]) -> Self {
         let mut genesis_config = GenesisConfig::default();
         genesis_config.block_prod_time = 0;
         let root = init_simulator(Some(genesis_config));
@@ -183,33 +279,12 @@ impl Env {
         let owner = near.create_user(OWNER_ID.to_string(), to_yocto("10000"));
         let draft_operator = near.create_user(DRAFT_OPERATOR_ID.to_string(), to_yocto("10000"));
 
-        let token = near.deploy_and_init(
-            &FUNGIBLE_TOKEN_WASM_BYTES,
-            TOKEN_ID.to_string(),
-            "new",
-            &json!({
-                "owner_id": owner.valid_account_id(),
-                "total_supply": WrappedBalance::from(TOKEN_TOTAL_SUPPLY),
-                "metadata": FungibleTokenMetadata {
-                    spec: FT_METADATA_SPEC.to_string(),
-                    name: "Token".to_string(),
-                    symbol: "TOKEN".to_string(),
-                    icon: None,
-                    reference: None,
-                    reference_hash: None,
-                    decimals: TOKEN_DECIMALS,
-                }
-            })
-            .to_string()
-            .into_bytes(),
-            to_yocto("10"),
-            DEFAULT_GAS,
-        );
+        let token = deploy_token(&near, &owner, TOKEN_ID);
 
         let contract = deploy!(
             contract: FtLockupContract,
             contract_id: FT_LOCKUP_ID.to_string(),
-            bytes: &FT_LOCKUP_WASM_BYTES,
+            bytes: wasm_bytes,
             signer_account: near,
             deposit: to_yocto("10"),
             gas: DEFAULT_GAS,
@@ -232,106 +307,744 @@ impl Env {
         }
     }
 
-    pub fn ft_transfer(
+    /// Deploys the current lockup contract code over the deployed one.
+    pub fn upgrade(&self) -> ExecutionResult {
+        self.contract
+            .user_account
+            .create_transaction(FT_LOCKUP_ID.to_string())
+            .deploy_contract(FT_LOCKUP_WASM_BYTES.to_vec())
+            .submit()
+    }
+
+    pub fn stage_upgrade(
+        &self,
+        user: &UserAccount,
+        code_hash: Base58CryptoHash,
+    ) -> ExecutionResult {
+        user.function_call(
+            self.contract.contract.stage_upgrade(code_hash),
+            DEFAULT_GAS,
+            1,
+        )
+    }
+
+    pub fn cancel_staged_upgrade(&self, user: &UserAccount) -> ExecutionResult {
+        user.function_call(
+            self.contract.contract.cancel_staged_upgrade(),
+            DEFAULT_GAS,
+            1,
+        )
+    }
+
+    pub fn deploy_staged_upgrade(&self, user: &UserAccount, code: &[u8]) -> ExecutionResult {
+        user.call(
+            FT_LOCKUP_ID.to_string(),
+            "deploy_staged_upgrade",
+            code,
+            MAX_GAS,
+            1,
+        )
+    }
+
+    pub fn set_upgrade_delay(
+        &self,
+        user: &UserAccount,
+        upgrade_delay_sec: TimestampSec,
+    ) -> ExecutionResult {
+        user.function_call(
+            self.contract.contract.set_upgrade_delay(upgrade_delay_sec),
+            DEFAULT_GAS,
+            1,
+        )
+    }
+
+    pub fn get_staged_upgrade(&self) -> Option<StagedUpgrade> {
+        self.near
+            .view_method_call(self.contract.contract.get_staged_upgrade())
+            .unwrap_json()
+    }
+
+    pub fn get_upgrade_delay(&self) -> TimestampSec {
+        self.near
+            .view_method_call(self.contract.contract.get_upgrade_delay())
+            .unwrap_json()
+    }
+
+    pub fn get_staged_upgrade_delay(&self) -> Option<StagedUpgradeDelay> {
+        self.near
+            .view_method_call(self.contract.contract.get_staged_upgrade_delay())
+            .unwrap_json()
+    }
+
+    pub fn migrate(&self, user: &UserAccount) -> ExecutionResult {
+        user.call(
+            FT_LOCKUP_ID.to_string(),
+            "migrate",
+            &json!({}).to_string().into_bytes(),
+            MAX_GAS,
+            0,
+        )
+    }
+
+    pub fn migrate_batch(&self, user: &UserAccount, limit: u32) -> ExecutionResult {
+        user.function_call(self.contract.contract.migrate_batch(limit), DEFAULT_GAS, 0)
+    }
+
+    pub fn get_migration(&self) -> Option<Migration> {
+        self.near
+            .view_method_call(self.contract.contract.get_migration())
+            .unwrap_json()
+    }
+
+    pub fn get_state_version(&self) -> u32 {
+        self.near
+            .view_method_call(self.contract.contract.get_state_version())
+            .unwrap_json()
+    }
+
+    /// Deploys another lockup contract for the same token to receive claims with `ft_transfer_call`.
+    pub fn deploy_lockup_receiver(&self, account_id: &str) -> UserAccount {
+        let receiver = self.near.deploy_and_init(
+            &FT_LOCKUP_WASM_BYTES,
+            account_id.to_string(),
+            "new",
+            &json!({
+                "token_account_id": self.token.valid_account_id(),
+                "deposit_whitelist": vec![self.contract.user_account.valid_account_id()],
+            })
+            .to_string()
+            .into_bytes(),
+            to_yocto("10"),
+            DEFAULT_GAS,
+        );
+        ft_storage_deposit(&self.owner, TOKEN_ID, account_id);
+        lockup_storage_deposit(&self.owner, account_id, FT_LOCKUP_ID);
+        receiver
+    }
+
+    pub fn ft_transfer(
+        &self,
+        sender: &UserAccount,
+        amount: Balance,
+        receiver: &UserAccount,
+    ) -> ExecutionResult {
+        sender.call(
+            self.token.account_id.clone(),
+            "ft_transfer",
+            &json!({
+                "receiver_id": receiver.valid_account_id(),
+                "amount": WrappedBalance::from(amount),
+            })
+            .to_string()
+            .into_bytes(),
+            MAX_GAS,
+            1,
+        )
+    }
+
+    pub fn ft_transfer_call(
+        &self,
+        user: &UserAccount,
+        amount: Balance,
+        msg: &str,
+    ) -> ExecutionResult {
+        user.call(
+            self.token.account_id.clone(),
+            "ft_transfer_call",
+            &json!({
+                "receiver_id": self.contract.user_account.valid_account_id(),
+                "amount": WrappedBalance::from(amount),
+                "msg": msg,
+            })
+            .to_string()
+            .into_bytes(),
+            FT_TRANSFER_CALL_GAS,
+            1,
+        )
+    }
+
+    pub fn ft_transfer_call_token(
+        &self,
+        token: &UserAccount,
+        user: &UserAccount,
+        amount: Balance,
+        msg: &str,
+    ) -> ExecutionResult {
+        user.call(
+            token.account_id.clone(),
+            "ft_transfer_call",
+            &json!({
+                "receiver_id": self.contract.user_account.valid_account_id(),
+                "amount": WrappedBalance::from(amount),
+                "msg": msg,
+            })
+            .to_string()
+            .into_bytes(),
+            FT_TRANSFER_CALL_GAS,
+            1,
+        )
+    }
+
+    pub fn ft_balance_of_token(&self, token: &UserAccount, user: &UserAccount) -> Balance {
+        let balance: WrappedBalance = self
+            .near
+            .view(
+                token.account_id.clone(),
+                "ft_balance_of",
+                &json!({
+                    "account_id": user.valid_account_id(),
+                })
+                .to_string()
+                .into_bytes(),
+            )
+            .unwrap_json();
+        balance.0
+    }
+
+    pub fn add_to_token_whitelist(
+        &self,
+        user: &UserAccount,
+        token: &UserAccount,
+    ) -> ExecutionResult {
+        user.function_call(
+            self.contract
+                .contract
+                .add_to_token_whitelist(vec![token.valid_account_id()]),
+            DEFAULT_GAS,
+            1,
+        )
+    }
+
+    pub fn remove_from_token_whitelist(
+        &self,
+        user: &UserAccount,
+        token: &UserAccount,
+    ) -> ExecutionResult {
+        user.function_call(
+            self.contract
+                .contract
+                .remove_from_token_whitelist(vec![token.valid_account_id()]),
+            DEFAULT_GAS,
+            1,
+        )
+    }
+
+    /// Returns the total and the available storage balance of the account.
+    pub fn get_storage_balance(&self, user: &UserAccount) -> Option<(Balance, Balance)> {
+        let balance: Option<serde_json::Value> = self
+            .near
+            .view(
+                self.contract.user_account.account_id.clone(),
+                "storage_balance_of",
+                &json!({ "account_id": user.valid_account_id() })
+                    .to_string()
+                    .into_bytes(),
+            )
+            .unwrap_json();
+        balance.map(|balance| {
+            let parse = |key: &str| balance[key].as_str().unwrap().parse::<Balance>().unwrap();
+            (parse("total"), parse("available"))
+        })
+    }
+
+    pub fn storage_withdraw(&self, user: &UserAccount, amount: Option<Balance>) -> ExecutionResult {
+        user.call(
+            self.contract.user_account.account_id.clone(),
+            "storage_withdraw",
+            &json!({ "amount": amount.map(WrappedBalance::from) })
+                .to_string()
+                .into_bytes(),
+            DEFAULT_GAS,
+            1,
+        )
+    }
+
+    pub fn storage_unregister(&self, user: &UserAccount) -> ExecutionResult {
+        user.call(
+            self.contract.user_account.account_id.clone(),
+            "storage_unregister",
+            &json!({}).to_string().into_bytes(),
+            DEFAULT_GAS,
+            1,
+        )
+    }
+
+    pub fn pause(&self, user: &UserAccount, flags: Vec<PauseFlag>) -> ExecutionResult {
+        user.function_call(self.contract.contract.pause(flags), DEFAULT_GAS, 1)
+    }
+
+    pub fn unpause(&self, user: &UserAccount, flags: Vec<PauseFlag>) -> ExecutionResult {
+        user.function_call(self.contract.contract.unpause(flags), DEFAULT_GAS, 1)
+    }
+
+    pub fn add_to_guardians_whitelist(
+        &self,
+        user: &UserAccount,
+        account_id: &ValidAccountId,
+    ) -> ExecutionResult {
+        user.function_call(
+            self.contract
+                .contract
+                .add_to_guardians_whitelist(vec![account_id.clone()]),
+            DEFAULT_GAS,
+            1,
+        )
+    }
+
+    pub fn remove_from_guardians_whitelist(
+        &self,
+        user: &UserAccount,
+        account_id: &ValidAccountId,
+    ) -> ExecutionResult {
+        user.function_call(
+            self.contract
+                .contract
+                .remove_from_guardians_whitelist(vec![account_id.clone()]),
+            DEFAULT_GAS,
+            1,
+        )
+    }
+
+    pub fn get_guardians_whitelist(&self) -> Vec<AccountId> {
+        self.near
+            .view_method_call(self.contract.contract.get_guardians_whitelist())
+            .unwrap_json()
+    }
+
+    pub fn grant_role(
+        &self,
+        user: &UserAccount,
+        role: Role,
+        account_ids: Vec<ValidAccountId>,
+    ) -> ExecutionResult {
+        user.function_call(
+            self.contract.contract.grant_role(role, account_ids),
+            DEFAULT_GAS,
+            1,
+        )
+    }
+
+    pub fn revoke_role(
+        &self,
+        user: &UserAccount,
+        role: Role,
+        account_ids: Vec<ValidAccountId>,
+    ) -> ExecutionResult {
+        user.function_call(
+            self.contract.contract.revoke_role(role, account_ids),
+            DEFAULT_GAS,
+            1,
+        )
+    }
+
+    pub fn propose_admin_change(
+        &self,
+        user: &UserAccount,
+        change: &AdminChange,
+    ) -> ExecutionResult {
+        user.function_call(
+            self.contract.contract.propose_admin_change(change.clone()),
+            DEFAULT_GAS,
+            1,
+        )
+    }
+
+    pub fn accept_admin_change(
+        &self,
+        user: &UserAccount,
+        proposal_id: AdminProposalIndex,
+    ) -> ExecutionResult {
+        user.function_call(
+            self.contract.contract.accept_admin_change(proposal_id),
+            DEFAULT_GAS,
+            1,
+        )
+    }
+
+    pub fn cancel_admin_change(
+        &self,
+        user: &UserAccount,
+        proposal_id: AdminProposalIndex,
+    ) -> ExecutionResult {
+        user.function_call(
+            self.contract.contract.cancel_admin_change(proposal_id),
+            DEFAULT_GAS,
+            1,
+        )
+    }
+
+    pub fn get_admin_proposals(&self) -> Vec<(AdminProposalIndex, AdminProposal)> {
+        self.near
+            .view_method_call(self.contract.contract.get_admin_proposals())
+            .unwrap_json()
+    }
+
+    pub fn get_admin_change_delay(&self) -> TimestampSec {
+        self.near
+            .view_method_call(self.contract.contract.get_admin_change_delay())
+            .unwrap_json()
+    }
+
+    pub fn get_role_members(&self, role: Role) -> Vec<AccountId> {
+        self.near
+            .view_method_call(self.contract.contract.get_role_members(role))
+            .unwrap_json()
+    }
+
+    pub fn get_paused(&self) -> Vec<PauseFlag> {
+        self.near
+            .view_method_call(self.contract.contract.get_paused())
+            .unwrap_json()
+    }
+
+    pub fn get_token_whitelist(&self) -> Vec<AccountId> {
+        self.near
+            .view_method_call(self.contract.contract.get_token_whitelist())
+            .unwrap_json()
+    }
+
+    pub fn add_lockup(
+        &self,
+        user: &UserAccount,
+        amount: Balance,
+        lockup_create: &LockupCreate,
+    ) -> ExecutionResult {
+        self.ft_transfer_call(user, amount, &serde_json::to_string(lockup_create).unwrap())
+    }
+
+    pub fn create_native_lockup(
+        &self,
+        user: &UserAccount,
+        amount: Balance,
+        lockup_create: &LockupCreate,
+    ) -> ExecutionResult {
+        user.function_call(
+            self.contract
+                .contract
+                .create_native_lockup(lockup_create.clone()),
+            DEFAULT_GAS,
+            amount,
+        )
+    }
+
+    pub fn fund_draft_group(
+        &self,
+        user: &UserAccount,
+        amount: Balance,
+        draft_group_id: DraftGroupIndex,
+    ) -> ExecutionResult {
+        let funding = DraftGroupFunding {
+            draft_group_id,
+            try_convert: None,
+            clawback_receiver: None,
+        };
+        self.ft_transfer_call(user, amount, &serde_json::to_string(&funding).unwrap())
+    }
+
+    pub fn fund_draft_group_with_convert(
+        &self,
+        user: &UserAccount,
+        amount: Balance,
+        draft_group_id: DraftGroupIndex,
+    ) -> ExecutionResult {
+        let funding = DraftGroupFunding {
+            draft_group_id,
+            try_convert: Some(true),
+            clawback_receiver: None,
+        };
+        self.ft_transfer_call(user, amount, &serde_json::to_string(&funding).unwrap())
+    }
+
+    pub fn fund_draft_group_with_clawback_receiver(
+        &self,
+        user: &UserAccount,
+        amount: Balance,
+        draft_group_id: DraftGroupIndex,
+        clawback_receiver: ClawbackReceiver,
+    ) -> ExecutionResult {
+        let funding = DraftGroupFunding {
+            draft_group_id,
+            try_convert: None,
+            clawback_receiver: Some(clawback_receiver),
+        };
+        self.ft_transfer_call(user, amount, &serde_json::to_string(&funding).unwrap())
+    }
+
+    pub fn claim(&self, user: &UserAccount) -> ExecutionResult {
+        user.function_call(self.contract.contract.claim(None, None, None), CLAIM_GAS, 0)
+    }
+
+    pub fn claim_to(
+        &self,
+        user: &UserAccount,
+        receiver_id: &ValidAccountId,
+        msg: Option<String>,
+    ) -> ExecutionResult {
+        user.function_call(
+            self.contract
+                .contract
+                .claim(None, Some(receiver_id.clone()), msg),
+            CLAIM_GAS,
+            0,
+        )
+    }
+
+    pub fn claim_for(&self, operator: &UserAccount, user: &UserAccount) -> ExecutionResult {
+        operator.function_call(
+            self.contract
+                .contract
+                .claim_for(user.valid_account_id(), None),
+            CLAIM_GAS,
+            0,
+        )
+    }
+
+    pub fn approve_claim_operator(
+        &self,
+        user: &UserAccount,
+        operator: &UserAccount,
+        allowances: Option<HashMap<TokenAccountId, WrappedBalance>>,
+    ) -> ExecutionResult {
+        user.function_call(
+            self.contract
+                .contract
+                .approve_claim_operator(operator.valid_account_id(), allowances),
+            DEFAULT_GAS,
+            1,
+        )
+    }
+
+    pub fn revoke_claim_operator(
+        &self,
+        user: &UserAccount,
+        operator: &UserAccount,
+    ) -> ExecutionResult {
+        user.function_call(
+            self.contract
+                .contract
+                .revoke_claim_operator(operator.valid_account_id()),
+            DEFAULT_GAS,
+            1,
+        )
+    }
+
+    pub fn claim_specific_lockups(
+        &self,
+        user: &UserAccount,
+        amounts: &Vec<(LockupIndex, Option<WrappedBalance>)>,
+    ) -> ExecutionResult {
+        user.function_call(
+            self.contract
+                .contract
+                .claim(Some(amounts.clone()), None, None),
+            CLAIM_GAS,
+            0,
+        )
+    }
+
+    pub fn terminate(&self, user: &UserAccount, lockup_index: LockupIndex) -> ExecutionResult {
+        user.function_call(
+            self.contract
+                .contract
+                .terminate(lockup_index, None, None, None),
+            TERMINATE_GAS,
+            1,
+        )
+    }
+
+    pub fn terminate_batch(
+        &self,
+        user: &UserAccount,
+        lockup_indices: &[LockupIndex],
+    ) -> ExecutionResult {
+        self.terminate_batch_with_schedules(
+            user,
+            lockup_indices
+                .iter()
+                .map(|lockup_index| (*lockup_index, None, None))
+                .collect(),
+        )
+    }
+
+    pub fn terminate_batch_with_schedules(
+        &self,
+        user: &UserAccount,
+        lockups: Vec<(LockupIndex, Option<Schedule>, Option<&[u8]>)>,
+    ) -> ExecutionResult {
+        user.function_call(
+            self.contract.contract.terminate_batch(
+                lockups
+                    .into_iter()
+                    .map(|(lockup_index, hashed_schedule, salt)| {
+                        (
+                            lockup_index,
+                            hashed_schedule,
+                            salt.map(|salt| salt.to_vec().into()),
+                        )
+                    })
+                    .collect(),
+                None,
+            ),
+            MAX_GAS,
+            1,
+        )
+    }
+
+    pub fn schedule_termination(
+        &self,
+        user: &UserAccount,
+        lockup_index: LockupIndex,
+        termination_timestamp: TimestampSec,
+    ) -> ExecutionResult {
+        user.function_call(
+            self.contract.contract.schedule_termination(
+                lockup_index,
+                None,
+                termination_timestamp,
+                None,
+            ),
+            DEFAULT_GAS,
+            1,
+        )
+    }
+
+    pub fn reduce_lockup(
         &self,
-        sender: &UserAccount,
+        user: &UserAccount,
+        lockup_index: LockupIndex,
         amount: Balance,
-        receiver: &UserAccount,
     ) -> ExecutionResult {
-        sender.call(
-            self.token.account_id.clone(),
-            "ft_transfer",
-            &json!({
-                "receiver_id": receiver.valid_account_id(),
-                "amount": WrappedBalance::from(amount),
-            })
-            .to_string()
-            .into_bytes(),
-            MAX_GAS,
+        user.function_call(
+            self.contract
+                .contract
+                .reduce_lockup(lockup_index, amount.into()),
+            TERMINATE_GAS,
             1,
         )
     }
 
-    pub fn ft_transfer_call(
+    pub fn pause_vesting(&self, user: &UserAccount, lockup_index: LockupIndex) -> ExecutionResult {
+        user.function_call(
+            self.contract.contract.pause_vesting(lockup_index),
+            DEFAULT_GAS,
+            1,
+        )
+    }
+
+    pub fn resume_vesting(&self, user: &UserAccount, lockup_index: LockupIndex) -> ExecutionResult {
+        user.function_call(
+            self.contract.contract.resume_vesting(lockup_index),
+            DEFAULT_GAS,
+            1,
+        )
+    }
+
+    pub fn cancel_scheduled_termination(
         &self,
         user: &UserAccount,
-        amount: Balance,
-        msg: &str,
+        lockup_index: LockupIndex,
     ) -> ExecutionResult {
-        user.call(
-            self.token.account_id.clone(),
-            "ft_transfer_call",
-            &json!({
-                "receiver_id": self.contract.user_account.valid_account_id(),
-                "amount": WrappedBalance::from(amount),
-                "msg": msg,
-            })
-            .to_string()
-            .into_bytes(),
-            FT_TRANSFER_CALL_GAS,
+        user.function_call(
+            self.contract
+                .contract
+                .cancel_scheduled_termination(lockup_index),
+            DEFAULT_GAS,
             1,
         )
     }
 
-    pub fn add_lockup(
+    pub fn execute_scheduled_termination(
         &self,
         user: &UserAccount,
-        amount: Balance,
-        lockup_create: &LockupCreate,
+        lockup_index: LockupIndex,
     ) -> ExecutionResult {
-        self.ft_transfer_call(user, amount, &serde_json::to_string(lockup_create).unwrap())
+        user.function_call(
+            self.contract
+                .contract
+                .execute_scheduled_termination(lockup_index),
+            TERMINATE_GAS,
+            0,
+        )
     }
 
-    pub fn fund_draft_group(
+    pub fn set_termination_approval_config(
         &self,
         user: &UserAccount,
-        amount: Balance,
-        draft_group_id: DraftGroupIndex,
+        config: Option<TerminationApprovalConfig>,
     ) -> ExecutionResult {
-        let funding = DraftGroupFunding {
-            draft_group_id,
-            try_convert: None,
-        };
-        self.ft_transfer_call(user, amount, &serde_json::to_string(&funding).unwrap())
+        user.function_call(
+            self.contract
+                .contract
+                .set_termination_approval_config(config),
+            DEFAULT_GAS,
+            1,
+        )
     }
 
-    pub fn fund_draft_group_with_convert(
+    pub fn get_termination_approval_config(&self) -> Option<TerminationApprovalConfig> {
+        self.near
+            .view_method_call(self.contract.contract.get_termination_approval_config())
+            .unwrap_json()
+    }
+
+    pub fn request_termination(
         &self,
         user: &UserAccount,
-        amount: Balance,
-        draft_group_id: DraftGroupIndex,
+        lockup_index: LockupIndex,
     ) -> ExecutionResult {
-        let funding = DraftGroupFunding {
-            draft_group_id,
-            try_convert: Some(true),
-        };
-        self.ft_transfer_call(user, amount, &serde_json::to_string(&funding).unwrap())
+        self.request_termination_at(user, lockup_index, None)
     }
 
-    pub fn claim(&self, user: &UserAccount) -> ExecutionResult {
-        user.function_call(self.contract.contract.claim(None), CLAIM_GAS, 0)
+    pub fn request_termination_at(
+        &self,
+        user: &UserAccount,
+        lockup_index: LockupIndex,
+        termination_timestamp: Option<TimestampSec>,
+    ) -> ExecutionResult {
+        user.function_call(
+            self.contract.contract.request_termination(
+                lockup_index,
+                None,
+                termination_timestamp,
+                None,
+            ),
+            DEFAULT_GAS,
+            1,
+        )
     }
 
-    pub fn claim_specific_lockups(
+    pub fn approve_termination(
         &self,
         user: &UserAccount,
-        amounts: &Vec<(LockupIndex, Option<WrappedBalance>)>,
+        request_id: TerminationRequestIndex,
     ) -> ExecutionResult {
         user.function_call(
-            self.contract.contract.claim(Some(amounts.clone())),
-            CLAIM_GAS,
-            0,
+            self.contract.contract.approve_termination(request_id),
+            TERMINATE_GAS,
+            1,
         )
     }
 
-    pub fn terminate(&self, user: &UserAccount, lockup_index: LockupIndex) -> ExecutionResult {
+    pub fn cancel_termination_request(
+        &self,
+        user: &UserAccount,
+        request_id: TerminationRequestIndex,
+    ) -> ExecutionResult {
         user.function_call(
-            self.contract.contract.terminate(lockup_index, None, None),
-            TERMINATE_GAS,
+            self.contract
+                .contract
+                .cancel_termination_request(request_id),
+            DEFAULT_GAS,
             1,
         )
     }
 
+    pub fn get_termination_requests(&self) -> Vec<(TerminationRequestIndex, TerminationRequest)> {
+        self.near
+            .view_method_call(self.contract.contract.get_termination_requests())
+            .unwrap_json()
+    }
+
     pub fn terminate_with_schedule(
         &self,
         user: &UserAccount,
@@ -341,7 +1054,26 @@ impl Env {
         user.function_call(
             self.contract
                 .contract
-                .terminate(lockup_index, Some(hashed_schedule), None),
+                .terminate(lockup_index, Some(hashed_schedule), None, None),
+            TERMINATE_GAS,
+            1,
+        )
+    }
+
+    pub fn terminate_with_salted_schedule(
+        &self,
+        user: &UserAccount,
+        lockup_index: LockupIndex,
+        hashed_schedule: Schedule,
+        salt: &[u8],
+    ) -> ExecutionResult {
+        user.function_call(
+            self.contract.contract.terminate(
+                lockup_index,
+                Some(hashed_schedule),
+                None,
+                Some(salt.to_vec().into()),
+            ),
             TERMINATE_GAS,
             1,
         )
@@ -356,7 +1088,7 @@ impl Env {
         user.function_call(
             self.contract
                 .contract
-                .terminate(lockup_index, None, Some(termination_timestamp)),
+                .terminate(lockup_index, None, Some(termination_timestamp), None),
             TERMINATE_GAS,
             1,
         )
@@ -368,7 +1100,7 @@ impl Env {
         account_id: &ValidAccountId,
     ) -> ExecutionResult {
         user.call(
-            self.contract.account_id(),
+            self.contract.user_account.account_id.clone(),
             "remove_from_deposit_whitelist",
             &json!({ "account_id": account_id }).to_string().into_bytes(),
             DEFAULT_GAS,
@@ -382,7 +1114,7 @@ impl Env {
         account_id: &ValidAccountId,
     ) -> ExecutionResult {
         user.call(
-            self.contract.account_id(),
+            self.contract.user_account.account_id.clone(),
             "add_to_deposit_whitelist",
             &json!({ "account_id": account_id }).to_string().into_bytes(),
             DEFAULT_GAS,
@@ -396,7 +1128,7 @@ impl Env {
         account_id: &ValidAccountId,
     ) -> ExecutionResult {
         user.call(
-            self.contract.account_id(),
+            self.contract.user_account.account_id.clone(),
             "remove_from_deposit_whitelist",
             &json!({ "account_ids": vec![account_id] })
                 .to_string()
@@ -412,7 +1144,7 @@ impl Env {
         account_id: &ValidAccountId,
     ) -> ExecutionResult {
         user.call(
-            self.contract.account_id(),
+            self.contract.user_account.account_id.clone(),
             "add_to_deposit_whitelist",
             &json!({ "account_ids": vec![account_id] })
                 .to_string()
@@ -450,6 +1182,79 @@ impl Env {
         )
     }
 
+    pub fn transfer_lockup(
+        &self,
+        user: &UserAccount,
+        lockup_index: LockupIndex,
+        new_account_id: &ValidAccountId,
+    ) -> ExecutionResult {
+        user.function_call(
+            self.contract
+                .contract
+                .transfer_lockup(lockup_index, new_account_id.clone()),
+            DEFAULT_GAS,
+            1,
+        )
+    }
+
+    pub fn split_lockup(
+        &self,
+        user: &UserAccount,
+        lockup_index: LockupIndex,
+        amount: Balance,
+        new_account_id: &ValidAccountId,
+    ) -> ExecutionResult {
+        user.function_call(
+            self.contract.contract.split_lockup(
+                lockup_index,
+                amount.into(),
+                new_account_id.clone(),
+            ),
+            DEFAULT_GAS,
+            1,
+        )
+    }
+
+    pub fn merge_lockups(
+        &self,
+        user: &UserAccount,
+        lockup_indices: Vec<LockupIndex>,
+    ) -> ExecutionResult {
+        user.function_call(
+            self.contract.contract.merge_lockups(lockup_indices),
+            DEFAULT_GAS,
+            1,
+        )
+    }
+
+    pub fn add_termination_delegates(
+        &self,
+        user: &UserAccount,
+        account_ids: Vec<ValidAccountId>,
+    ) -> ExecutionResult {
+        user.function_call(
+            self.contract
+                .contract
+                .add_termination_delegates(account_ids),
+            DEFAULT_GAS,
+            1,
+        )
+    }
+
+    pub fn remove_termination_delegates(
+        &self,
+        user: &UserAccount,
+        account_ids: Vec<ValidAccountId>,
+    ) -> ExecutionResult {
+        user.function_call(
+            self.contract
+                .contract
+                .remove_termination_delegates(account_ids),
+            DEFAULT_GAS,
+            1,
+        )
+    }
+
     pub fn create_draft_group(&self, user: &UserAccount) -> ExecutionResult {
         user.function_call(self.contract.contract.create_draft_group(), DEFAULT_GAS, 0)
     }
@@ -470,6 +1275,27 @@ impl Env {
         )
     }
 
+    pub fn update_draft(
+        &self,
+        user: &UserAccount,
+        draft_id: DraftIndex,
+        draft: &Draft,
+    ) -> ExecutionResult {
+        user.function_call(
+            self.contract.contract.update_draft(draft_id, draft.clone()),
+            DEFAULT_GAS,
+            0,
+        )
+    }
+
+    pub fn remove_draft(&self, user: &UserAccount, draft_id: DraftIndex) -> ExecutionResult {
+        user.function_call(
+            self.contract.contract.remove_draft(draft_id),
+            DEFAULT_GAS,
+            0,
+        )
+    }
+
     pub fn convert_draft(&self, user: &UserAccount, draft_id: DraftIndex) -> ExecutionResult {
         user.function_call(
             self.contract.contract.convert_draft(draft_id),
@@ -544,12 +1370,51 @@ impl Env {
             .unwrap_json()
     }
 
+    pub fn get_termination_delegates(&self, user: &UserAccount) -> Vec<AccountId> {
+        self.near
+            .view_method_call(
+                self.contract
+                    .contract
+                    .get_termination_delegates(user.valid_account_id()),
+            )
+            .unwrap_json()
+    }
+
+    pub fn get_claim_operators(
+        &self,
+        user: &UserAccount,
+    ) -> Vec<(AccountId, Option<HashMap<TokenAccountId, WrappedBalance>>)> {
+        self.near
+            .view_method_call(
+                self.contract
+                    .contract
+                    .get_claim_operators(user.valid_account_id()),
+            )
+            .unwrap_json()
+    }
+
+    pub fn get_lockup_terminators(&self, lockup_index: LockupIndex) -> Vec<AccountId> {
+        self.near
+            .view_method_call(self.contract.contract.get_lockup_terminators(lockup_index))
+            .unwrap_json()
+    }
+
     pub fn hash_schedule(&self, schedule: &Schedule) -> Base58CryptoHash {
         self.near
             .view_method_call(self.contract.contract.hash_schedule(schedule.clone()))
             .unwrap_json()
     }
 
+    pub fn hash_schedule_with_salt(&self, schedule: &Schedule, salt: &[u8]) -> Base58CryptoHash {
+        self.near
+            .view_method_call(
+                self.contract
+                    .contract
+                    .hash_schedule_with_salt(schedule.clone(), salt.to_vec().into()),
+            )
+            .unwrap_json()
+    }
+
     pub fn validate_schedule(
         &self,
         schedule: &Schedule,
@@ -644,6 +1509,24 @@ impl Env {
         lockup.unwrap()
     }
 
+    pub fn get_receiver_account_lockups(
+        &self,
+        receiver: &UserAccount,
+        user: &UserAccount,
+    ) -> Vec<(LockupIndex, LockupView)> {
+        self.near
+            .view(
+                receiver.account_id.clone(),
+                "get_account_lockups",
+                &json!({
+                    "account_id": user.valid_account_id(),
+                })
+                .to_string()
+                .into_bytes(),
+            )
+            .unwrap_json()
+    }
+
     pub fn ft_balance_of(&self, user: &UserAccount) -> Balance {
         let balance: WrappedBalance = self
             .near
@@ -663,11 +1546,38 @@ impl Env {
     pub fn set_time_sec(&self, timestamp_sec: TimestampSec) {
         self.near.borrow_runtime_mut().cur_block.block_timestamp = to_nano(timestamp_sec);
     }
+
+    /// Sends the calls in one block, so each call is executed before the callbacks
+    /// of the previous ones.
+    pub fn call_in_one_block(
+        &self,
+        user: &UserAccount,
+        calls: Vec<(PendingContractTx, Gas, Balance)>,
+    ) {
+        let mut runtime = user.borrow_runtime_mut();
+        let nonce = runtime
+            .view_access_key(&user.account_id, &user.signer.public_key)
+            .unwrap()
+            .nonce;
+        for (i, (pending_tx, gas, deposit)) in calls.into_iter().enumerate() {
+            let tx = Transaction::new(
+                user.account_id.clone(),
+                user.signer.public_key.clone(),
+                pending_tx.receiver_id,
+                nonce + 1 + i as u64,
+                CryptoHash::default(),
+            )
+            .function_call(pending_tx.method, pending_tx.args, gas, deposit)
+            .sign(&user.signer);
+            runtime.send_tx(tx);
+        }
+        runtime.process_all().unwrap();
+    }
 }
 
 impl Users {
     pub fn init(e: &Env) -> Self {
-        Self {
+        let users = Self {
             alice: e
                 .near
                 .create_user("alice.near".to_string(), to_yocto("10000")),
@@ -683,10 +1593,29 @@ impl Users {
             eve: e
                 .near
                 .create_user("eve.near".to_string(), to_yocto("10000")),
+        };
+        for user in [
+            &users.alice,
+            &users.bob,
+            &users.charlie,
+            &users.dude,
+            &users.eve,
+        ]
+        .iter()
+        {
+            lockup_storage_deposit(&e.owner, FT_LOCKUP_ID, &user.account_id);
         }
+        users
     }
 }
 
+pub fn claim_allowances(allowances: &[(&str, Balance)]) -> HashMap<TokenAccountId, WrappedBalance> {
+    allowances
+        .iter()
+        .map(|(token_account_id, allowance)| (token_account_id.to_string(), (*allowance).into()))
+        .collect()
+}
+
 pub const fn d(value: Balance, decimals: u8) -> Balance {
     value * 10u128.pow(decimals as _)
 }
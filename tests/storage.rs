@@ -0,0 +1,169 @@
+mod setup;
+
+use crate::setup::*;
+
+#[test]
+fn test_storage_lockup() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+    let amount = d(10000, TOKEN_DECIMALS);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+
+    let frank = e
+        .near
+        .create_user("frank.near".to_string(), to_yocto("10000"));
+    let res = e.add_to_deposit_whitelist(&e.owner, &frank.valid_account_id());
+    assert!(res.is_ok());
+    ft_storage_deposit(&e.owner, TOKEN_ID, &frank.account_id);
+    e.ft_transfer(&e.owner, amount, &frank);
+    assert!(e.get_storage_balance(&frank).is_none());
+
+    let lockup_create = LockupCreate::new_unlocked(users.alice.valid_account_id(), amount);
+
+    // the lockup storage is not paid for, the tokens are refunded
+    let res: WrappedBalance = e.add_lockup(&frank, amount, &lockup_create).unwrap_json();
+    assert_eq!(res.0, 0);
+    assert!(e.get_account_lockups(&users.alice).is_empty());
+    assert_eq!(e.ft_balance_of(&frank), amount);
+
+    lockup_storage_deposit(&frank, FT_LOCKUP_ID, &frank.account_id);
+    let (total, available) = e.get_storage_balance(&frank).unwrap();
+    assert_eq!(total, to_yocto("1"));
+    let registered_available = available;
+
+    let res: WrappedBalance = e.add_lockup(&frank, amount, &lockup_create).unwrap_json();
+    assert_eq!(res.0, amount);
+    let (total, available) = e.get_storage_balance(&frank).unwrap();
+    assert_eq!(total, to_yocto("1"));
+    assert!(available < registered_available);
+
+    // the storage in use can't be withdrawn
+    let res = e.storage_withdraw(&frank, Some(total));
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status())
+        .contains("The amount is greater than the available storage balance"));
+    let res = e.storage_unregister(&frank);
+    assert!(!res.is_ok());
+    assert!(
+        format!("{:?}", res.status()).contains("Can't unregister the account with storage in use")
+    );
+
+    // the storage of the index is refunded once the lockup is fully claimed,
+    // the lockup record is kept and stays paid for
+    let lockup_available = available;
+    ft_storage_deposit(&e.owner, TOKEN_ID, &users.alice.account_id);
+    let res: WrappedBalance = e.claim(&users.alice).unwrap_json();
+    assert_eq!(res.0, amount);
+    assert!(e.get_account_lockups(&users.alice).is_empty());
+    let (_, available) = e.get_storage_balance(&frank).unwrap();
+    assert!(available > lockup_available);
+    assert!(available < registered_available);
+
+    let res = e.storage_withdraw(&frank, None);
+    assert!(res.is_ok());
+    let (total, available) = e.get_storage_balance(&frank).unwrap();
+    assert!(total > to_yocto("1") - registered_available);
+    assert_eq!(available, 0);
+
+    let res = e.storage_unregister(&frank);
+    assert!(!res.is_ok());
+    assert!(
+        format!("{:?}", res.status()).contains("Can't unregister the account with storage in use")
+    );
+}
+
+#[test]
+fn test_storage_split_and_merge() {
+    let e = Env::init(None);
+    let amount = d(10000, TOKEN_DECIMALS);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+
+    let frank = e
+        .near
+        .create_user("frank.near".to_string(), to_yocto("10000"));
+    let (lockup_schedule, _vesting_schedule) = lockup_vesting_schedule(amount);
+    let lockup_create = LockupCreate {
+        account_id: frank.valid_account_id(),
+        schedule: lockup_schedule,
+        vesting_schedule: None,
+        transferable: false,
+        clawback_receiver: None,
+    };
+    let res: WrappedBalance = e.add_lockup(&e.owner, amount, &lockup_create).unwrap_json();
+    assert_eq!(res.0, amount);
+    let lockup_index = e.get_account_lockups(&frank)[0].0;
+
+    // the owner pays for the storage of the new lockups
+    let res = e.split_lockup(&frank, lockup_index, amount / 2, &frank.valid_account_id());
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("The payer is not registered for storage"));
+
+    lockup_storage_deposit(&frank, FT_LOCKUP_ID, &frank.account_id);
+    let (_, registered_available) = e.get_storage_balance(&frank).unwrap();
+    let new_lockup_index: LockupIndex = e
+        .split_lockup(&frank, lockup_index, amount / 2, &frank.valid_account_id())
+        .unwrap_json();
+    let (_, available) = e.get_storage_balance(&frank).unwrap();
+    assert!(available < registered_available);
+
+    // the storage of the merged lockup replaces the storage of the split one
+    let res = e.merge_lockups(&frank, vec![lockup_index, new_lockup_index]);
+    assert!(res.is_ok());
+    let (_, available) = e.get_storage_balance(&frank).unwrap();
+    assert!(available < registered_available);
+    assert_eq!(e.get_account_lockups(&frank).len(), 1);
+}
+
+#[test]
+fn test_storage_drafts() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+    let amount = d(10000, TOKEN_DECIMALS);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+
+    let (_, registered_available) = e.get_storage_balance(&e.draft_operator).unwrap();
+
+    e.create_draft_group(&e.draft_operator);
+    let draft = Draft {
+        draft_group_id: 0,
+        lockup_create: LockupCreate::new_unlocked(users.alice.valid_account_id(), amount),
+    };
+    let res = e.create_drafts(&e.draft_operator, &vec![draft.clone(), draft]);
+    assert!(res.is_ok());
+    let (_, available) = e.get_storage_balance(&e.draft_operator).unwrap();
+    assert!(available < registered_available);
+    let draft_storage_cost = (registered_available - available) / 2;
+
+    // the converted lockup storage is paid by the draft storage payer
+    let res = e.fund_draft_group(&e.owner, amount * 2, 0);
+    assert!(res.is_ok());
+    let res = e.convert_draft(&users.alice, 0);
+    assert!(res.is_ok());
+    let (_, available) = e.get_storage_balance(&e.draft_operator).unwrap();
+    assert!(available < registered_available - draft_storage_cost);
+
+    // the storage of the index is refunded once the lockup is fully claimed
+    let lockup_available = available;
+    ft_storage_deposit(&e.owner, TOKEN_ID, &users.alice.account_id);
+    let res: WrappedBalance = e.claim(&users.alice).unwrap_json();
+    assert_eq!(res.0, amount);
+    let (_, available) = e.get_storage_balance(&e.draft_operator).unwrap();
+    assert!(available > lockup_available);
+    assert!(available < registered_available - draft_storage_cost);
+    let claimed_available = available;
+
+    // the storage of the deleted drafts is refunded
+    e.create_draft_group(&e.draft_operator);
+    let draft = Draft {
+        draft_group_id: 1,
+        lockup_create: LockupCreate::new_unlocked(users.bob.valid_account_id(), amount),
+    };
+    let res = e.create_draft(&e.draft_operator, &draft);
+    let draft_id: DraftIndex = res.unwrap_json();
+    let res = e.discard_draft_group(&e.draft_operator, 1);
+    assert!(res.is_ok());
+    let res = e.delete_drafts(&e.draft_operator, vec![draft_id]);
+    assert!(res.is_ok());
+    let (_, available) = e.get_storage_balance(&e.draft_operator).unwrap();
+    assert_eq!(available, claimed_available);
+}
@@ -21,10 +21,12 @@ fn test_terminate_basic_payer_logic() {
         Checkpoint {
             timestamp: GENESIS_TIMESTAMP_SEC,
             balance: 0,
+            segment: SegmentKind::Linear,
         },
         Checkpoint {
             timestamp: GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC,
             balance: amount,
+            segment: SegmentKind::Linear,
         },
     ]);
 
@@ -32,6 +34,8 @@ fn test_terminate_basic_payer_logic() {
         account_id: users.alice.valid_account_id(),
         schedule: schedule.clone(),
         vesting_schedule: Some(VestingConditions::Schedule(schedule.clone())),
+        transferable: false,
+        clawback_receiver: None,
     };
 
     // create lockup succeeds
@@ -46,16 +50,21 @@ fn test_terminate_basic_payer_logic() {
     // receiver cannot terminate
     let res = e.terminate(&users.alice, lockup_index);
     assert!(!res.is_ok());
-    assert!(format!("{:?}", res.status()).contains("Not in deposit whitelist"));
+    assert!(format!("{:?}", res.status()).contains("Not authorized to terminate the lockup"));
 
     // random user cannot terminate
     ft_storage_deposit(&e.owner, TOKEN_ID, &users.dude.account_id);
     let res = e.terminate(&users.dude, lockup_index);
     assert!(!res.is_ok());
-    assert!(format!("{:?}", res.status()).contains("Not in deposit whitelist"));
+    assert!(format!("{:?}", res.status()).contains("Not authorized to terminate the lockup"));
 
-    // non-payer deposit whitelist can terminate the lockup
-    let res: WrappedBalance = e.terminate(&e.owner, lockup_index).unwrap_json();
+    // non-payer deposit whitelist cannot terminate the lockup
+    let res = e.terminate(&e.owner, lockup_index);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Not authorized to terminate the lockup"));
+
+    // payer can terminate the lockup
+    let res: WrappedBalance = e.terminate(&users.eve, lockup_index).unwrap_json();
     assert_eq!(res.0, amount);
     let balance = e.ft_balance_of(&users.eve);
     assert_eq!(balance, amount);
@@ -66,6 +75,8 @@ fn test_terminate_basic_payer_logic() {
         account_id: users.alice.valid_account_id(),
         schedule: schedule.clone(),
         vesting_schedule: None,
+        transferable: false,
+        clawback_receiver: None,
     };
 
     // lockup without terminator creates successfuly
@@ -102,10 +113,12 @@ fn test_terminate_when_payer_doesnt_have_storage_deposit() {
         Checkpoint {
             timestamp: GENESIS_TIMESTAMP_SEC,
             balance: 0,
+            segment: SegmentKind::Linear,
         },
         Checkpoint {
             timestamp: GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC,
             balance: amount,
+            segment: SegmentKind::Linear,
         },
     ]);
 
@@ -113,6 +126,8 @@ fn test_terminate_when_payer_doesnt_have_storage_deposit() {
         account_id: users.alice.valid_account_id(),
         schedule: schedule.clone(),
         vesting_schedule: Some(VestingConditions::Schedule(schedule.clone())),
+        transferable: false,
+        clawback_receiver: None,
     };
 
     // create lockup succeeds
@@ -177,13 +192,17 @@ fn test_lockup_terminate_no_vesting_schedule() {
             Checkpoint {
                 timestamp: GENESIS_TIMESTAMP_SEC,
                 balance: 0,
+                segment: SegmentKind::Linear,
             },
             Checkpoint {
                 timestamp: GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC,
                 balance: amount,
+                segment: SegmentKind::Linear,
             },
         ]),
         vesting_schedule: Some(VestingConditions::SameAsLockupSchedule),
+        transferable: false,
+        clawback_receiver: None,
     };
 
     let balance: WrappedBalance = e
@@ -228,7 +247,7 @@ fn test_lockup_terminate_no_vesting_schedule() {
 
     // TERMINATE
     ft_storage_deposit(&users.eve, TOKEN_ID, &users.eve.account_id);
-    let res: WrappedBalance = e.terminate(&e.owner, lockup_index).unwrap_json();
+    let res: WrappedBalance = e.terminate(&users.eve, lockup_index).unwrap_json();
     assert_eq!(res.0, amount / 2);
 
     let terminator_balance = e.ft_balance_of(&users.eve);
@@ -276,6 +295,8 @@ fn test_lockup_terminate_custom_vesting_hash() {
         account_id: users.alice.valid_account_id(),
         schedule: lockup_schedule,
         vesting_schedule: Some(VestingConditions::Hash(vesting_hash)),
+        transferable: false,
+        clawback_receiver: None,
     };
 
     let balance: WrappedBalance = e
@@ -295,7 +316,7 @@ fn test_lockup_terminate_custom_vesting_hash() {
 
     // TERMINATE
     let res: WrappedBalance = e
-        .terminate_with_schedule(&e.owner, lockup_index, vesting_schedule)
+        .terminate_with_schedule(&users.eve, lockup_index, vesting_schedule)
         .unwrap_json();
     assert_eq!(res.0, amount * 3 / 4);
     let terminator_balance = e.ft_balance_of(&users.eve);
@@ -350,6 +371,8 @@ fn test_lockup_terminate_custom_vesting_invalid_hash() {
         account_id: users.alice.valid_account_id(),
         schedule: lockup_schedule,
         vesting_schedule: Some(VestingConditions::Hash(vesting_hash)),
+        transferable: false,
+        clawback_receiver: None,
     };
 
     let balance: WrappedBalance = e
@@ -372,13 +395,15 @@ fn test_lockup_terminate_custom_vesting_invalid_hash() {
         Checkpoint {
             timestamp: GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC * 2,
             balance: 0,
+            segment: SegmentKind::Linear,
         },
         Checkpoint {
             timestamp: GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC * 4,
             balance: amount,
+            segment: SegmentKind::Linear,
         },
     ]);
-    let res = e.terminate_with_schedule(&e.owner, lockup_index, fake_schedule);
+    let res = e.terminate_with_schedule(&users.eve, lockup_index, fake_schedule);
     assert!(!res.is_ok());
     assert!(format!("{:?}", res.status()).contains("The revealed schedule hash doesn't match"));
 }
@@ -402,10 +427,12 @@ fn test_lockup_terminate_custom_vesting_incompatible_vesting_schedule_by_hash()
         Checkpoint {
             timestamp: GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC * 4,
             balance: 0,
+            segment: SegmentKind::Linear,
         },
         Checkpoint {
             timestamp: GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC * 4 + 1,
             balance: amount,
+            segment: SegmentKind::Linear,
         },
     ]);
     let incompatible_vesting_hash = e.hash_schedule(&incompatible_vesting_schedule);
@@ -413,6 +440,8 @@ fn test_lockup_terminate_custom_vesting_incompatible_vesting_schedule_by_hash()
         account_id: users.alice.valid_account_id(),
         schedule: lockup_schedule,
         vesting_schedule: Some(VestingConditions::Hash(incompatible_vesting_hash)),
+        transferable: false,
+        clawback_receiver: None,
     };
 
     let balance: WrappedBalance = e
@@ -431,7 +460,7 @@ fn test_lockup_terminate_custom_vesting_incompatible_vesting_schedule_by_hash()
     assert_eq!(lockups[0].1.unclaimed_balance, 0);
 
     // TERMINATE
-    let res = e.terminate_with_schedule(&e.owner, lockup_index, incompatible_vesting_schedule);
+    let res = e.terminate_with_schedule(&users.eve, lockup_index, incompatible_vesting_schedule);
     assert!(!res.is_ok());
     assert!(format!("{:?}", res.status()).contains("The lockup schedule is ahead of"));
 }
@@ -455,6 +484,8 @@ fn test_lockup_terminate_custom_vesting_terminate_before_schedule_start() {
         account_id: users.alice.valid_account_id(),
         schedule: lockup_schedule.clone(),
         vesting_schedule: Some(VestingConditions::Schedule(vesting_schedule)),
+        transferable: false,
+        clawback_receiver: None,
     };
 
     e.set_time_sec(GENESIS_TIMESTAMP_SEC - ONE_YEAR_SEC);
@@ -542,6 +573,8 @@ fn test_lockup_terminate_custom_vesting_terminate_before_cliff() {
         account_id: users.alice.valid_account_id(),
         schedule: lockup_schedule.clone(),
         vesting_schedule: Some(VestingConditions::Schedule(vesting_schedule)),
+        transferable: false,
+        clawback_receiver: None,
     };
 
     let balance: WrappedBalance = e
@@ -561,7 +594,7 @@ fn test_lockup_terminate_custom_vesting_terminate_before_cliff() {
     assert_eq!(lockups[0].1.unclaimed_balance, 0);
 
     // TERMINATE
-    let res: WrappedBalance = e.terminate(&e.owner, lockup_index).unwrap_json();
+    let res: WrappedBalance = e.terminate(&users.eve, lockup_index).unwrap_json();
     assert_eq!(res.0, amount);
 
     let terminator_balance = e.ft_balance_of(&users.eve);
@@ -628,6 +661,8 @@ fn test_lockup_terminate_custom_vesting_before_release() {
         account_id: users.alice.valid_account_id(),
         schedule: lockup_schedule,
         vesting_schedule: Some(VestingConditions::Schedule(vesting_schedule)),
+        transferable: false,
+        clawback_receiver: None,
     };
 
     let balance: WrappedBalance = e
@@ -646,7 +681,7 @@ fn test_lockup_terminate_custom_vesting_before_release() {
     assert_eq!(lockups[0].1.unclaimed_balance, 0);
 
     // TERMINATE
-    let res: WrappedBalance = e.terminate(&e.owner, lockup_index).unwrap_json();
+    let res: WrappedBalance = e.terminate(&users.eve, lockup_index).unwrap_json();
     assert_eq!(res.0, amount * 3 / 4);
     let terminator_balance = e.ft_balance_of(&users.eve);
     assert_eq!(terminator_balance, amount * 3 / 4);
@@ -714,6 +749,8 @@ fn test_lockup_terminate_custom_vesting_during_release() {
         account_id: users.alice.valid_account_id(),
         schedule: lockup_schedule,
         vesting_schedule: Some(VestingConditions::Schedule(vesting_schedule)),
+        transferable: false,
+        clawback_receiver: None,
     };
 
     let balance: WrappedBalance = e
@@ -739,7 +776,7 @@ fn test_lockup_terminate_custom_vesting_during_release() {
     // TERMINATE, 2Y + Y / 2, 5/8 unlocked
     e.set_time_sec(GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC * 2 + ONE_YEAR_SEC / 2);
     ft_storage_deposit(&users.eve, TOKEN_ID, &users.eve.account_id);
-    let res: WrappedBalance = e.terminate(&e.owner, lockup_index).unwrap_json();
+    let res: WrappedBalance = e.terminate(&users.eve, lockup_index).unwrap_json();
     assert_eq!(res.0, amount * 3 / 8);
     let terminator_balance = e.ft_balance_of(&users.eve);
     assert_eq!(terminator_balance, amount * 3 / 8);
@@ -802,6 +839,8 @@ fn test_lockup_terminate_custom_vesting_during_lockup_cliff() {
         account_id: users.alice.valid_account_id(),
         schedule: lockup_schedule,
         vesting_schedule: Some(VestingConditions::Schedule(vesting_schedule)),
+        transferable: false,
+        clawback_receiver: None,
     };
 
     let balance: WrappedBalance = e
@@ -826,7 +865,7 @@ fn test_lockup_terminate_custom_vesting_during_lockup_cliff() {
 
     // TERMINATE, 3Y + Y / 3, 5/6 vested
     e.set_time_sec(GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC * 3 + ONE_YEAR_SEC / 3);
-    let res: WrappedBalance = e.terminate(&e.owner, lockup_index).unwrap_json();
+    let res: WrappedBalance = e.terminate(&users.eve, lockup_index).unwrap_json();
     assert_eq!(res.0, amount / 6);
     let terminator_balance = e.ft_balance_of(&users.eve);
     assert_eq!(terminator_balance, amount / 6);
@@ -889,6 +928,8 @@ fn test_lockup_terminate_custom_vesting_after_vesting_finished() {
         account_id: users.alice.valid_account_id(),
         schedule: lockup_schedule,
         vesting_schedule: Some(VestingConditions::Schedule(vesting_schedule)),
+        transferable: false,
+        clawback_receiver: None,
     };
 
     let balance: WrappedBalance = e
@@ -913,7 +954,7 @@ fn test_lockup_terminate_custom_vesting_after_vesting_finished() {
 
     // TERMINATE, 4Y, fully vested
     e.set_time_sec(GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC * 4);
-    let res: WrappedBalance = e.terminate(&e.owner, lockup_index).unwrap_json();
+    let res: WrappedBalance = e.terminate(&users.eve, lockup_index).unwrap_json();
     assert_eq!(res.0, 0);
     let terminator_balance = e.ft_balance_of(&users.eve);
     assert_eq!(terminator_balance, 0);
@@ -949,3 +990,161 @@ fn test_lockup_terminate_custom_vesting_after_vesting_finished() {
     assert_eq!(lockup.claimed_balance, amount);
     assert_eq!(lockup.unclaimed_balance, 0);
 }
+
+#[test]
+fn test_terminate_by_delegate() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+    let amount = d(60000, TOKEN_DECIMALS);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+
+    let res = e.add_to_deposit_whitelist(&e.owner, &users.eve.valid_account_id());
+    assert!(res.is_ok());
+    ft_storage_deposit(&e.owner, TOKEN_ID, &users.eve.account_id);
+    e.ft_transfer(&e.owner, amount, &users.eve);
+
+    let schedule = Schedule(vec![
+        Checkpoint {
+            timestamp: GENESIS_TIMESTAMP_SEC,
+            balance: 0,
+            segment: SegmentKind::Linear,
+        },
+        Checkpoint {
+            timestamp: GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC,
+            balance: amount,
+            segment: SegmentKind::Linear,
+        },
+    ]);
+
+    let lockup_create = LockupCreate {
+        account_id: users.alice.valid_account_id(),
+        schedule: schedule.clone(),
+        vesting_schedule: Some(VestingConditions::Schedule(schedule.clone())),
+        transferable: false,
+        clawback_receiver: None,
+    };
+
+    let balance: WrappedBalance = e
+        .add_lockup(&users.eve, amount, &lockup_create)
+        .unwrap_json();
+    assert_eq!(balance.0, amount);
+    let lockups = e.get_account_lockups(&users.alice);
+    assert_eq!(lockups.len(), 1);
+    let lockup_index = lockups[0].0;
+
+    // only the payer can terminate by default
+    assert_eq!(
+        e.get_lockup_terminators(lockup_index),
+        vec![users.eve.account_id.clone()]
+    );
+
+    // not delegated account cannot terminate
+    let res = e.terminate(&users.bob, lockup_index);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Not authorized to terminate the lockup"));
+
+    // delegating termination
+    let res = e.add_termination_delegates(
+        &users.eve,
+        vec![
+            users.bob.valid_account_id(),
+            users.charlie.valid_account_id(),
+        ],
+    );
+    assert!(res.is_ok());
+    assert_eq!(
+        e.get_termination_delegates(&users.eve),
+        vec![
+            users.bob.account_id.clone(),
+            users.charlie.account_id.clone()
+        ]
+    );
+    assert_eq!(
+        e.get_lockup_terminators(lockup_index),
+        vec![
+            users.eve.account_id.clone(),
+            users.bob.account_id.clone(),
+            users.charlie.account_id.clone()
+        ]
+    );
+
+    // revoking delegation
+    let res = e.remove_termination_delegates(&users.eve, vec![users.charlie.valid_account_id()]);
+    assert!(res.is_ok());
+    assert_eq!(
+        e.get_termination_delegates(&users.eve),
+        vec![users.bob.account_id.clone()]
+    );
+    let res = e.terminate(&users.charlie, lockup_index);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Not authorized to terminate the lockup"));
+
+    // delegate can terminate, unvested balance still goes to the payer
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC / 4);
+    let res: WrappedBalance = e.terminate(&users.bob, lockup_index).unwrap_json();
+    assert_eq!(res.0, amount * 3 / 4);
+    assert_eq!(e.ft_balance_of(&users.eve), amount * 3 / 4);
+    assert_eq!(e.ft_balance_of(&users.bob), 0);
+
+    // lockup is not terminatable anymore
+    assert!(e.get_lockup_terminators(lockup_index).is_empty());
+}
+
+#[test]
+fn test_lockup_terminate_custom_vesting_salted_hash() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+    let amount = d(60000, TOKEN_DECIMALS);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+
+    let res = e.add_to_deposit_whitelist(&e.owner, &users.eve.valid_account_id());
+    assert!(res.is_ok());
+    ft_storage_deposit(&e.owner, TOKEN_ID, &users.eve.account_id);
+    e.ft_transfer(&e.owner, amount, &users.eve);
+
+    let salt = b"a secret salt of the payer";
+    let (lockup_schedule, vesting_schedule) = lockup_vesting_schedule(amount);
+    let vesting_hash = e.hash_schedule_with_salt(&vesting_schedule, salt);
+    assert_ne!(vesting_hash, e.hash_schedule(&vesting_schedule));
+    let lockup_create = LockupCreate {
+        account_id: users.alice.valid_account_id(),
+        schedule: lockup_schedule,
+        vesting_schedule: Some(VestingConditions::HashWithSalt(vesting_hash)),
+        transferable: false,
+        clawback_receiver: None,
+    };
+
+    let balance: WrappedBalance = e
+        .add_lockup(&users.eve, amount, &lockup_create)
+        .unwrap_json();
+    assert_eq!(balance.0, amount);
+    let lockup_index = e.get_account_lockups(&users.alice)[0].0;
+
+    // 1Y, 1 / 4 vested, 0 unlocked
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC);
+
+    // the salt is required
+    let res = e.terminate_with_schedule(&users.eve, lockup_index, vesting_schedule.clone());
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Revealed salt required for the termination"));
+
+    // a wrong salt doesn't match
+    let res = e.terminate_with_salted_schedule(
+        &users.eve,
+        lockup_index,
+        vesting_schedule.clone(),
+        b"a wrong salt",
+    );
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("The revealed schedule hash doesn't match"));
+
+    // TERMINATE
+    let res: WrappedBalance = e
+        .terminate_with_salted_schedule(&users.eve, lockup_index, vesting_schedule, salt)
+        .unwrap_json();
+    assert_eq!(res.0, amount * 3 / 4);
+    assert_eq!(e.ft_balance_of(&users.eve), amount * 3 / 4);
+
+    let lockups = e.get_account_lockups(&users.alice);
+    assert_eq!(lockups[0].1.total_balance, amount / 4);
+}
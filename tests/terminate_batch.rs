@@ -0,0 +1,125 @@
+mod setup;
+
+use crate::setup::*;
+
+fn create_terminatable_lockup(
+    e: &Env,
+    account: &UserAccount,
+    amount: Balance,
+    clawback_receiver: Option<ClawbackReceiver>,
+) -> LockupIndex {
+    let (lockup_schedule, vesting_schedule) = lockup_vesting_schedule(amount);
+    let lockup_create = LockupCreate {
+        account_id: account.valid_account_id(),
+        schedule: lockup_schedule,
+        vesting_schedule: Some(VestingConditions::Schedule(vesting_schedule)),
+        transferable: false,
+        clawback_receiver,
+    };
+    let balance: WrappedBalance = e.add_lockup(&e.owner, amount, &lockup_create).unwrap_json();
+    assert_eq!(balance.0, amount);
+    e.get_account_lockups(account)[0].0
+}
+
+#[test]
+fn test_terminate_batch() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+    let amount = d(60000, TOKEN_DECIMALS);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+    let lockup_alice = create_terminatable_lockup(&e, &users.alice, amount, None);
+    let lockup_bob = create_terminatable_lockup(&e, &users.bob, amount, None);
+    let lockup_charlie = create_terminatable_lockup(
+        &e,
+        &users.charlie,
+        amount,
+        Some(ClawbackReceiver {
+            account_id: users.dude.valid_account_id(),
+            msg: None,
+        }),
+    );
+    let lockup_unlocked: WrappedBalance = e
+        .add_lockup(
+            &e.owner,
+            amount,
+            &LockupCreate::new_unlocked(users.eve.valid_account_id(), amount),
+        )
+        .unwrap_json();
+    assert_eq!(lockup_unlocked.0, amount);
+    let lockup_eve = e.get_account_lockups(&users.eve)[0].0;
+
+    // the whole batch fails if any lockup can't be terminated
+    let res = e.terminate_batch(&e.owner, &[lockup_alice, lockup_eve]);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("No termination config"));
+    let res = e.terminate_batch(&users.alice, &[lockup_alice]);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Not authorized to terminate the lockup"));
+    assert_eq!(e.get_lockup(lockup_alice).total_balance, amount);
+
+    // 1/4 is vested after the cliff
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC);
+    let balance_before = e.ft_balance_of(&e.owner);
+    let res: WrappedBalance = e
+        .terminate_batch(&e.owner, &[lockup_alice, lockup_bob, lockup_charlie])
+        .unwrap_json();
+    assert_eq!(res.0, amount * 9 / 4);
+    assert_eq!(e.ft_balance_of(&e.owner), balance_before + amount * 3 / 2);
+    assert_eq!(e.ft_balance_of(&users.dude), amount * 3 / 4);
+    for lockup_index in [lockup_alice, lockup_bob, lockup_charlie] {
+        let lockup = e.get_lockup(lockup_index);
+        assert_eq!(lockup.total_balance, amount / 4);
+        assert!(lockup.termination_config.is_none());
+    }
+}
+
+#[test]
+fn test_terminate_batch_with_salted_schedule() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+    let amount = d(60000, TOKEN_DECIMALS);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+    let lockup_alice = create_terminatable_lockup(&e, &users.alice, amount, None);
+
+    let salt = b"a secret salt of the payer";
+    let (lockup_schedule, vesting_schedule) = lockup_vesting_schedule(amount);
+    let lockup_create = LockupCreate {
+        account_id: users.bob.valid_account_id(),
+        schedule: lockup_schedule,
+        vesting_schedule: Some(VestingConditions::HashWithSalt(
+            e.hash_schedule_with_salt(&vesting_schedule, salt),
+        )),
+        transferable: false,
+        clawback_receiver: None,
+    };
+    let balance: WrappedBalance = e.add_lockup(&e.owner, amount, &lockup_create).unwrap_json();
+    assert_eq!(balance.0, amount);
+    let lockup_bob = e.get_account_lockups(&users.bob)[0].0;
+
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC);
+    let res = e.terminate_batch_with_schedules(
+        &e.owner,
+        vec![
+            (lockup_alice, None, None),
+            (lockup_bob, Some(vesting_schedule.clone()), None),
+        ],
+    );
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Revealed salt required for the termination"));
+
+    let balance_before = e.ft_balance_of(&e.owner);
+    let res: WrappedBalance = e
+        .terminate_batch_with_schedules(
+            &e.owner,
+            vec![
+                (lockup_alice, None, None),
+                (lockup_bob, Some(vesting_schedule), Some(salt)),
+            ],
+        )
+        .unwrap_json();
+    assert_eq!(res.0, amount * 3 / 2);
+    assert_eq!(e.ft_balance_of(&e.owner), balance_before + amount * 3 / 2);
+    for lockup_index in [lockup_alice, lockup_bob] {
+        assert_eq!(e.get_lockup(lockup_index).total_balance, amount / 4);
+    }
+}
@@ -21,6 +21,8 @@ fn test_lockup_terminate_with_timestamp_in_future() {
         account_id: users.alice.valid_account_id(),
         schedule: lockup_schedule,
         vesting_schedule: Some(VestingConditions::Schedule(vesting_schedule)),
+        transferable: false,
+        clawback_receiver: None,
     };
 
     let balance: WrappedBalance = e
@@ -36,7 +38,7 @@ fn test_lockup_terminate_with_timestamp_in_future() {
 
     // try TERMINATE with past timestamp
     let res = e.terminate_with_timestamp(
-        &e.owner,
+        &users.eve,
         lockup_index,
         GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC - 1 - 1,
     );
@@ -46,7 +48,7 @@ fn test_lockup_terminate_with_timestamp_in_future() {
     // TERMINATE with future timestamp
     let res: WrappedBalance = e
         .terminate_with_timestamp(
-            &e.owner,
+            &users.eve,
             lockup_index,
             GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC * 2,
         )
@@ -105,10 +107,12 @@ fn test_lockup_terminate_with_timestamp_in_future_no_storage_deposit() {
         Checkpoint {
             timestamp: GENESIS_TIMESTAMP_SEC,
             balance: 0,
+            segment: SegmentKind::Linear,
         },
         Checkpoint {
             timestamp: GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC,
             balance: amount,
+            segment: SegmentKind::Linear,
         },
     ]);
 
@@ -116,6 +120,8 @@ fn test_lockup_terminate_with_timestamp_in_future_no_storage_deposit() {
         account_id: users.alice.valid_account_id(),
         schedule: schedule.clone(),
         vesting_schedule: Some(VestingConditions::Schedule(schedule.clone())),
+        transferable: false,
+        clawback_receiver: None,
     };
 
     // create lockup succeeds
@@ -0,0 +1,259 @@
+mod setup;
+
+use crate::setup::*;
+
+fn create_terminatable_lockup(e: &Env, users: &Users, amount: Balance) -> LockupIndex {
+    let (lockup_schedule, vesting_schedule) = lockup_vesting_schedule(amount);
+    let lockup_create = LockupCreate {
+        account_id: users.alice.valid_account_id(),
+        schedule: lockup_schedule,
+        vesting_schedule: Some(VestingConditions::Schedule(vesting_schedule)),
+        transferable: false,
+        clawback_receiver: None,
+    };
+    let balance: WrappedBalance = e.add_lockup(&e.owner, amount, &lockup_create).unwrap_json();
+    assert_eq!(balance.0, amount);
+    e.get_account_lockups(&users.alice)[0].0
+}
+
+#[test]
+fn test_termination_approvals() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+    let amount = d(60000, TOKEN_DECIMALS);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+    let lockup_index = create_terminatable_lockup(&e, &users, amount);
+
+    let config = TerminationApprovalConfig {
+        approvers: vec![
+            users.bob.valid_account_id(),
+            users.charlie.valid_account_id(),
+            users.dude.valid_account_id(),
+        ],
+        threshold: 2,
+        request_duration_sec: ONE_DAY_SEC,
+    };
+    let res = e.set_termination_approval_config(&users.bob, Some(config.clone()));
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Not an admin"));
+    let res = e.set_termination_approval_config(
+        &e.owner,
+        Some(TerminationApprovalConfig {
+            threshold: 4,
+            ..config.clone()
+        }),
+    );
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status())
+        .contains("The threshold is greater than the number of approvers"));
+    let res = e.set_termination_approval_config(&e.owner, Some(config));
+    assert!(res.is_ok());
+
+    // the direct termination is disabled
+    let res = e.terminate(&e.owner, lockup_index);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("The termination requires approvals"));
+
+    let res = e.request_termination(&users.bob, lockup_index);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Not authorized to terminate the lockup"));
+
+    let request_id: TerminationRequestIndex =
+        e.request_termination(&e.owner, lockup_index).unwrap_json();
+    assert_eq!(
+        e.get_termination_requests(),
+        vec![(
+            request_id,
+            TerminationRequest {
+                lockup_index,
+                requester_id: e.owner.account_id(),
+                hashed_schedule: None,
+                termination_timestamp: None,
+                salt: None,
+                approvals: vec![],
+                expiration_timestamp: GENESIS_TIMESTAMP_SEC + ONE_DAY_SEC,
+            }
+        )]
+    );
+
+    let res = e.approve_termination(&users.alice, request_id);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Not a termination approver"));
+
+    let res: WrappedBalance = e.approve_termination(&users.bob, request_id).unwrap_json();
+    assert_eq!(res.0, 0);
+    let res = e.approve_termination(&users.bob, request_id);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Already approved"));
+    assert_eq!(
+        e.get_termination_requests()[0].1.approvals,
+        vec![users.bob.account_id()]
+    );
+    assert_eq!(
+        e.get_account_lockups(&users.alice)[0].1.total_balance,
+        amount
+    );
+
+    // the approvals of the removed approvers don't count
+    let res = e.set_termination_approval_config(
+        &e.owner,
+        Some(TerminationApprovalConfig {
+            approvers: vec![
+                users.charlie.valid_account_id(),
+                users.dude.valid_account_id(),
+            ],
+            threshold: 2,
+            request_duration_sec: ONE_DAY_SEC,
+        }),
+    );
+    assert!(res.is_ok());
+    let res: WrappedBalance = e
+        .approve_termination(&users.charlie, request_id)
+        .unwrap_json();
+    assert_eq!(res.0, 0);
+    assert_eq!(
+        e.get_termination_requests()[0].1.approvals,
+        vec![users.bob.account_id(), users.charlie.account_id()]
+    );
+
+    // the approval that reaches the threshold executes the termination
+    let balance_before = e.ft_balance_of(&e.owner);
+    let res: WrappedBalance = e.approve_termination(&users.dude, request_id).unwrap_json();
+    assert_eq!(res.0, amount);
+    assert_eq!(e.ft_balance_of(&e.owner), balance_before + amount);
+    assert!(e.get_termination_requests().is_empty());
+    assert!(e.get_lockup(lockup_index).termination_config.is_none());
+}
+
+#[test]
+fn test_termination_request_expiration() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+    let amount = d(60000, TOKEN_DECIMALS);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+    let lockup_index = create_terminatable_lockup(&e, &users, amount);
+
+    let res = e.set_termination_approval_config(
+        &e.owner,
+        Some(TerminationApprovalConfig {
+            approvers: vec![users.bob.valid_account_id()],
+            threshold: 1,
+            request_duration_sec: ONE_DAY_SEC,
+        }),
+    );
+    assert!(res.is_ok());
+
+    // the request can be cancelled by the terminators before it expires
+    let request_id: TerminationRequestIndex =
+        e.request_termination(&e.owner, lockup_index).unwrap_json();
+    let res = e.cancel_termination_request(&users.bob, request_id);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Not authorized to terminate the lockup"));
+    let res = e.cancel_termination_request(&e.owner, request_id);
+    assert!(res.is_ok());
+    assert!(e.get_termination_requests().is_empty());
+
+    // the expired request can't be approved, but can be removed by anyone
+    let request_id: TerminationRequestIndex =
+        e.request_termination(&e.owner, lockup_index).unwrap_json();
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC + ONE_DAY_SEC);
+    let res = e.approve_termination(&users.bob, request_id);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("The termination request has expired"));
+    let res = e.cancel_termination_request(&users.bob, request_id);
+    assert!(res.is_ok());
+    assert!(e.get_termination_requests().is_empty());
+    assert!(e.get_lockup(lockup_index).termination_config.is_some());
+
+    // the request of a terminated lockup can be removed by anyone
+    let request_id: TerminationRequestIndex =
+        e.request_termination(&e.owner, lockup_index).unwrap_json();
+    let request_id_2: TerminationRequestIndex =
+        e.request_termination(&e.owner, lockup_index).unwrap_json();
+    let res = e.approve_termination(&users.bob, request_id);
+    assert!(res.is_ok());
+    assert!(e.get_lockup(lockup_index).termination_config.is_none());
+    let res = e.cancel_termination_request(&users.bob, request_id_2);
+    assert!(res.is_ok());
+    assert!(e.get_termination_requests().is_empty());
+}
+
+#[test]
+fn test_termination_request_timestamp() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+    let amount = d(60000, TOKEN_DECIMALS);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+    let lockup_index = create_terminatable_lockup(&e, &users, amount);
+
+    let res = e.set_termination_approval_config(
+        &e.owner,
+        Some(TerminationApprovalConfig {
+            approvers: vec![users.bob.valid_account_id()],
+            threshold: 1,
+            request_duration_sec: ONE_DAY_SEC,
+        }),
+    );
+    assert!(res.is_ok());
+
+    // a past termination timestamp is rejected by the request
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC + 1);
+    let res = e.request_termination_at(&e.owner, lockup_index, Some(GENESIS_TIMESTAMP_SEC));
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("expected termination_timestamp >= now"));
+
+    // the timestamp passed while collecting the approvals, the termination happens now
+    let request_id: TerminationRequestIndex = e
+        .request_termination_at(
+            &e.owner,
+            lockup_index,
+            Some(GENESIS_TIMESTAMP_SEC + ONE_DAY_SEC / 2),
+        )
+        .unwrap_json();
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC + ONE_DAY_SEC * 3 / 4);
+    let balance_before = e.ft_balance_of(&e.owner);
+    let res: WrappedBalance = e.approve_termination(&users.bob, request_id).unwrap_json();
+    assert_eq!(res.0, amount);
+    assert_eq!(e.ft_balance_of(&e.owner), balance_before + amount);
+    assert!(e.get_termination_requests().is_empty());
+}
+
+#[test]
+fn test_termination_approval_config_delay() {
+    let e = Env::deploy(None, &FT_LOCKUP_WASM_BYTES);
+    let users = Users::init(&e);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+
+    let config = TerminationApprovalConfig {
+        approvers: vec![users.bob.valid_account_id()],
+        threshold: 1,
+        request_duration_sec: ONE_DAY_SEC,
+    };
+    let res = e.set_termination_approval_config(
+        &e.owner,
+        Some(TerminationApprovalConfig {
+            threshold: 2,
+            ..config.clone()
+        }),
+    );
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status())
+        .contains("The threshold is greater than the number of approvers"));
+
+    // the config change is pending until the admin change delay passes
+    let proposal_id: AdminProposalIndex = e
+        .set_termination_approval_config(&e.owner, Some(config.clone()))
+        .unwrap_json();
+    assert!(e.get_termination_approval_config().is_none());
+    assert_eq!(
+        e.get_admin_proposals()[0].1.change,
+        AdminChange::SetTerminationApprovalConfig {
+            config: Some(config.clone())
+        }
+    );
+
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC + DEFAULT_ADMIN_CHANGE_DELAY_SEC);
+    let res = e.accept_admin_change(&e.owner, proposal_id);
+    assert!(res.is_ok());
+    assert_eq!(e.get_termination_approval_config(), Some(config));
+}
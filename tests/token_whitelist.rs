@@ -0,0 +1,110 @@
+mod setup;
+
+use crate::setup::*;
+
+const TOKEN2_ID: &str = "token2.near";
+
+#[test]
+fn test_token_whitelist() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+    let amount = d(10000, TOKEN_DECIMALS);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+    let token2 = deploy_token(&e.near, &e.owner, TOKEN2_ID);
+    ft_storage_deposit(&e.owner, TOKEN2_ID, FT_LOCKUP_ID);
+
+    // the primary token is whitelisted by default
+    assert_eq!(e.get_token_whitelist(), vec![TOKEN_ID.to_string()]);
+
+    let lockup_create = LockupCreate::new_unlocked(users.alice.valid_account_id(), amount);
+    let msg = serde_json::to_string(&lockup_create).unwrap();
+
+    // not whitelisted token is refunded
+    let res: WrappedBalance = e
+        .ft_transfer_call_token(&token2, &e.owner, amount, &msg)
+        .unwrap_json();
+    assert_eq!(res.0, 0);
+    assert!(e.get_account_lockups(&users.alice).is_empty());
+
+    // only depositors can manage the token whitelist
+    let res = e.add_to_token_whitelist(&users.alice, &token2);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Not an admin"));
+
+    // the native token id is reserved for the native lockups
+    assert_eq!(e.near.account_id(), NATIVE_TOKEN_ID);
+    let res = e.add_to_token_whitelist(&e.owner, &e.near);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("The native token can't be whitelisted"));
+
+    let res = e.add_to_token_whitelist(&e.owner, &token2);
+    assert!(res.is_ok());
+    assert_eq!(
+        e.get_token_whitelist(),
+        vec![TOKEN_ID.to_string(), TOKEN2_ID.to_string()]
+    );
+
+    let res: WrappedBalance = e
+        .ft_transfer_call_token(&token2, &e.owner, amount, &msg)
+        .unwrap_json();
+    assert_eq!(res.0, amount);
+    let lockups = e.get_account_lockups(&users.alice);
+    assert_eq!(lockups.len(), 1);
+    assert_eq!(lockups[0].1.token_account_id, TOKEN2_ID.to_string());
+
+    let res = e.remove_from_token_whitelist(&e.owner, &token2);
+    assert!(res.is_ok());
+    assert_eq!(e.get_token_whitelist(), vec![TOKEN_ID.to_string()]);
+
+    // the existing lockup can still be claimed
+    ft_storage_deposit(&users.alice, TOKEN2_ID, &users.alice.account_id);
+    let res: WrappedBalance = e.claim(&users.alice).unwrap_json();
+    assert_eq!(res.0, amount);
+    assert_eq!(e.ft_balance_of_token(&token2, &users.alice), amount);
+}
+
+#[test]
+fn test_claim_multiple_tokens() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+    let amount = d(10000, TOKEN_DECIMALS);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+    let token2 = deploy_token(&e.near, &e.owner, TOKEN2_ID);
+    ft_storage_deposit(&e.owner, TOKEN2_ID, FT_LOCKUP_ID);
+    let res = e.add_to_token_whitelist(&e.owner, &token2);
+    assert!(res.is_ok());
+
+    let lockup_create = LockupCreate::new_unlocked(users.alice.valid_account_id(), amount);
+    let balance: WrappedBalance = e.add_lockup(&e.owner, amount, &lockup_create).unwrap_json();
+    assert_eq!(balance.0, amount);
+    let lockup_create = LockupCreate::new_unlocked(users.alice.valid_account_id(), amount * 2);
+    let msg = serde_json::to_string(&lockup_create).unwrap();
+    let balance: WrappedBalance = e
+        .ft_transfer_call_token(&token2, &e.owner, amount * 2, &msg)
+        .unwrap_json();
+    assert_eq!(balance.0, amount * 2);
+
+    // different tokens can't be merged
+    let res = e.merge_lockups(&users.alice, vec![0, 1]);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Cannot merge lockups of different tokens"));
+
+    // no storage deposit for the second token, so only its transfer fails
+    ft_storage_deposit(&users.alice, TOKEN_ID, &users.alice.account_id);
+    let res: WrappedBalance = users
+        .alice
+        .function_call(e.contract.contract.claim(None, None, None), MAX_GAS, 0)
+        .unwrap_json();
+    assert_eq!(res.0, amount);
+    assert_eq!(e.ft_balance_of(&users.alice), amount);
+    let lockups = e.get_account_lockups(&users.alice);
+    assert_eq!(lockups.len(), 1);
+    assert_eq!(lockups[0].1.token_account_id, TOKEN2_ID.to_string());
+    assert_eq!(lockups[0].1.unclaimed_balance, amount * 2);
+
+    ft_storage_deposit(&users.alice, TOKEN2_ID, &users.alice.account_id);
+    let res: WrappedBalance = e.claim(&users.alice).unwrap_json();
+    assert_eq!(res.0, amount * 2);
+    assert_eq!(e.ft_balance_of_token(&token2, &users.alice), amount * 2);
+    assert!(e.get_account_lockups(&users.alice).is_empty());
+}
@@ -0,0 +1,451 @@
+mod setup;
+
+use crate::setup::*;
+
+#[test]
+fn test_transfer_lockup() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+    let amount = d(60000, TOKEN_DECIMALS);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+
+    let schedule = Schedule(vec![
+        Checkpoint {
+            timestamp: GENESIS_TIMESTAMP_SEC,
+            balance: 0,
+            segment: SegmentKind::Linear,
+        },
+        Checkpoint {
+            timestamp: GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC,
+            balance: amount,
+            segment: SegmentKind::Linear,
+        },
+    ]);
+
+    let lockup_create = LockupCreate {
+        account_id: users.alice.valid_account_id(),
+        schedule: schedule.clone(),
+        vesting_schedule: None,
+        transferable: true,
+        clawback_receiver: None,
+    };
+    let balance: WrappedBalance = e.add_lockup(&e.owner, amount, &lockup_create).unwrap_json();
+    assert_eq!(balance.0, amount);
+    let lockups = e.get_account_lockups(&users.alice);
+    assert_eq!(lockups.len(), 1);
+    let lockup_index = lockups[0].0;
+    assert!(lockups[0].1.transferable);
+
+    // claim half
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC / 2);
+    ft_storage_deposit(&users.alice, TOKEN_ID, &users.alice.account_id);
+    let res: WrappedBalance = e.claim(&users.alice).unwrap_json();
+    assert_eq!(res.0, amount / 2);
+
+    // only the owner can transfer
+    let res = e.transfer_lockup(&users.bob, lockup_index, &users.bob.valid_account_id());
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("lockup not found for account"));
+
+    // transfer to bob
+    let res = e.transfer_lockup(&users.alice, lockup_index, &users.bob.valid_account_id());
+    assert!(res.is_ok());
+    assert!(e.get_account_lockups(&users.alice).is_empty());
+    let lockups = e.get_account_lockups(&users.bob);
+    assert_eq!(lockups.len(), 1);
+    assert_eq!(lockups[0].0, lockup_index);
+    assert_eq!(lockups[0].1.account_id, users.bob.valid_account_id());
+    assert_eq!(lockups[0].1.claimed_balance, amount / 2);
+    assert_eq!(lockups[0].1.unclaimed_balance, 0);
+
+    // alice cannot claim anymore, bob claims the rest
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC);
+    let res: WrappedBalance = e.claim(&users.alice).unwrap_json();
+    assert_eq!(res.0, 0);
+    ft_storage_deposit(&users.bob, TOKEN_ID, &users.bob.account_id);
+    let res: WrappedBalance = e.claim(&users.bob).unwrap_json();
+    assert_eq!(res.0, amount / 2);
+    assert_eq!(e.ft_balance_of(&users.alice), amount / 2);
+    assert_eq!(e.ft_balance_of(&users.bob), amount / 2);
+    assert!(e.get_account_lockups(&users.bob).is_empty());
+}
+
+#[test]
+fn test_transfer_lockup_with_claim_in_flight() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+    let amount = d(60000, TOKEN_DECIMALS);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+
+    let mut lockup_create = LockupCreate::new_unlocked(users.alice.valid_account_id(), amount);
+    lockup_create.transferable = true;
+    let balance: WrappedBalance = e.add_lockup(&e.owner, amount, &lockup_create).unwrap_json();
+    assert_eq!(balance.0, amount);
+    let lockups = e.get_account_lockups(&users.alice);
+    assert_eq!(lockups.len(), 1);
+    let lockup_index = lockups[0].0;
+
+    // alice is not registered with the token, so the claim fails,
+    // but the lockup is transferred to bob before the callback refunds it
+    let (_, available) = e.get_storage_balance(&e.owner).unwrap();
+    e.call_in_one_block(
+        &users.alice,
+        vec![
+            (e.contract.contract.claim(None, None, None), CLAIM_GAS, 0),
+            (
+                e.contract
+                    .contract
+                    .transfer_lockup(lockup_index, users.bob.valid_account_id()),
+                DEFAULT_GAS,
+                1,
+            ),
+        ],
+    );
+    assert_eq!(e.ft_balance_of(&users.alice), 0);
+
+    // bob's lockup stays claimed, alice gets the refund as a new lockup
+    let lockups = e.get_account_lockups(&users.bob);
+    assert_eq!(lockups.len(), 1);
+    assert_eq!(lockups[0].0, lockup_index);
+    assert_eq!(lockups[0].1.claimed_balance, amount);
+    assert_eq!(lockups[0].1.unclaimed_balance, 0);
+
+    let lockups = e.get_account_lockups(&users.alice);
+    assert_eq!(lockups.len(), 1);
+    assert_ne!(lockups[0].0, lockup_index);
+    assert_eq!(lockups[0].1.claimed_balance, 0);
+    assert_eq!(lockups[0].1.unclaimed_balance, amount);
+    // the payer of the claimed lockup pays for the refund lockup
+    assert!(e.get_storage_balance(&e.owner).unwrap().1 < available);
+
+    ft_storage_deposit(&users.alice, TOKEN_ID, &users.alice.account_id);
+    let res: WrappedBalance = e.claim(&users.alice).unwrap_json();
+    assert_eq!(res.0, amount);
+    assert_eq!(e.ft_balance_of(&users.alice), amount);
+    ft_storage_deposit(&users.bob, TOKEN_ID, &users.bob.account_id);
+    let res: WrappedBalance = e.claim(&users.bob).unwrap_json();
+    assert_eq!(res.0, 0);
+}
+
+#[test]
+fn test_transfer_lockup_not_transferable() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+    let amount = d(1, TOKEN_DECIMALS);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+
+    let lockup_create = LockupCreate::new_unlocked(users.alice.valid_account_id(), amount);
+    let balance: WrappedBalance = e.add_lockup(&e.owner, amount, &lockup_create).unwrap_json();
+    assert_eq!(balance.0, amount);
+    let lockups = e.get_account_lockups(&users.alice);
+    assert_eq!(lockups.len(), 1);
+    let lockup_index = lockups[0].0;
+    assert!(!lockups[0].1.transferable);
+
+    let res = e.transfer_lockup(&users.alice, lockup_index, &users.bob.valid_account_id());
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("The lockup is not transferable"));
+    assert_eq!(e.get_account_lockups(&users.alice).len(), 1);
+    assert!(e.get_account_lockups(&users.bob).is_empty());
+}
+
+#[test]
+fn test_split_lockup() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+    let amount = d(60000, TOKEN_DECIMALS);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+
+    let res = e.add_to_deposit_whitelist(&e.owner, &users.eve.valid_account_id());
+    assert!(res.is_ok());
+    ft_storage_deposit(&e.owner, TOKEN_ID, &users.eve.account_id);
+    e.ft_transfer(&e.owner, amount, &users.eve);
+
+    let (lockup_schedule, vesting_schedule) = lockup_vesting_schedule(amount);
+    let lockup_create = LockupCreate {
+        account_id: users.alice.valid_account_id(),
+        schedule: lockup_schedule,
+        vesting_schedule: Some(VestingConditions::Schedule(vesting_schedule)),
+        transferable: true,
+        clawback_receiver: None,
+    };
+    let balance: WrappedBalance = e
+        .add_lockup(&users.eve, amount, &lockup_create)
+        .unwrap_json();
+    assert_eq!(balance.0, amount);
+    let lockup_index = e.get_account_lockups(&users.alice)[0].0;
+
+    // 3Y, 3 / 8 unlocked
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC * 3);
+    ft_storage_deposit(&users.alice, TOKEN_ID, &users.alice.account_id);
+    let res: WrappedBalance = e.claim(&users.alice).unwrap_json();
+    assert_eq!(res.0, amount * 3 / 8);
+
+    // amount must be less than the total balance
+    let res = e.split_lockup(
+        &users.alice,
+        lockup_index,
+        amount,
+        &users.bob.valid_account_id(),
+    );
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status())
+        .contains("expected amount to be positive and less than the lockup total balance"));
+
+    // split 1 / 4 to bob
+    let res = e.split_lockup(
+        &users.alice,
+        lockup_index,
+        amount / 4,
+        &users.bob.valid_account_id(),
+    );
+    let new_lockup_index: LockupIndex = res.unwrap_json();
+
+    let lockups = e.get_account_lockups(&users.alice);
+    assert_eq!(lockups.len(), 1);
+    assert_eq!(lockups[0].1.total_balance, amount * 3 / 4);
+    assert_eq!(lockups[0].1.claimed_balance, amount * 9 / 32);
+    assert_eq!(lockups[0].1.unclaimed_balance, 0);
+
+    let lockups = e.get_account_lockups(&users.bob);
+    assert_eq!(lockups.len(), 1);
+    assert_eq!(lockups[0].0, new_lockup_index);
+    let lockup = &lockups[0].1;
+    assert_eq!(lockup.total_balance, amount / 4);
+    assert_eq!(lockup.claimed_balance, amount * 3 / 32);
+    assert_eq!(lockup.unclaimed_balance, 0);
+    assert_eq!(lockup.schedule.0[2].balance, amount * 3 / 16);
+    let termination_config = lockup.termination_config.as_ref().unwrap();
+    assert_eq!(
+        termination_config.beneficiary_id,
+        users.eve.valid_account_id()
+    );
+    assert_eq!(
+        termination_config.vesting_schedule,
+        VestingConditions::Schedule(Schedule(vec![
+            Checkpoint {
+                timestamp: GENESIS_TIMESTAMP_SEC,
+                balance: 0,
+                segment: SegmentKind::Linear,
+            },
+            Checkpoint {
+                timestamp: GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC - 1,
+                balance: 0,
+                segment: SegmentKind::Linear,
+            },
+            Checkpoint {
+                timestamp: GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC,
+                balance: amount / 16,
+                segment: SegmentKind::Linear,
+            },
+            Checkpoint {
+                timestamp: GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC * 4,
+                balance: amount / 4,
+                segment: SegmentKind::Linear,
+            },
+        ]))
+    );
+
+    // the payer can terminate the split lockup
+    let res: WrappedBalance = e.terminate(&users.eve, new_lockup_index).unwrap_json();
+    assert_eq!(res.0, amount / 16);
+
+    // claiming the rest
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC * 4 + 1);
+    let res: WrappedBalance = e.claim(&users.alice).unwrap_json();
+    assert_eq!(res.0, amount * 15 / 32);
+    ft_storage_deposit(&users.bob, TOKEN_ID, &users.bob.account_id);
+    let res: WrappedBalance = e.claim(&users.bob).unwrap_json();
+    assert_eq!(res.0, amount * 3 / 32);
+    assert_eq!(e.ft_balance_of(&users.alice), amount * 27 / 32);
+    assert_eq!(e.ft_balance_of(&users.bob), amount * 3 / 32);
+}
+
+#[test]
+fn test_split_lockup_with_claim_in_flight() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+    let amount = d(60000, TOKEN_DECIMALS);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+
+    let mut lockup_create = LockupCreate::new_unlocked(users.alice.valid_account_id(), amount);
+    lockup_create.transferable = true;
+    let balance: WrappedBalance = e.add_lockup(&e.owner, amount, &lockup_create).unwrap_json();
+    assert_eq!(balance.0, amount);
+    let lockup_index = e.get_account_lockups(&users.alice)[0].0;
+
+    // the failing claim is refunded after the claimed balance was split with bob
+    e.call_in_one_block(
+        &users.alice,
+        vec![
+            (e.contract.contract.claim(None, None, None), CLAIM_GAS, 0),
+            (
+                e.contract.contract.split_lockup(
+                    lockup_index,
+                    (amount / 2).into(),
+                    users.bob.valid_account_id(),
+                ),
+                DEFAULT_GAS,
+                1,
+            ),
+        ],
+    );
+    assert_eq!(e.ft_balance_of(&users.alice), 0);
+
+    let lockups = e.get_account_lockups(&users.bob);
+    assert_eq!(lockups.len(), 1);
+    assert_eq!(lockups[0].1.claimed_balance, amount / 2);
+    assert_eq!(lockups[0].1.unclaimed_balance, 0);
+
+    // the refund exceeding the remaining claimed balance comes back as a new lockup
+    let mut lockups = e.get_account_lockups(&users.alice);
+    lockups.sort_by_key(|(index, _)| *index);
+    assert_eq!(lockups.len(), 2);
+    assert_eq!(lockups[0].0, lockup_index);
+    assert_eq!(lockups[0].1.claimed_balance, 0);
+    assert_eq!(lockups[0].1.unclaimed_balance, amount / 2);
+    assert_eq!(lockups[1].1.unclaimed_balance, amount / 2);
+
+    ft_storage_deposit(&users.alice, TOKEN_ID, &users.alice.account_id);
+    let res: WrappedBalance = e.claim(&users.alice).unwrap_json();
+    assert_eq!(res.0, amount);
+    assert_eq!(e.ft_balance_of(&users.alice), amount);
+}
+
+#[test]
+fn test_split_lockup_not_transferable() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+    let amount = d(60000, TOKEN_DECIMALS);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+
+    let (lockup_schedule, vesting_schedule) = lockup_vesting_schedule(amount);
+    let vesting_hash = e.hash_schedule(&vesting_schedule);
+    let lockup_create = LockupCreate {
+        account_id: users.alice.valid_account_id(),
+        schedule: lockup_schedule,
+        vesting_schedule: Some(VestingConditions::Hash(vesting_hash)),
+        transferable: false,
+        clawback_receiver: None,
+    };
+    let balance: WrappedBalance = e.add_lockup(&e.owner, amount, &lockup_create).unwrap_json();
+    assert_eq!(balance.0, amount);
+    let lockup_index = e.get_account_lockups(&users.alice)[0].0;
+
+    // cannot split to another account
+    let res = e.split_lockup(
+        &users.alice,
+        lockup_index,
+        amount / 2,
+        &users.bob.valid_account_id(),
+    );
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("The lockup is not transferable"));
+
+    // cannot split the hidden vesting schedule
+    let res = e.split_lockup(
+        &users.alice,
+        lockup_index,
+        amount / 2,
+        &users.alice.valid_account_id(),
+    );
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status())
+        .contains("Cannot split a lockup with a hidden vesting schedule"));
+}
+
+#[test]
+fn test_merge_lockups() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+    let amount = d(60000, TOKEN_DECIMALS);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+
+    let (lockup_schedule, _vesting_schedule) = lockup_vesting_schedule(amount);
+    let lockup_create = LockupCreate {
+        account_id: users.alice.valid_account_id(),
+        schedule: lockup_schedule,
+        vesting_schedule: None,
+        transferable: false,
+        clawback_receiver: None,
+    };
+    let balance: WrappedBalance = e.add_lockup(&e.owner, amount, &lockup_create).unwrap_json();
+    assert_eq!(balance.0, amount);
+    let balance: WrappedBalance = e
+        .add_lockup(
+            &e.owner,
+            amount,
+            &LockupCreate::new_unlocked(users.alice.valid_account_id(), amount),
+        )
+        .unwrap_json();
+    assert_eq!(balance.0, amount);
+    let lockup_indices: Vec<LockupIndex> = e
+        .get_account_lockups(&users.alice)
+        .into_iter()
+        .map(|(index, _)| index)
+        .collect();
+    assert_eq!(lockup_indices.len(), 2);
+
+    // claim the unlocked lockup
+    let res: WrappedBalance = e.claim(&users.alice).unwrap_json();
+    assert_eq!(res.0, amount);
+
+    // at least two lockups are required
+    let res = e.merge_lockups(&users.alice, vec![lockup_indices[0]]);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("At least two lockups are required"));
+
+    // only own lockups can be merged
+    let res = e.merge_lockups(&users.bob, lockup_indices.clone());
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("lockup not found for account"));
+
+    let new_lockup_index: LockupIndex = e
+        .merge_lockups(&users.alice, lockup_indices.clone())
+        .unwrap_json();
+    let lockups = e.get_account_lockups(&users.alice);
+    assert_eq!(lockups.len(), 1);
+    assert_eq!(lockups[0].0, new_lockup_index);
+    assert_eq!(lockups[0].1.total_balance, amount * 2);
+    assert_eq!(lockups[0].1.claimed_balance, amount);
+    assert_eq!(lockups[0].1.unclaimed_balance, 0);
+
+    // claiming the rest
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC * 4 + 1);
+    let res: WrappedBalance = e.claim(&users.alice).unwrap_json();
+    assert_eq!(res.0, amount);
+    assert_eq!(e.ft_balance_of(&users.alice), amount * 2);
+}
+
+#[test]
+fn test_merge_terminatable_lockups() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+    let amount = d(60000, TOKEN_DECIMALS);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+
+    let (lockup_schedule, vesting_schedule) = lockup_vesting_schedule(amount);
+    let vesting_hash = e.hash_schedule(&vesting_schedule);
+    let lockup_create = LockupCreate {
+        account_id: users.alice.valid_account_id(),
+        schedule: lockup_schedule,
+        vesting_schedule: Some(VestingConditions::Hash(vesting_hash)),
+        transferable: false,
+        clawback_receiver: None,
+    };
+    let balance: WrappedBalance = e.add_lockup(&e.owner, amount, &lockup_create).unwrap_json();
+    assert_eq!(balance.0, amount);
+    let balance: WrappedBalance = e
+        .add_lockup(
+            &e.owner,
+            amount,
+            &LockupCreate::new_unlocked(users.alice.valid_account_id(), amount),
+        )
+        .unwrap_json();
+    assert_eq!(balance.0, amount);
+
+    let res = e.merge_lockups(&users.alice, vec![0, 1]);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Cannot merge terminatable lockup #0"));
+    assert_eq!(e.get_account_lockups(&users.alice).len(), 2);
+}
@@ -0,0 +1,142 @@
+mod setup;
+
+use crate::setup::*;
+
+#[test]
+fn test_staged_upgrade() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+    let amount = d(10000, TOKEN_DECIMALS);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+
+    let lockup_create = LockupCreate::new_unlocked(users.alice.valid_account_id(), amount);
+    let res: WrappedBalance = e.add_lockup(&e.owner, amount, &lockup_create).unwrap_json();
+    assert_eq!(res.0, amount);
+
+    let code_hash = code_hash(&FT_LOCKUP_WASM_BYTES);
+
+    // only admins can stage upgrades
+    let res = e.stage_upgrade(&users.alice, code_hash);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Not an admin"));
+
+    let res = e.deploy_staged_upgrade(&e.owner, &FT_LOCKUP_WASM_BYTES);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("No staged upgrade"));
+
+    let res = e.stage_upgrade(&e.owner, code_hash);
+    assert!(res.is_ok());
+    let deploy_timestamp = GENESIS_TIMESTAMP_SEC + DEFAULT_UPGRADE_DELAY_SEC;
+    assert_eq!(
+        e.get_staged_upgrade(),
+        Some(StagedUpgrade {
+            code_hash,
+            deploy_timestamp,
+        })
+    );
+
+    // the upgrade can't be deployed before the delay
+    e.set_time_sec(deploy_timestamp - 1);
+    let res = e.deploy_staged_upgrade(&e.owner, &FT_LOCKUP_WASM_BYTES);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("The upgrade can't be deployed before"));
+
+    e.set_time_sec(deploy_timestamp);
+    let res = e.deploy_staged_upgrade(&users.alice, &FT_LOCKUP_WASM_BYTES);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Not an admin"));
+
+    // only the staged code can be deployed
+    let res = e.deploy_staged_upgrade(&e.owner, &FUNGIBLE_TOKEN_WASM_BYTES);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("The code doesn't match the staged upgrade"));
+
+    let res = e.deploy_staged_upgrade(&e.owner, &FT_LOCKUP_WASM_BYTES);
+    assert!(res.is_ok());
+    assert!(e.get_staged_upgrade().is_none());
+    assert_eq!(e.get_state_version(), 2);
+    let lockups = e.get_account_lockups(&users.alice);
+    assert_eq!(lockups.len(), 1);
+    assert_eq!(lockups[0].1.total_balance, amount);
+}
+
+#[test]
+fn test_cancel_staged_upgrade() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+
+    let res = e.set_upgrade_delay(&users.alice, 0);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Not an admin"));
+
+    let res = e.set_upgrade_delay(&e.owner, ONE_DAY_SEC);
+    assert!(res.is_ok());
+    let start_timestamp = GENESIS_TIMESTAMP_SEC + DEFAULT_UPGRADE_DELAY_SEC;
+    e.set_time_sec(start_timestamp);
+    assert_eq!(e.get_upgrade_delay(), ONE_DAY_SEC);
+
+    let code_hash = code_hash(&FT_LOCKUP_WASM_BYTES);
+    let res = e.stage_upgrade(&e.owner, code_hash);
+    assert!(res.is_ok());
+    assert_eq!(
+        e.get_staged_upgrade().unwrap().deploy_timestamp,
+        start_timestamp + ONE_DAY_SEC
+    );
+
+    let res = e.cancel_staged_upgrade(&users.alice);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Not an admin"));
+
+    let res = e.cancel_staged_upgrade(&e.owner);
+    assert!(res.is_ok());
+    assert!(e.get_staged_upgrade().is_none());
+
+    e.set_time_sec(start_timestamp + ONE_DAY_SEC);
+    let res = e.deploy_staged_upgrade(&e.owner, &FT_LOCKUP_WASM_BYTES);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("No staged upgrade"));
+}
+
+#[test]
+fn test_shorter_upgrade_delay_is_timelocked() {
+    let e = Env::init(None);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+    let code_hash = code_hash(&FT_LOCKUP_WASM_BYTES);
+
+    // a shorter delay takes effect only after the current delay
+    let res = e.set_upgrade_delay(&e.owner, 0);
+    assert!(res.is_ok());
+    let effective_timestamp = GENESIS_TIMESTAMP_SEC + DEFAULT_UPGRADE_DELAY_SEC;
+    assert_eq!(e.get_upgrade_delay(), DEFAULT_UPGRADE_DELAY_SEC);
+    assert_eq!(
+        e.get_staged_upgrade_delay(),
+        Some(StagedUpgradeDelay {
+            upgrade_delay_sec: 0,
+            effective_timestamp,
+        })
+    );
+    let res = e.stage_upgrade(&e.owner, code_hash);
+    assert!(res.is_ok());
+    let res = e.deploy_staged_upgrade(&e.owner, &FT_LOCKUP_WASM_BYTES);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("The upgrade can't be deployed before"));
+    assert_eq!(
+        e.get_staged_upgrade().unwrap().deploy_timestamp,
+        effective_timestamp
+    );
+
+    // a longer delay takes effect immediately and replaces the staged one
+    let res = e.set_upgrade_delay(&e.owner, DEFAULT_UPGRADE_DELAY_SEC * 2);
+    assert!(res.is_ok());
+    assert_eq!(e.get_upgrade_delay(), DEFAULT_UPGRADE_DELAY_SEC * 2);
+    assert!(e.get_staged_upgrade_delay().is_none());
+    e.set_time_sec(effective_timestamp);
+    assert_eq!(e.get_upgrade_delay(), DEFAULT_UPGRADE_DELAY_SEC * 2);
+
+    let res = e.set_upgrade_delay(&e.owner, 0);
+    assert!(res.is_ok());
+    e.set_time_sec(effective_timestamp + DEFAULT_UPGRADE_DELAY_SEC * 2);
+    assert_eq!(e.get_upgrade_delay(), 0);
+    assert!(e.get_staged_upgrade_delay().is_none());
+}
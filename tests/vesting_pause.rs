@@ -0,0 +1,58 @@
+mod setup;
+
+use crate::setup::*;
+
+#[test]
+fn test_vesting_pause() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+    let amount = d(60000, TOKEN_DECIMALS);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+
+    let (lockup_schedule, vesting_schedule) = lockup_vesting_schedule(amount);
+    let lockup_create = LockupCreate {
+        account_id: users.alice.valid_account_id(),
+        schedule: lockup_schedule,
+        vesting_schedule: Some(VestingConditions::Schedule(vesting_schedule)),
+        transferable: false,
+        clawback_receiver: None,
+    };
+    let balance: WrappedBalance = e.add_lockup(&e.owner, amount, &lockup_create).unwrap_json();
+    assert_eq!(balance.0, amount);
+    let lockup_index = e.get_account_lockups(&users.alice)[0].0;
+
+    // the leave starts a year before the lockup ends
+    let paused_since = GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC * 3;
+    e.set_time_sec(paused_since);
+    let res = e.pause_vesting(&users.alice, lockup_index);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Not the termination beneficiary"));
+    let res = e.resume_vesting(&e.owner, lockup_index);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("The vesting is not paused"));
+
+    let res = e.pause_vesting(&e.owner, lockup_index);
+    assert!(res.is_ok());
+    let lockup = e.get_lockup(lockup_index);
+    assert_eq!(lockup.vesting_paused_since, Some(paused_since));
+    assert_eq!(lockup.unclaimed_balance, amount * 3 / 8);
+    let res = e.pause_vesting(&e.owner, lockup_index);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("The vesting is already paused"));
+
+    // nothing is unlocked during the leave
+    e.set_time_sec(paused_since + ONE_YEAR_SEC);
+    assert_eq!(e.get_lockup(lockup_index).unclaimed_balance, amount * 3 / 8);
+
+    let res = e.resume_vesting(&e.owner, lockup_index);
+    assert!(res.is_ok());
+    let lockup = e.get_lockup(lockup_index);
+    assert!(lockup.vesting_paused_since.is_none());
+    assert_eq!(lockup.unclaimed_balance, amount * 3 / 8);
+
+    // the rest of the lockup is shifted by the leave duration
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC * 5);
+    assert_eq!(e.get_lockup(lockup_index).unclaimed_balance, amount * 3 / 4);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC * 5 + 1);
+    assert_eq!(e.get_lockup(lockup_index).unclaimed_balance, amount);
+}
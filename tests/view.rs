@@ -35,10 +35,12 @@ fn test_validate_schedule() {
         Checkpoint {
             timestamp: GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC * 4,
             balance: 0,
+            segment: SegmentKind::Linear,
         },
         Checkpoint {
             timestamp: GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC * 4 + 1,
             balance: amount,
+            segment: SegmentKind::Linear,
         },
     ]);
     let res = e.validate_schedule(